@@ -1,6 +1,10 @@
 use std::path::PathBuf;
 
 fn main() {
+    // Web API-only builds (`--no-default-features`) never load libsteam_api.
+    if std::env::var_os("CARGO_FEATURE_NATIVE").is_none() {
+        return;
+    }
     set_rpath();
     copy_steam_api_library();
 }
@@ -0,0 +1,258 @@
+use anyhow::{bail, Result};
+use colored::Colorize;
+use unicode_width::UnicodeWidthStr;
+
+use crate::charts;
+use crate::display::truncate;
+use crate::games::format_date;
+use crate::steam::{AchievementDetail, Game, GameAchievements};
+
+const BAR_WIDTH: usize = 20;
+const MAX_NAME_WIDTH: usize = 40;
+const MAX_CANDIDATES: usize = 5;
+
+/// Resolves a game name to an owned app ID: an exact (case-insensitive)
+/// match wins, otherwise the name must match exactly one game partially.
+pub fn find_game(games: &[Game], query: &str) -> Result<(u32, String)> {
+    let needle = query.to_lowercase();
+    let named = games
+        .iter()
+        .filter_map(|g| g.name.as_deref().map(|name| (g.appid, name)));
+
+    if let Some((appid, name)) = named
+        .clone()
+        .find(|(_, name)| name.to_lowercase() == needle)
+    {
+        return Ok((appid, name.to_string()));
+    }
+
+    let mut matches: Vec<(u32, &str)> = named
+        .filter(|(_, name)| name.to_lowercase().contains(&needle))
+        .collect();
+    match matches.len() {
+        0 => bail!("No owned game matches '{}'", query),
+        1 => {
+            let (appid, name) = matches.remove(0);
+            Ok((appid, name.to_string()))
+        }
+        n => {
+            matches.sort_by_key(|(_, name)| name.to_lowercase());
+            let listed: Vec<String> = matches
+                .iter()
+                .take(MAX_CANDIDATES)
+                .map(|(appid, name)| format!("{} ({})", name, appid))
+                .collect();
+            let more = if n > MAX_CANDIDATES {
+                format!(" and {} more", n - MAX_CANDIDATES)
+            } else {
+                String::new()
+            };
+            bail!(
+                "'{}' matches {} games: {}{}. Use a more specific name or the app ID.",
+                query,
+                n,
+                listed.join(", "),
+                more
+            )
+        }
+    }
+}
+
+/// Renders a completion bar followed by every achievement, as fetched
+/// (rarest first).
+pub fn render(details: &GameAchievements) -> String {
+    let unlocked = details.unlocked_count();
+    let total = details.achievements.len();
+    let fraction = if total == 0 {
+        0.0
+    } else {
+        unlocked as f64 / total as f64
+    };
+    let mut out = format!(
+        "{} {}\n[{}] {}/{} ({:.0}%)\n\n",
+        details.game.bold().cyan(),
+        format!("({})", details.appid).dimmed(),
+        charts::meter(fraction, BAR_WIDTH),
+        unlocked,
+        total,
+        percent(unlocked, total)
+    );
+
+    let name_width = details
+        .achievements
+        .iter()
+        .map(|a| a.name.width())
+        .max()
+        .unwrap_or(0)
+        .min(MAX_NAME_WIDTH);
+    for achievement in &details.achievements {
+        out.push_str(&achievement_line(achievement, name_width));
+        out.push('\n');
+        if let Some(description) = &achievement.description {
+            out.push_str(&format!("    {}\n", description.dimmed()));
+        }
+    }
+    out
+}
+
+fn achievement_line(achievement: &AchievementDetail, name_width: usize) -> String {
+    let mark = if achievement.achieved {
+        "✓".green()
+    } else {
+        "✗".dimmed()
+    };
+    let rarity = achievement
+        .global_percent
+        .map_or("-".to_string(), |p| format!("{:.1}%", p));
+    let unlocked = match (achievement.achieved, achievement.unlock_time) {
+        (true, Some(time)) => format_date(time),
+        (true, None) => "unlocked".to_string(),
+        (false, _) => "locked".to_string(),
+    };
+    let hidden = if achievement.hidden {
+        format!("  {}", "hidden".dimmed())
+    } else {
+        String::new()
+    };
+    format!(
+        "{} {}  {:>6}  {}{}",
+        mark,
+        truncate(&achievement.name, name_width),
+        rarity,
+        unlocked,
+        hidden
+    )
+}
+
+fn percent(unlocked: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        unlocked as f64 / total as f64 * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(appid: u32, name: &str) -> Game {
+        Game {
+            appid,
+            name: Some(name.to_string()),
+            playtime_forever: 0,
+            playtime_2weeks: 0,
+            rtime_last_played: 0,
+        }
+    }
+
+    fn library() -> Vec<Game> {
+        vec![
+            game(400, "Portal"),
+            game(620, "Portal 2"),
+            game(570, "Dota 2"),
+            Game {
+                name: None,
+                ..game(1, "")
+            },
+        ]
+    }
+
+    fn detail(name: &str, achieved: bool, percent: Option<f64>) -> AchievementDetail {
+        AchievementDetail {
+            api_name: name.to_uppercase(),
+            name: name.to_string(),
+            description: None,
+            hidden: false,
+            achieved,
+            unlock_time: None,
+            global_percent: percent,
+        }
+    }
+
+    fn strip_ansi(s: &str) -> String {
+        let mut result = String::new();
+        let mut in_escape = false;
+        for c in s.chars() {
+            match (in_escape, c) {
+                (false, '\x1b') => in_escape = true,
+                (true, 'm') => in_escape = false,
+                (false, c) => result.push(c),
+                _ => {}
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_find_game_prefers_exact_match() {
+        assert_eq!(
+            find_game(&library(), "portal").unwrap(),
+            (400, "Portal".to_string())
+        );
+        assert_eq!(
+            find_game(&library(), "dota").unwrap(),
+            (570, "Dota 2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_game_reports_missing_and_ambiguous_names() {
+        let err = find_game(&library(), "half-life").unwrap_err().to_string();
+        assert_eq!(err, "No owned game matches 'half-life'");
+
+        let err = find_game(&library(), "2").unwrap_err().to_string();
+        assert!(err.starts_with("'2' matches 2 games: Dota 2 (570), Portal 2 (620)."));
+    }
+
+    #[test]
+    fn test_render_shows_bar_and_achievement_states() {
+        let mut unlocked = detail("First Steps", true, Some(92.0));
+        unlocked.unlock_time = Some(1_700_000_000);
+        unlocked.description = Some("Start the game".to_string());
+        let mut secret = detail("Secret", false, Some(1.5));
+        secret.hidden = true;
+        let details = GameAchievements {
+            appid: 620,
+            game: "Portal 2".to_string(),
+            achievements: vec![secret, unlocked, detail("Unrated", true, None)],
+        };
+
+        let text = strip_ansi(&render(&details));
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "Portal 2 (620)");
+        assert_eq!(
+            lines[1],
+            format!("[{}{}] 2/3 (67%)", "█".repeat(13), "░".repeat(7))
+        );
+        assert_eq!(lines[3], "✗ Secret         1.5%  locked  hidden");
+        assert_eq!(lines[4], "✓ First Steps   92.0%  2023-11-14");
+        assert_eq!(lines[5], "    Start the game");
+        assert_eq!(lines[6], "✓ Unrated           -  unlocked");
+    }
+
+    #[test]
+    fn test_completion_bar_bounds() {
+        let bar = |unlocked: usize, total: usize| {
+            let details = GameAchievements {
+                appid: 1,
+                game: "Game".to_string(),
+                achievements: (0..total)
+                    .map(|i| detail(&format!("A{i}"), i < unlocked, None))
+                    .collect(),
+            };
+            let text = strip_ansi(&render(&details));
+            text.lines()
+                .nth(1)
+                .unwrap()
+                .split(' ')
+                .next()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(bar(0, 0), format!("[{}]", "░".repeat(20)));
+        assert_eq!(bar(5, 5), format!("[{}]", "█".repeat(20)));
+        assert_eq!(bar(99, 100), format!("[{}░]", "█".repeat(19)));
+        assert_eq!(percent(0, 0), 0.0);
+    }
+}
@@ -3,25 +3,64 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+/// Bumped when the entry layout changes; older caches are discarded.
+const CACHE_VERSION: u32 = 3;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedAchievement {
     pub last_played: u64,
     pub achieved: u32,
     pub total: u32,
-    pub rarest_name: Option<String>,
-    pub rarest_percent: Option<f64>,
+    /// The game's rarest unlocked achievements, rarest first.
+    #[serde(default)]
+    pub rarest: Vec<CachedRarest>,
+    /// The most entries `rarest` was allowed to keep; None when it is the
+    /// game's full list.
+    #[serde(default)]
+    pub rarest_limit: Option<usize>,
+}
+
+impl CachedAchievement {
+    /// Whether `rarest` holds the game's `count` rarest unlocks, i.e. was
+    /// not cut shorter than that.
+    pub fn covers_rarest(&self, count: usize) -> bool {
+        self.rarest_limit
+            .is_none_or(|limit| count <= limit || self.rarest.len() < limit)
+    }
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedRarest {
+    pub name: String,
+    pub percent: Option<f64>,
+}
+
+/// Per-game achievement counts stored in `~/.cache/steamfetch/achievements.json`.
+///
+/// Entries are keyed by app ID and invalidated when the game's last-played
+/// time changes.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct AchievementCache {
+    #[serde(default)]
+    version: u32,
     games: HashMap<u32, CachedAchievement>,
 }
 
+impl Default for AchievementCache {
+    fn default() -> Self {
+        Self {
+            version: CACHE_VERSION,
+            games: HashMap::new(),
+        }
+    }
+}
+
 impl AchievementCache {
     pub fn load() -> Self {
         cache_path()
             .and_then(|p| fs::read_to_string(p).ok())
-            .and_then(|s| serde_json::from_str(&s).ok())
+            .and_then(|s| serde_json::from_str::<Self>(&s).ok())
+            .filter(|cache| cache.version == CACHE_VERSION)
             .unwrap_or_default()
     }
 
@@ -40,13 +79,28 @@ impl AchievementCache {
             .filter(|c| c.last_played == last_played)
     }
 
-    pub fn set(
+    /// Stores a game with its full list of rated unlocks.
+    pub fn set<'a>(
         &mut self,
         appid: u32,
         last_played: u64,
         achieved: u32,
         total: u32,
-        rarest: Option<(&str, f64)>,
+        rarest: impl IntoIterator<Item = (&'a str, f64)>,
+    ) {
+        self.set_with_rarest_limit(appid, last_played, achieved, total, rarest, None);
+    }
+
+    /// Like [`AchievementCache::set`], for a `rarest` list that was cut to
+    /// at most `rarest_limit` entries.
+    pub fn set_with_rarest_limit<'a>(
+        &mut self,
+        appid: u32,
+        last_played: u64,
+        achieved: u32,
+        total: u32,
+        rarest: impl IntoIterator<Item = (&'a str, f64)>,
+        rarest_limit: Option<usize>,
     ) {
         self.games.insert(
             appid,
@@ -54,8 +108,14 @@ impl AchievementCache {
                 last_played,
                 achieved,
                 total,
-                rarest_name: rarest.map(|(n, _)| n.to_string()),
-                rarest_percent: rarest.map(|(_, p)| p),
+                rarest: rarest
+                    .into_iter()
+                    .map(|(name, percent)| CachedRarest {
+                        name: name.to_string(),
+                        percent: Some(percent),
+                    })
+                    .collect(),
+                rarest_limit,
             },
         );
     }
@@ -83,8 +143,8 @@ mod tests {
         assert_eq!(entry.last_played, 1000);
         assert_eq!(entry.achieved, 5);
         assert_eq!(entry.total, 10);
-        assert_eq!(entry.rarest_name.as_deref(), Some("Rare"));
-        assert_eq!(entry.rarest_percent, Some(1.5));
+        assert_eq!(entry.rarest[0].name, "Rare");
+        assert_eq!(entry.rarest[0].percent, Some(1.5));
     }
 
     #[test]
@@ -106,8 +166,32 @@ mod tests {
         let mut cache = AchievementCache::default();
         cache.set(7, 500, 1, 2, None);
         let entry = cache.get(7, 500).unwrap();
-        assert!(entry.rarest_name.is_none());
-        assert!(entry.rarest_percent.is_none());
+        assert!(entry.rarest.is_empty());
+    }
+
+    #[test]
+    fn test_set_keeps_several_rarest_in_order() {
+        let mut cache = AchievementCache::default();
+        cache.set(7, 500, 3, 4, [("First", 0.5), ("Second", 2.0)]);
+        let entry = cache.get(7, 500).unwrap();
+        let names: Vec<&str> = entry.rarest.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["First", "Second"]);
+        assert_eq!(entry.rarest[1].percent, Some(2.0));
+    }
+
+    #[test]
+    fn test_cut_rarest_list_covers_only_its_limit() {
+        let mut cache = AchievementCache::default();
+        cache.set(1, 0, 2, 2, [("A", 1.0), ("B", 2.0)]);
+        assert!(cache.get(1, 0).unwrap().covers_rarest(50));
+
+        cache.set_with_rarest_limit(2, 0, 3, 3, [("A", 1.0), ("B", 2.0)], Some(2));
+        let cut = cache.get(2, 0).unwrap();
+        assert!(cut.covers_rarest(2));
+        assert!(!cut.covers_rarest(3));
+
+        cache.set_with_rarest_limit(3, 0, 1, 3, [("A", 1.0)], Some(2));
+        assert!(cache.get(3, 0).unwrap().covers_rarest(3));
     }
 
     #[test]
@@ -118,8 +202,8 @@ mod tests {
         assert!(cache.get(1, 100).is_none());
         let entry = cache.get(1, 200).unwrap();
         assert_eq!(entry.achieved, 3);
-        assert_eq!(entry.rarest_name.as_deref(), Some("New"));
-        assert_eq!(entry.rarest_percent, Some(10.0));
+        assert_eq!(entry.rarest[0].name, "New");
+        assert_eq!(entry.rarest[0].percent, Some(10.0));
     }
 
     #[test]
@@ -132,11 +216,11 @@ mod tests {
         let a = restored.get(11, 1234).unwrap();
         assert_eq!(a.achieved, 8);
         assert_eq!(a.total, 12);
-        assert_eq!(a.rarest_name.as_deref(), Some("Legend"));
-        assert_eq!(a.rarest_percent, Some(0.25));
+        assert_eq!(a.rarest[0].name, "Legend");
+        assert_eq!(a.rarest[0].percent, Some(0.25));
         let b = restored.get(22, 5678).unwrap();
         assert_eq!(b.total, 50);
-        assert!(b.rarest_name.is_none());
+        assert!(b.rarest.is_empty());
     }
 
     #[test]
@@ -151,7 +235,7 @@ mod tests {
     #[test]
     fn test_serde_roundtrip_handles_nan_percent_as_json_null() {
         // serde_json represents non-finite floats (NaN/Inf) as JSON null
-        // rather than failing. A cache entry containing rarest_percent =
+        // rather than failing. A cache entry containing a rarest percent of
         // f64::NAN therefore serializes successfully, and the resulting
         // null deserializes back into Option::None — proving that the
         // pipeline survives non-finite floats end-to-end without touching
@@ -162,12 +246,12 @@ mod tests {
 
         let json = serde_json::to_string(&cache).expect("NaN should serialize as JSON null");
         assert!(
-            json.contains("\"rarest_percent\":null"),
+            json.contains("\"percent\":null"),
             "NaN should encode as JSON null, got: {json}",
         );
         assert!(
-            json.contains("\"rarest_name\":\"Edge Float\""),
-            "rarest_name should round-trip verbatim, got: {json}",
+            json.contains("\"name\":\"Edge Float\""),
+            "rarest name should round-trip verbatim, got: {json}",
         );
 
         let restored: AchievementCache =
@@ -175,9 +259,9 @@ mod tests {
         let entry = restored.get(99, 4242).expect("entry must persist");
         assert_eq!(entry.achieved, 3);
         assert_eq!(entry.total, 7);
-        assert_eq!(entry.rarest_name.as_deref(), Some("Edge Float"));
+        assert_eq!(entry.rarest[0].name, "Edge Float");
         assert!(
-            entry.rarest_percent.is_none(),
+            entry.rarest[0].percent.is_none(),
             "JSON null deserializes Option<f64> as None"
         );
     }
@@ -192,12 +276,12 @@ mod tests {
         cache.set(7, 0, 0, 0, Some(("Inf Sentinel", f64::INFINITY)));
 
         let json = serde_json::to_string(&cache).expect("Inf should serialize as JSON null");
-        assert!(json.contains("\"rarest_percent\":null"));
+        assert!(json.contains("\"percent\":null"));
 
         let restored: AchievementCache = serde_json::from_str(&json).unwrap();
         let entry = restored.get(7, 0).expect("entry must persist");
-        assert_eq!(entry.rarest_name.as_deref(), Some("Inf Sentinel"));
-        assert!(entry.rarest_percent.is_none());
+        assert_eq!(entry.rarest[0].name, "Inf Sentinel");
+        assert!(entry.rarest[0].percent.is_none());
     }
 
     #[cfg(target_os = "linux")]
@@ -281,6 +365,26 @@ mod tests {
             let _ = std::fs::remove_dir_all(&root);
         }
 
+        #[test]
+        fn test_load_discards_cache_from_older_version() {
+            let _guard = lock_env();
+            let root = unique_cache_root("old-version");
+            let _scope = EnvScope::set(&root);
+
+            let path = cache_path().unwrap();
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(
+                &path,
+                r#"{"games":{"1":{"last_played":0,"achieved":1,"total":2,"rarest_name":"Old","rarest_percent":1.0}}}"#,
+            )
+            .unwrap();
+
+            let cache = AchievementCache::load();
+            assert!(cache.get(1, 0).is_none());
+
+            let _ = std::fs::remove_dir_all(&root);
+        }
+
         #[test]
         fn test_save_default_cache_persists_empty_file() {
             let _guard = lock_env();
@@ -359,12 +463,12 @@ mod tests {
             let a = loaded.get(101, 5000).expect("entry should persist");
             assert_eq!(a.achieved, 7);
             assert_eq!(a.total, 10);
-            assert_eq!(a.rarest_name.as_deref(), Some("Rare"));
-            assert_eq!(a.rarest_percent, Some(0.5));
+            assert_eq!(a.rarest[0].name, "Rare");
+            assert_eq!(a.rarest[0].percent, Some(0.5));
 
             let b = loaded.get(202, 6000).expect("entry should persist");
             assert_eq!(b.total, 5);
-            assert!(b.rarest_name.is_none());
+            assert!(b.rarest.is_empty());
 
             let _ = std::fs::remove_dir_all(&root);
         }
@@ -0,0 +1,379 @@
+//! `steamfetch card`: a self-contained SVG stats card for profile READMEs,
+//! in the spirit of github-readme-stats.
+//!
+//! The output depends only on the stats and the avatar, so the same input
+//! always produces the same file.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::ValueEnum;
+use image::DynamicImage;
+use std::fmt::Write;
+use std::io::Cursor;
+
+use crate::config::DisplayConfig;
+use crate::display::{format_number, stat_rows, StatRow};
+use crate::html::escape;
+use crate::modules::{Module, ModuleKind};
+use crate::steam::SteamStats;
+use crate::theme::{Rgb, Theme};
+
+const PADDING: u32 = 24;
+const ROW_HEIGHT: u32 = 24;
+const VALUE_COLUMN: u32 = 110;
+const FONT: &str = "'Segoe UI', Ubuntu, 'Helvetica Neue', sans-serif";
+/// The stats shown on the card; the rest need a terminal's worth of room.
+const CARD_STATS: [ModuleKind; 4] = [
+    ModuleKind::Level,
+    ModuleKind::Games,
+    ModuleKind::Playtime,
+    ModuleKind::Achievements,
+];
+
+/// Card background when the theme sets none.
+const BACKGROUND: Rgb = Rgb(0x0d, 0x11, 0x17);
+/// Stands in for the terminal's cyan when the theme has no accent.
+const ACCENT: Rgb = Rgb(0x39, 0xc5, 0xcf);
+
+/// The card's colors, picked from the terminal theme.
+struct Palette {
+    background: Rgb,
+    border: Rgb,
+    text: Rgb,
+    muted: Rgb,
+    accent: Rgb,
+}
+
+impl Palette {
+    fn new(theme: &Theme) -> Self {
+        let background = theme.background.unwrap_or(BACKGROUND);
+        let Rgb(r, g, b) = background;
+        let luma = 0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64;
+        let text = if luma > 140.0 {
+            Rgb(0x33, 0x33, 0x33)
+        } else {
+            Rgb(0xc9, 0xd1, 0xd9)
+        };
+        Palette {
+            background,
+            border: background.lerp(text, 0.2),
+            text,
+            muted: theme.label.unwrap_or(text.lerp(background, 0.35)),
+            accent: theme.accent.unwrap_or(ACCENT),
+        }
+    }
+}
+
+/// How much the card shows; larger cards are also wider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CardSize {
+    /// Stats only
+    Small,
+    /// Stats and the top 3 games
+    Medium,
+    /// Stats, the top 5 games and the rarest achievement
+    Large,
+}
+
+struct Metrics {
+    width: u32,
+    avatar: u32,
+    top_games: usize,
+    rarest: bool,
+}
+
+impl CardSize {
+    fn metrics(self) -> Metrics {
+        match self {
+            CardSize::Small => Metrics {
+                width: 400,
+                avatar: 48,
+                top_games: 0,
+                rarest: false,
+            },
+            CardSize::Medium => Metrics {
+                width: 480,
+                avatar: 64,
+                top_games: 3,
+                rarest: false,
+            },
+            CardSize::Large => Metrics {
+                width: 560,
+                avatar: 80,
+                top_games: 5,
+                rarest: true,
+            },
+        }
+    }
+}
+
+/// Renders the card; without an avatar image, the username's initial is
+/// drawn in its place.
+pub fn render(
+    stats: &SteamStats,
+    theme: &Theme,
+    size: CardSize,
+    avatar: Option<&DynamicImage>,
+) -> String {
+    let palette = Palette::new(theme);
+    let metrics = size.metrics();
+    let width = metrics.width;
+    let display = DisplayConfig {
+        modules: CARD_STATS.into_iter().map(Module::new).collect(),
+        theme: theme.clone(),
+        ..DisplayConfig::default()
+    };
+    let rows: Vec<StatRow> = stat_rows(stats, &display);
+
+    let mut body = String::new();
+    let mut y = PADDING + metrics.avatar + 16;
+    let _ = writeln!(
+        body,
+        r#"  <line x1="{PADDING}" y1="{y}" x2="{}" y2="{y}" stroke="{}"/>"#,
+        width - PADDING,
+        palette.border
+    );
+    y += 4;
+    for (i, row) in rows.iter().enumerate() {
+        y += ROW_HEIGHT;
+        let _ = writeln!(
+            body,
+            r#"  <text x="{PADDING}" y="{y}" class="label">{}</text>
+  <text x="{}" y="{y}">{}</text>
+  <text x="{}" y="{y}" class="title" text-anchor="end" style="fill: url(#tier-{i})">{}</text>"#,
+            row.label,
+            PADDING + VALUE_COLUMN,
+            escape(&row.value),
+            width - PADDING,
+            row.title.unwrap_or_default()
+        );
+    }
+
+    let name_chars = ((width - 2 * PADDING - 80) / 8) as usize;
+    let games: Vec<_> = stats.top_games.iter().take(metrics.top_games).collect();
+    if !games.is_empty() {
+        y += 16;
+        section(&mut body, &mut y, "Top Played");
+        for (i, game) in games.iter().enumerate() {
+            y += ROW_HEIGHT;
+            entry(
+                &mut body,
+                y,
+                width,
+                &format!("{}. {}", i + 1, game.name),
+                name_chars,
+                &format!("{}h", format_number(game.playtime_hours())),
+            );
+        }
+    }
+
+    let rarest = stats
+        .achievement_stats
+        .as_ref()
+        .and_then(|a| a.rarest.as_ref())
+        .filter(|_| metrics.rarest);
+    if let Some(rarest) = rarest {
+        y += 16;
+        section(&mut body, &mut y, "Rarest Achievement");
+        y += ROW_HEIGHT;
+        entry(
+            &mut body,
+            y,
+            width,
+            &format!("{} — {}", rarest.name, rarest.game),
+            name_chars,
+            &format!("{:.1}%", rarest.percent),
+        );
+    }
+    let height = y + PADDING;
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" role="img" aria-label="{name}'s Steam stats">
+  <title>{name}'s Steam stats</title>
+  <style>
+    text {{ font-family: {FONT}; font-size: 14px; fill: {text}; }}
+    .name {{ font-size: 20px; font-weight: 600; fill: {accent}; }}
+    .heading {{ font-weight: 600; fill: {accent}; }}
+    .label, .muted {{ fill: {muted}; }}
+    .title {{ font-size: 13px; font-weight: 600; }}
+  </style>
+  <defs>
+"#,
+        name = escape(&stats.username),
+        text = palette.text,
+        accent = palette.accent,
+        muted = palette.muted,
+    );
+    for (i, row) in rows.iter().enumerate() {
+        let _ = writeln!(
+            svg,
+            r#"    <linearGradient id="tier-{i}"><stop offset="0" stop-color="{}"/><stop offset="1" stop-color="{}"/></linearGradient>"#,
+            row.color,
+            row.color.scale(1.0 - theme.title_fade)
+        );
+    }
+    let (radius, center) = (metrics.avatar / 2, PADDING + metrics.avatar / 2);
+    let _ = writeln!(
+        svg,
+        r#"    <clipPath id="avatar"><circle cx="{center}" cy="{center}" r="{radius}"/></clipPath>
+  </defs>
+  <rect x="0.5" y="0.5" width="{}" height="{}" rx="8" fill="{}" stroke="{}"/>"#,
+        width - 1,
+        height - 1,
+        palette.background,
+        palette.border
+    );
+    match avatar.and_then(|img| png_data_uri(img, metrics.avatar * 2)) {
+        Some(uri) => {
+            let _ = writeln!(
+                svg,
+                r#"  <image x="{PADDING}" y="{PADDING}" width="{0}" height="{0}" href="{uri}" clip-path="url(#avatar)"/>"#,
+                metrics.avatar
+            );
+        }
+        None => {
+            let initial = stats
+                .username
+                .chars()
+                .next()
+                .map(|c| c.to_uppercase().to_string())
+                .unwrap_or_default();
+            let _ = writeln!(
+                svg,
+                r#"  <circle cx="{center}" cy="{center}" r="{radius}" fill="{}"/>
+  <text x="{center}" y="{center}" text-anchor="middle" dominant-baseline="central" style="font-size: {}px; font-weight: 600; fill: {}">{}</text>"#,
+                palette.accent,
+                radius,
+                palette.background,
+                escape(&initial)
+            );
+        }
+    }
+    let text_x = PADDING + metrics.avatar + 16;
+    let _ = writeln!(
+        svg,
+        r#"  <text x="{text_x}" y="{}" class="name">{}</text>
+  <text x="{text_x}" y="{}" class="muted">on Steam</text>"#,
+        center - 2,
+        escape(&truncate(&stats.username, name_chars)),
+        center + 18
+    );
+    svg.push_str(&body);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn section(body: &mut String, y: &mut u32, heading: &str) {
+    *y += ROW_HEIGHT;
+    let _ = writeln!(
+        body,
+        r#"  <text x="{PADDING}" y="{y}" class="heading">{heading}</text>"#
+    );
+}
+
+/// A list line: text on the left, cut to `max_chars`, and a figure on the right.
+fn entry(body: &mut String, y: u32, width: u32, text: &str, max_chars: usize, figure: &str) {
+    let _ = writeln!(
+        body,
+        r#"  <text x="{PADDING}" y="{y}">{}</text>
+  <text x="{}" y="{y}" class="muted" text-anchor="end">{}</text>"#,
+        escape(&truncate(text, max_chars)),
+        width - PADDING,
+        escape(figure)
+    );
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+fn png_data_uri(img: &DynamicImage, size: u32) -> Option<String> {
+    let resized = img.resize_exact(size, size, image::imageops::FilterType::Lanczos3);
+    let mut png = Cursor::new(Vec::new());
+    resized.write_to(&mut png, image::ImageFormat::Png).ok()?;
+    Some(format!(
+        "data:image/png;base64,{}",
+        STANDARD.encode(png.into_inner())
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEMO_SNAPSHOT: &str = include_str!("../tests/snapshots/card-demo.svg");
+
+    #[test]
+    fn test_render_demo_matches_snapshot() {
+        let svg = render(
+            &crate::demo_stats(),
+            &Theme::default(),
+            CardSize::Medium,
+            None,
+        );
+        assert!(
+            svg == DEMO_SNAPSHOT,
+            "card differs from tests/snapshots/card-demo.svg; if the change is \
+             intended, regenerate it with `steamfetch --demo card --out tests/snapshots/card-demo.svg`\n{svg}"
+        );
+    }
+
+    #[test]
+    fn test_render_sizes_choose_sections() {
+        let stats = crate::demo_stats();
+        let small = render(&stats, &Theme::default(), CardSize::Small, None);
+        assert!(small.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="400""#));
+        assert!(!small.contains("Top Played"));
+        assert!(!small.contains("Member"));
+        let large = render(&stats, &Theme::default(), CardSize::Large, None);
+        assert!(large.contains("Top Played"));
+        assert!(large.contains("DRG Survivor"));
+        assert!(large.contains("Rarest Achievement"));
+        assert!(large.contains("Impossible Task — Dark Souls III"));
+    }
+
+    #[test]
+    fn test_render_embeds_avatar_and_escapes_text() {
+        let mut stats = crate::demo_stats();
+        stats.username = "<Tom & Jerry>".to_string();
+        let avatar = DynamicImage::new_rgb8(4, 4);
+        let svg = render(&stats, &Theme::default(), CardSize::Small, Some(&avatar));
+        assert!(svg.contains(r#"href="data:image/png;base64,"#));
+        assert!(svg.contains("&lt;Tom &amp; Jerry&gt;"));
+        assert!(!svg.contains("<Tom"));
+        let placeholder = render(&stats, &Theme::default(), CardSize::Small, None);
+        assert!(!placeholder.contains("<image"));
+    }
+
+    #[test]
+    fn test_render_follows_theme() {
+        let stats = crate::demo_stats();
+        let deck = Theme::builtin("deck").unwrap();
+        let svg = render(&stats, &deck, CardSize::Small, None);
+        assert!(svg.contains(r##"fill="#23262e""##));
+        assert!(svg.contains(".name { font-size: 20px; font-weight: 600; fill: #1a9fff; }"));
+        assert!(svg.contains(".label, .muted { fill: #ffffff; }"));
+
+        let theme = Theme {
+            background: Some(Rgb(0xff, 0xff, 0xff)),
+            title_palette: vec![Rgb(0x10, 0x20, 0x30)],
+            title_fade: 0.5,
+            ..Theme::default()
+        };
+        let svg = render(&stats, &theme, CardSize::Small, None);
+        assert!(svg.contains("fill: #333333; }"));
+        assert!(svg.contains(
+            r##"<stop offset="0" stop-color="#102030"/><stop offset="1" stop-color="#081018"/>"##
+        ));
+    }
+
+    #[test]
+    fn test_truncate_adds_ellipsis() {
+        assert_eq!(truncate("Portal 2", 8), "Portal 2");
+        assert_eq!(truncate("Portal 2: Peer Review", 8), "Portal …");
+    }
+}
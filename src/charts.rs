@@ -0,0 +1,152 @@
+//! Bar charts drawn with Unicode block characters.
+
+use colored::Colorize;
+use unicode_width::UnicodeWidthStr;
+
+use crate::display::truncate;
+use crate::theme::Theme;
+
+/// Partial cells, from one eighth to seven eighths filled.
+const EIGHTHS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+const FULL: char = '█';
+const TRACK: char = '░';
+const MIN_BAR_WIDTH: usize = 4;
+const MAX_BAR_WIDTH: usize = 24;
+const MIN_LABEL_WIDTH: usize = 6;
+const MAX_LABEL_WIDTH: usize = 24;
+
+/// One row of a [`bar_chart`]: the bar is `fraction` (0.0..=1.0) of the
+/// longest possible bar and `value` is printed after it.
+pub struct ChartRow {
+    pub label: String,
+    pub fraction: f64,
+    pub value: String,
+}
+
+/// A bar exactly `width` cells wide, filled to `fraction` with eighth-cell
+/// precision and padded with spaces.
+pub fn bar(fraction: f64, width: usize) -> String {
+    let eighths = (fraction.clamp(0.0, 1.0) * (width * 8) as f64).round() as usize;
+    let (full, partial) = (eighths / 8, eighths % 8);
+    let mut out: String = std::iter::repeat_n(FULL, full).collect();
+    if partial > 0 {
+        out.push(EIGHTHS[partial - 1]);
+    }
+    out.push_str(&" ".repeat(width - full - usize::from(partial > 0)));
+    out
+}
+
+/// A completion meter: whole filled cells followed by a dimmed track.
+/// Cells are rounded down, so the meter is only full at 1.0.
+pub fn meter(fraction: f64, width: usize) -> String {
+    let filled = (fraction.clamp(0.0, 1.0) * width as f64) as usize;
+    let track: String = std::iter::repeat_n(TRACK, width - filled).collect();
+    format!(
+        "{}{}",
+        std::iter::repeat_n(FULL, filled)
+            .collect::<String>()
+            .green(),
+        track.dimmed()
+    )
+}
+
+/// Rows of `label bar value`, sized so every row fits `inner_width`.
+/// Labels are cut to make room for the bar; when even that is not enough,
+/// there is no chart.
+pub fn bar_chart(rows: &[ChartRow], inner_width: usize, theme: &Theme) -> Vec<String> {
+    let longest_label = rows.iter().map(|r| r.label.width()).max().unwrap_or(0);
+    let value_width = rows.iter().map(|r| r.value.width()).max().unwrap_or(0);
+    let label_width = longest_label
+        .min(MAX_LABEL_WIDTH)
+        .min(inner_width.saturating_sub(value_width + 2 + MIN_BAR_WIDTH));
+    if label_width < longest_label.min(MIN_LABEL_WIDTH) {
+        return Vec::new();
+    }
+    let Some(bar_width) = chart_width(inner_width.saturating_sub(label_width + value_width + 2))
+    else {
+        return Vec::new();
+    };
+    rows.iter()
+        .map(|row| {
+            format!(
+                "{} {} {}",
+                truncate(&row.label, label_width),
+                theme.accent(&bar(row.fraction, bar_width)),
+                row.value
+            )
+        })
+        .collect()
+}
+
+/// The space left for a bar, capped at a readable width; None when not
+/// even the narrowest bar fits.
+pub fn chart_width(available: usize) -> Option<usize> {
+    (available >= MIN_BAR_WIDTH).then(|| available.min(MAX_BAR_WIDTH))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip_ansi(s: &str) -> String {
+        let mut result = String::new();
+        let mut in_escape = false;
+        for c in s.chars() {
+            match (in_escape, c) {
+                (false, '\x1b') => in_escape = true,
+                (true, 'm') => in_escape = false,
+                (false, c) => result.push(c),
+                _ => {}
+            }
+        }
+        result
+    }
+
+    fn row(label: &str, fraction: f64, value: &str) -> ChartRow {
+        ChartRow {
+            label: label.to_string(),
+            fraction,
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_bar_uses_eighth_blocks_and_pads_to_width() {
+        assert_eq!(bar(0.0, 4), "    ");
+        assert_eq!(bar(1.0, 4), "████");
+        assert_eq!(bar(0.5, 4), "██  ");
+        assert_eq!(bar(0.5 / 4.0, 4), "▌   ");
+        assert_eq!(bar(0.3, 4), "█▎  ");
+        assert_eq!(bar(2.0, 3), "███");
+        assert_eq!(bar(-1.0, 2), "  ");
+    }
+
+    #[test]
+    fn test_meter_fills_whole_cells_over_a_track() {
+        assert_eq!(strip_ansi(&meter(0.5, 10)), "█████░░░░░");
+        assert_eq!(strip_ansi(&meter(0.0, 3)), "░░░");
+        assert_eq!(strip_ansi(&meter(1.0, 3)), "███");
+        assert_eq!(strip_ansi(&meter(0.99, 20)), format!("{}░", "█".repeat(19)));
+    }
+
+    #[test]
+    fn test_bar_chart_aligns_rows_within_inner_width() {
+        let rows = [row("Alpha", 1.0, "50%"), row("Beta Gamma", 0.5, "5%")];
+        let lines: Vec<String> = bar_chart(&rows, 30, &Theme::default())
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+        assert_eq!(lines[0], "Alpha      ███████████████ 50%");
+        assert_eq!(lines[1], "Beta Gamma ███████▌        5%");
+        assert!(lines.iter().all(|l| l.width() <= 30));
+
+        // Long labels are cut to fit; too narrow a space drops the chart.
+        let long = [row(&"x".repeat(40), 1.0, "1")];
+        let narrow = strip_ansi(&bar_chart(&long, 20, &Theme::default())[0]);
+        assert_eq!(narrow.width(), 20);
+        assert!(narrow.starts_with("xxxxxxxxxx... "));
+        assert!(bar_chart(&long, 10, &Theme::default()).is_empty());
+        assert_eq!(chart_width(3), None);
+        assert_eq!(chart_width(100), Some(MAX_BAR_WIDTH));
+    }
+}
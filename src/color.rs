@@ -0,0 +1,309 @@
+//! Terminal color depth, and the one place RGB colors become escape codes.
+//!
+//! Colors are written as 24-bit RGB everywhere else and downsampled here to
+//! what the terminal supports.
+
+use clap::ValueEnum;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::theme::Rgb;
+
+/// `--color`: whether to detect color support or force it on or off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    None,
+    /// The 16 basic ANSI colors
+    Ansi16,
+    /// The xterm 256-color palette
+    Ansi256,
+    TrueColor,
+}
+
+const RESET: &str = "\x1b[0m";
+
+// Until `init` runs (e.g. when used as a library) colors are 24-bit.
+static DEPTH: AtomicU8 = AtomicU8::new(ColorDepth::TrueColor as u8);
+
+/// Detects the color depth of stdout and applies it to all output,
+/// including text colored with the `colored` crate.
+pub fn init(choice: ColorChoice) -> ColorDepth {
+    let depth = detect(
+        choice,
+        |name| std::env::var(name).ok(),
+        std::io::stdout().is_terminal(),
+    );
+    set_depth(depth);
+    depth
+}
+
+pub fn set_depth(depth: ColorDepth) {
+    DEPTH.store(depth as u8, Ordering::Relaxed);
+    colored::control::set_override(depth != ColorDepth::None);
+}
+
+pub fn depth() -> ColorDepth {
+    match DEPTH.load(Ordering::Relaxed) {
+        0 => ColorDepth::None,
+        1 => ColorDepth::Ansi16,
+        2 => ColorDepth::Ansi256,
+        _ => ColorDepth::TrueColor,
+    }
+}
+
+/// `NO_COLOR` and a non-TTY stdout only turn colors off in `auto` mode;
+/// the depth itself comes from `COLORTERM` and `TERM`.
+pub fn detect(
+    choice: ColorChoice,
+    env: impl Fn(&str) -> Option<String>,
+    is_tty: bool,
+) -> ColorDepth {
+    let term = env("TERM").unwrap_or_default();
+    match choice {
+        ColorChoice::Never => return ColorDepth::None,
+        ColorChoice::Auto => {
+            let no_color = env("NO_COLOR").is_some_and(|v| !v.is_empty());
+            if no_color || !is_tty || term == "dumb" {
+                return ColorDepth::None;
+            }
+        }
+        ColorChoice::Always => {}
+    }
+
+    let colorterm = env("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+    if colorterm == "truecolor" || colorterm == "24bit" || term.ends_with("-direct") {
+        ColorDepth::TrueColor
+    } else if term.contains("256color") {
+        ColorDepth::Ansi256
+    } else {
+        ColorDepth::Ansi16
+    }
+}
+
+/// Foreground escape for `color` at the current depth.
+pub fn fg(color: Rgb) -> String {
+    fg_at(depth(), color)
+}
+
+/// Background escape for `color` at the current depth.
+pub fn bg(color: Rgb) -> String {
+    bg_at(depth(), color)
+}
+
+/// Resets colors, or nothing when colors are off.
+pub fn reset() -> &'static str {
+    reset_at(depth())
+}
+
+/// `text` in `color`, followed by a reset.
+pub fn paint(text: &str, color: Rgb) -> String {
+    format!("{}{}{}", fg(color), text, reset())
+}
+
+pub fn fg_at(depth: ColorDepth, color: Rgb) -> String {
+    match depth {
+        ColorDepth::None => String::new(),
+        ColorDepth::Ansi16 => format!("\x1b[{}m", ansi16_code(color)),
+        ColorDepth::Ansi256 => format!("\x1b[38;5;{}m", to_ansi256(color)),
+        ColorDepth::TrueColor => format!("\x1b[38;2;{};{};{}m", color.0, color.1, color.2),
+    }
+}
+
+pub fn bg_at(depth: ColorDepth, color: Rgb) -> String {
+    match depth {
+        ColorDepth::None => String::new(),
+        // Background codes are the foreground ones plus 10.
+        ColorDepth::Ansi16 => format!("\x1b[{}m", ansi16_code(color) + 10),
+        ColorDepth::Ansi256 => format!("\x1b[48;5;{}m", to_ansi256(color)),
+        ColorDepth::TrueColor => format!("\x1b[48;2;{};{};{}m", color.0, color.1, color.2),
+    }
+}
+
+pub fn reset_at(depth: ColorDepth) -> &'static str {
+    if depth == ColorDepth::None {
+        ""
+    } else {
+        RESET
+    }
+}
+
+/// Levels of each channel in the 6x6x6 color cube (indices 16-231).
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Nearest xterm 256-palette index: a color cube entry or a gray (232-255).
+pub fn to_ansi256(color: Rgb) -> u8 {
+    let nearest_level = |c: u8| {
+        CUBE_LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, &level)| (level as i32 - c as i32).abs())
+            .map_or(0, |(i, _)| i)
+    };
+    let (r, g, b) = (
+        nearest_level(color.0),
+        nearest_level(color.1),
+        nearest_level(color.2),
+    );
+    let cube = Rgb(CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]);
+    let cube_index = 16 + 36 * r + 6 * g + b;
+
+    let average = (color.0 as u32 + color.1 as u32 + color.2 as u32) / 3;
+    let gray_step = (average.saturating_sub(3) / 10).min(23);
+    let gray_level = (8 + 10 * gray_step) as u8;
+    let gray = Rgb(gray_level, gray_level, gray_level);
+
+    if distance(color, gray) < distance(color, cube) {
+        (232 + gray_step) as u8
+    } else {
+        cube_index as u8
+    }
+}
+
+/// xterm's default RGB values of the 16 ANSI colors, in code order.
+const ANSI16: [Rgb; 16] = [
+    Rgb(0, 0, 0),
+    Rgb(205, 0, 0),
+    Rgb(0, 205, 0),
+    Rgb(205, 205, 0),
+    Rgb(0, 0, 238),
+    Rgb(205, 0, 205),
+    Rgb(0, 205, 205),
+    Rgb(229, 229, 229),
+    Rgb(127, 127, 127),
+    Rgb(255, 0, 0),
+    Rgb(0, 255, 0),
+    Rgb(255, 255, 0),
+    Rgb(92, 92, 255),
+    Rgb(255, 0, 255),
+    Rgb(0, 255, 255),
+    Rgb(255, 255, 255),
+];
+
+/// SGR foreground code (30-37, 90-97) of the nearest ANSI color.
+pub fn ansi16_code(color: Rgb) -> u8 {
+    let index = ANSI16
+        .iter()
+        .enumerate()
+        .min_by_key(|&(_, &c)| distance(color, c))
+        .map_or(0, |(i, _)| i as u8);
+    if index < 8 {
+        30 + index
+    } else {
+        90 + index - 8
+    }
+}
+
+fn distance(a: Rgb, b: Rgb) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    #[test]
+    fn test_detect_reads_colorterm_and_term() {
+        let auto = |vars| detect(ColorChoice::Auto, env(vars), true);
+        assert_eq!(
+            auto(&[("COLORTERM", "truecolor"), ("TERM", "xterm")]),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(auto(&[("COLORTERM", "24bit")]), ColorDepth::TrueColor);
+        assert_eq!(auto(&[("TERM", "xterm-direct")]), ColorDepth::TrueColor);
+        assert_eq!(auto(&[("TERM", "tmux-256color")]), ColorDepth::Ansi256);
+        assert_eq!(auto(&[("TERM", "linux")]), ColorDepth::Ansi16);
+        assert_eq!(auto(&[]), ColorDepth::Ansi16);
+    }
+
+    #[test]
+    fn test_detect_turns_colors_off_only_in_auto_mode() {
+        let truecolor = [("COLORTERM", "truecolor")];
+        assert_eq!(
+            detect(ColorChoice::Auto, env(&truecolor), false),
+            ColorDepth::None
+        );
+        let no_color = [("COLORTERM", "truecolor"), ("NO_COLOR", "1")];
+        assert_eq!(
+            detect(ColorChoice::Auto, env(&no_color), true),
+            ColorDepth::None
+        );
+        assert_eq!(
+            detect(ColorChoice::Auto, env(&[("TERM", "dumb")]), true),
+            ColorDepth::None
+        );
+        // An empty NO_COLOR does not count.
+        assert_eq!(
+            detect(ColorChoice::Auto, env(&[("NO_COLOR", "")]), true),
+            ColorDepth::Ansi16
+        );
+
+        assert_eq!(
+            detect(ColorChoice::Always, env(&no_color), false),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            detect(ColorChoice::Always, env(&[("TERM", "dumb")]), false),
+            ColorDepth::Ansi16
+        );
+        assert_eq!(
+            detect(ColorChoice::Never, env(&truecolor), true),
+            ColorDepth::None
+        );
+    }
+
+    #[test]
+    fn test_truecolor_escapes_pass_rgb_through() {
+        let steam = Rgb(27, 75, 103);
+        assert_eq!(fg_at(ColorDepth::TrueColor, steam), "\x1b[38;2;27;75;103m");
+        assert_eq!(bg_at(ColorDepth::TrueColor, steam), "\x1b[48;2;27;75;103m");
+        assert_eq!(reset_at(ColorDepth::TrueColor), "\x1b[0m");
+    }
+
+    #[test]
+    fn test_256_color_uses_cube_and_gray_ramp() {
+        assert_eq!(to_ansi256(Rgb(0, 0, 0)), 16);
+        assert_eq!(to_ansi256(Rgb(255, 255, 255)), 231);
+        assert_eq!(to_ansi256(Rgb(255, 0, 0)), 196);
+        assert_eq!(to_ansi256(Rgb(95, 135, 175)), 16 + 36 + 6 * 2 + 3);
+        // Mid grays fall between cube levels and use the ramp.
+        assert_eq!(to_ansi256(Rgb(128, 128, 128)), 244);
+        assert_eq!(to_ansi256(Rgb(27, 75, 103)), 23);
+        assert_eq!(fg_at(ColorDepth::Ansi256, Rgb(255, 0, 0)), "\x1b[38;5;196m");
+        assert_eq!(bg_at(ColorDepth::Ansi256, Rgb(255, 0, 0)), "\x1b[48;5;196m");
+    }
+
+    #[test]
+    fn test_16_color_picks_nearest_basic_color() {
+        assert_eq!(ansi16_code(Rgb(0, 0, 0)), 30);
+        assert_eq!(ansi16_code(Rgb(200, 10, 10)), 31);
+        assert_eq!(ansi16_code(Rgb(250, 250, 250)), 97);
+        assert_eq!(ansi16_code(Rgb(0, 190, 220)), 36);
+        assert_eq!(ansi16_code(Rgb(120, 120, 130)), 90);
+        assert_eq!(fg_at(ColorDepth::Ansi16, Rgb(255, 0, 0)), "\x1b[91m");
+        assert_eq!(bg_at(ColorDepth::Ansi16, Rgb(255, 0, 0)), "\x1b[101m");
+    }
+
+    #[test]
+    fn test_no_color_emits_nothing() {
+        assert_eq!(fg_at(ColorDepth::None, Rgb(1, 2, 3)), "");
+        assert_eq!(bg_at(ColorDepth::None, Rgb(1, 2, 3)), "");
+        assert_eq!(reset_at(ColorDepth::None), "");
+    }
+}
@@ -1,24 +1,36 @@
 use anyhow::{Context, Result};
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::{env, fs};
 
+use crate::logo::{Logo, LogoConfig};
+use crate::modules::{default_modules, Module, ModuleKind};
+use crate::steam::games_list::DEFAULT_GAMES_LIST_URL;
+use crate::steam::local;
+use crate::steam::{FetchOptions, DEFAULT_BASE_URL};
+use crate::theme::{Theme, ThemeConfig};
+
 #[derive(Debug, Default, Deserialize)]
 pub struct ConfigFile {
     #[serde(default)]
     pub api: ApiConfig,
     #[serde(default)]
     pub display: DisplayConfig,
+    #[serde(default)]
+    pub theme: ThemeConfig,
+    #[serde(default)]
+    pub logo: LogoConfig,
 }
 
 #[derive(Debug, Default, Deserialize)]
 pub struct ApiConfig {
     pub steam_api_key: Option<String>,
     pub steam_id: Option<String>,
+    pub base_url: Option<String>,
+    pub games_list_url: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
-#[allow(dead_code)]
 pub struct DisplayConfig {
     #[serde(default = "default_top_games")]
     pub show_top_games: usize,
@@ -28,6 +40,28 @@ pub struct DisplayConfig {
     pub show_achievements: bool,
     #[serde(default = "default_true")]
     pub show_rarest: bool,
+    /// How many rarest unlocks to list when `show_rarest` is on.
+    #[serde(default = "default_rarest_count")]
+    pub rarest_count: usize,
+    #[serde(default)]
+    pub show_installed: bool,
+    #[serde(default = "default_near_complete")]
+    pub show_near_complete: usize,
+    /// Save a history snapshot each run and show changes since the last one.
+    #[serde(default)]
+    pub show_history: bool,
+    /// Draw playtime share, playtime distribution and completion charts.
+    #[serde(default)]
+    pub show_charts: bool,
+    /// The lines and sections to show, in order.
+    #[serde(default = "default_modules")]
+    pub modules: Vec<Module>,
+    /// Resolved from the `[theme]` table by [`Config::load_display`].
+    #[serde(skip)]
+    pub theme: Theme,
+    /// Resolved from the `[logo]` table by [`Config::load_display`].
+    #[serde(skip)]
+    pub logo: Logo,
 }
 
 impl Default for DisplayConfig {
@@ -37,14 +71,63 @@ impl Default for DisplayConfig {
             show_recently_played: true,
             show_achievements: true,
             show_rarest: true,
+            rarest_count: 1,
+            show_installed: false,
+            show_near_complete: 0,
+            show_history: false,
+            show_charts: false,
+            modules: default_modules(),
+            theme: Theme::default(),
+            logo: Logo::default(),
+        }
+    }
+}
+
+impl DisplayConfig {
+    /// Only fetch what will be rendered; the achievement crawl is skipped
+    /// entirely when no achievement section is shown.
+    pub fn fetch_options(&self) -> FetchOptions {
+        let achievements = (self.show_achievements
+            && (self.shows(ModuleKind::Perfect) || self.shows(ModuleKind::Achievements)))
+            || (self.show_rarest && self.shows(ModuleKind::Rarest))
+            || (self.near_complete_count() > 0 && self.shows(ModuleKind::NearComplete));
+        FetchOptions {
+            top_games: self.show_top_games,
+            recently_played: self.show_recently_played,
+            achievements,
+            rarest: self.rarest_count,
+            near_complete: self.near_complete_count(),
         }
     }
+
+    /// Near-complete games are part of the achievement stats, so
+    /// `show_achievements = false` hides them too.
+    pub fn near_complete_count(&self) -> usize {
+        if self.show_achievements {
+            self.show_near_complete
+        } else {
+            0
+        }
+    }
+
+    /// Whether `modules` lists `kind`.
+    pub fn shows(&self, kind: ModuleKind) -> bool {
+        self.modules.iter().any(|m| m.kind == kind)
+    }
 }
 
 fn default_top_games() -> usize {
     5
 }
 
+fn default_rarest_count() -> usize {
+    1
+}
+
+fn default_near_complete() -> usize {
+    0
+}
+
 fn default_true() -> bool {
     true
 }
@@ -52,8 +135,22 @@ fn default_true() -> bool {
 pub struct Config {
     pub api_key: String,
     pub steam_id: String,
-    #[allow(dead_code)]
-    pub display: DisplayConfig,
+}
+
+/// Where steamfetch sends its HTTP requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoints {
+    pub api_base_url: String,
+    pub games_list_url: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            api_base_url: DEFAULT_BASE_URL.to_string(),
+            games_list_url: DEFAULT_GAMES_LIST_URL.to_string(),
+        }
+    }
 }
 
 const API_KEY_HELP: &str = r#"STEAM_API_KEY not set.
@@ -86,7 +183,8 @@ Or add to config file (~/.config/steamfetch/config.toml):
      [api]
      steam_id = "your-steam-id-here"
 
-Note: If Steam is running, STEAM_ID is auto-detected and not required.
+Note: If Steam is running, or you have logged in to Steam on this machine,
+STEAM_ID is auto-detected and not required.
 "#;
 
 impl Config {
@@ -99,16 +197,14 @@ impl Config {
             .or(config_file.api.steam_api_key)
             .context(API_KEY_HELP)?;
 
+        // Then the account last logged in to the local Steam install
         let steam_id = env::var("STEAM_ID")
             .ok()
             .or(config_file.api.steam_id)
+            .or_else(|| local::detect_account().map(|account| account.steam_id))
             .context(STEAM_ID_HELP)?;
 
-        Ok(Self {
-            api_key,
-            steam_id,
-            display: config_file.display,
-        })
+        Ok(Self { api_key, steam_id })
     }
 
     /// Load only API key (for Native SDK mode where steam_id is auto-detected)
@@ -120,6 +216,58 @@ impl Config {
             .or(config_file.api.steam_api_key)
             .context(API_KEY_HELP)
     }
+
+    /// Load endpoint URLs (env > config file > defaults). Credentials are not required.
+    pub fn load_endpoints(config_path: Option<PathBuf>) -> Result<Endpoints> {
+        let config_file = load_config_file(config_path)?;
+        let defaults = Endpoints::default();
+
+        let api_base_url = env::var("STEAMFETCH_API_BASE_URL")
+            .ok()
+            .or(config_file.api.base_url)
+            .map(|url| parse_url(&url).context("Invalid Steam API base URL"))
+            .transpose()?
+            .unwrap_or(defaults.api_base_url);
+
+        let games_list_url = env::var("STEAMFETCH_GAMES_LIST_URL")
+            .ok()
+            .or(config_file.api.games_list_url)
+            .map(|url| parse_url(&url).context("Invalid games list URL"))
+            .transpose()?
+            .unwrap_or(defaults.games_list_url);
+
+        Ok(Endpoints {
+            api_base_url,
+            games_list_url,
+        })
+    }
+
+    /// Load only the `[display]` section, its `[theme]` and `[logo]` (credentials are not required)
+    pub fn load_display(config_path: Option<PathBuf>) -> Result<DisplayConfig> {
+        let path = config_path.or_else(default_config_path);
+        let config_file = load_config_file(path.clone())?;
+        let mut display = config_file.display;
+        // Relative theme and logo `file`s are next to the config file.
+        let base_dir = path.as_deref().and_then(Path::parent);
+        display.theme = config_file
+            .theme
+            .resolve(base_dir)
+            .context("Invalid [theme] in config file")?;
+        display.logo = config_file
+            .logo
+            .resolve(base_dir)
+            .context("Invalid [logo] in config file")?;
+        Ok(display)
+    }
+}
+
+/// Validates an http(s) URL and strips trailing slashes so paths can be appended.
+pub fn parse_url(value: &str) -> Result<String> {
+    let url = reqwest::Url::parse(value).with_context(|| format!("'{}' is not a URL", value))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        anyhow::bail!("'{}' must use http or https", value);
+    }
+    Ok(value.trim_end_matches('/').to_string())
 }
 
 fn load_config_file(custom_path: Option<PathBuf>) -> Result<ConfigFile> {
@@ -153,7 +301,7 @@ fn create_default_config(path: &PathBuf) -> Result<()> {
     Ok(())
 }
 
-const DEFAULT_CONFIG: &str = r#"# steamfetch configuration file
+const DEFAULT_CONFIG: &str = r##"# steamfetch configuration file
 # https://github.com/unhappychoice/steamfetch
 
 [api]
@@ -164,6 +312,10 @@ const DEFAULT_CONFIG: &str = r#"# steamfetch configuration file
 # Note: If Steam is running, STEAM_ID is auto-detected
 # steam_id = "YOUR_STEAM_ID"
 
+# Point steamfetch at a caching proxy or mock server
+# base_url = "https://api.steampowered.com"
+# games_list_url = "https://gib.me/sam/games.xml"
+
 [display]
 # Number of top played games to show
 # show_top_games = 5
@@ -174,9 +326,69 @@ const DEFAULT_CONFIG: &str = r#"# steamfetch configuration file
 # Show achievement statistics
 # show_achievements = true
 
-# Show rarest achievement
+# Show rarest unlocked achievements
 # show_rarest = true
-"#;
+
+# Number of rarest unlocks to list
+# rarest_count = 1
+
+# Show installed games and disk usage from local Steam libraries
+# show_installed = false
+
+# Number of unfinished games closest to 100% achievements ("Almost Perfect");
+# hidden along with the other achievement stats when show_achievements = false
+# show_near_complete = 0
+
+# Keep a local history of each run (~/.local/share/steamfetch/history.jsonl)
+# and show changes since the last run
+# show_history = false
+
+# Draw bar charts for playtime share, playtime distribution and achievement
+# completion
+# show_charts = false
+
+# Lines and sections to show, in order. Each entry is a module name, or a
+# table that changes its label, hides its title (rank title or section
+# heading) or sets the text after the label:
+#   { type = "games", label = "Library", title = false, separator = " ->" }
+# Modules: header, separator, member, level, games, unplayed, playtime,
+# perfect, achievements, history, top_played, playtime_share,
+# playtime_distribution, recently_played, installed, near_complete, rarest,
+# break (an empty line)
+# modules = [
+#   "header", "separator", "member", "level", "games", "unplayed", "playtime",
+#   "perfect", "achievements", "history", "top_played", "playtime_share",
+#   "playtime_distribution", "recently_played", "installed", "near_complete",
+#   "rarest",
+# ]
+
+[theme]
+# Built-in theme: default, monochrome, steam-classic or deck
+# name = "default"
+
+# Theme file with the same keys as this table (relative to this file)
+# file = "themes/mine.toml"
+
+# Override single colors (#rrggbb)
+# logo = "#1b4b67"
+# logo_gradient = "#66c0f4"
+# accent = "#66c0f4"
+# label = "#c7d5e0"
+# title_palette = ["#8b929a", "#66c0f4", "#ff8500"]
+# title_fade = 0.4
+# separator = "─"
+
+[logo]
+# Built-in logo: steam, small, deck, steamos or none
+# name = "steam"
+
+# Text file with your own logo (relative to this file); `$1`..`$9` switch
+# colors like in neofetch
+# file = "logos/mine.txt"
+
+# Colors of `$1`, `$2`, ... (default: the theme's logo and accent colors)
+# colors = ["#1b4b67", "#66c0f4"]
+"##;
 
 fn default_config_path() -> Option<PathBuf> {
     dirs::config_dir().map(|p| p.join("steamfetch").join("config.toml"))
@@ -217,6 +429,177 @@ mod tests {
         assert!(d.show_recently_played);
         assert!(d.show_achievements);
         assert!(d.show_rarest);
+        assert_eq!(d.rarest_count, 1);
+        assert!(!d.show_installed);
+        assert_eq!(d.show_near_complete, 0);
+        assert!(!d.show_history);
+        assert!(!d.show_charts);
+    }
+
+    #[test]
+    fn test_display_config_default_fetch_options_match_client_defaults() {
+        assert_eq!(
+            DisplayConfig::default().fetch_options(),
+            FetchOptions::default()
+        );
+    }
+
+    #[test]
+    fn test_display_config_fetch_options_follow_toggles() {
+        let display = DisplayConfig {
+            show_top_games: 3,
+            show_recently_played: false,
+            show_achievements: false,
+            show_rarest: true,
+            rarest_count: 1,
+            show_installed: true,
+            show_near_complete: 0,
+            show_history: true,
+            show_charts: true,
+            modules: default_modules(),
+            theme: Theme::default(),
+            logo: Logo::default(),
+        };
+        let options = display.fetch_options();
+        assert_eq!(options.top_games, 3);
+        assert!(!options.recently_played);
+        assert!(options.achievements, "rarest still needs the crawl");
+        assert_eq!(options.rarest, 1);
+        assert_eq!(options.near_complete, 0);
+    }
+
+    #[test]
+    fn test_display_config_fetch_options_skip_achievements_when_both_hidden() {
+        let display = DisplayConfig {
+            show_achievements: false,
+            show_rarest: false,
+            ..DisplayConfig::default()
+        };
+        assert!(!display.fetch_options().achievements);
+    }
+
+    #[test]
+    fn test_display_config_near_complete_follows_show_achievements() {
+        let display = DisplayConfig {
+            show_near_complete: 5,
+            modules: vec![Module::new(ModuleKind::NearComplete)],
+            ..DisplayConfig::default()
+        };
+        assert_eq!(display.near_complete_count(), 5);
+        assert!(display.fetch_options().achievements);
+
+        let hidden = DisplayConfig {
+            show_achievements: false,
+            ..display
+        };
+        assert_eq!(hidden.near_complete_count(), 0);
+        assert_eq!(hidden.fetch_options().near_complete, 0);
+        assert!(!hidden.fetch_options().achievements);
+    }
+
+    #[test]
+    fn test_display_config_fetch_options_skip_achievements_without_their_modules() {
+        let display: DisplayConfig =
+            toml::from_str(r#"modules = ["header", "games", "top_played"]"#).unwrap();
+        assert!(display.shows(ModuleKind::Games));
+        assert!(!display.fetch_options().achievements);
+
+        let display = DisplayConfig {
+            modules: vec![Module::new(ModuleKind::Rarest)],
+            ..DisplayConfig::default()
+        };
+        assert!(display.fetch_options().achievements);
+    }
+
+    #[test]
+    fn test_load_display_reads_display_section() {
+        let path = unique_temp_path("load-display");
+        fs::write(
+            &path,
+            r#"
+[display]
+show_top_games = 2
+show_rarest = false
+"#,
+        )
+        .unwrap();
+
+        let display = Config::load_display(Some(path.clone())).expect("load should succeed");
+        assert_eq!(display.show_top_games, 2);
+        assert!(!display.show_rarest);
+        assert!(display.show_achievements);
+        assert_eq!(display.theme, Theme::default());
+        assert_eq!(display.logo, Logo::default());
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_load_display_resolves_theme_table() {
+        let path = unique_temp_path("load-display-theme");
+        fs::write(
+            &path,
+            r##"
+[theme]
+name = "deck"
+separator = "="
+"##,
+        )
+        .unwrap();
+        let display = Config::load_display(Some(path.clone())).expect("load should succeed");
+        assert_eq!(display.theme.logo, Theme::builtin("deck").unwrap().logo);
+        assert_eq!(display.theme.separator, "=");
+
+        fs::write(&path, "[theme]\nname = \"neon\"\n").unwrap();
+        let err = Config::load_display(Some(path.clone())).expect_err("unknown theme");
+        assert!(format!("{:#}", err).contains("Invalid [theme]"));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_load_display_resolves_logo_table() {
+        let path = unique_temp_path("load-display-logo");
+        fs::write(&path, "[logo]\nname = \"small\"\n").unwrap();
+        let display = Config::load_display(Some(path.clone())).expect("load should succeed");
+        assert_eq!(display.logo, Logo::builtin("small").unwrap());
+
+        fs::write(&path, "[logo]\nfile = \"missing-logo.txt\"\n").unwrap();
+        let err = Config::load_display(Some(path.clone())).expect_err("missing logo file");
+        let msg = format!("{:#}", err);
+        assert!(msg.contains("Invalid [logo]"));
+        assert!(msg.contains("missing-logo.txt"));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_load_display_propagates_parse_error() {
+        let path = unique_temp_path("load-display-invalid");
+        fs::write(&path, "this is = not [valid toml").unwrap();
+
+        let err = Config::load_display(Some(path.clone())).expect_err("invalid toml should error");
+        assert!(format!("{:#}", err).contains("Failed to parse config file"));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_parse_url_strips_trailing_slash() {
+        assert_eq!(
+            parse_url("http://127.0.0.1:8080/").unwrap(),
+            "http://127.0.0.1:8080"
+        );
+        assert_eq!(
+            parse_url("https://proxy.example/steam").unwrap(),
+            "https://proxy.example/steam"
+        );
+    }
+
+    #[test]
+    fn test_parse_url_rejects_invalid_values() {
+        assert!(parse_url("not a url").is_err());
+        assert!(parse_url("ftp://example.com").is_err());
     }
 
     #[test]
@@ -344,6 +727,9 @@ show_top_games = 7
         let content = fs::read_to_string(&path).unwrap();
         assert!(content.contains("steamfetch configuration file"));
         assert!(content.contains("show_top_games"));
+        let parsed: ConfigFile = toml::from_str(&content).expect("default config should parse");
+        assert_eq!(parsed.theme.resolve(None).unwrap(), Theme::default());
+        assert_eq!(parsed.logo.resolve(None).unwrap(), Logo::default());
 
         let _ = fs::remove_file(&path);
         let _ = fs::remove_dir(path.parent().unwrap());
@@ -455,6 +841,63 @@ steam_id = "file-sid"
             let _ = fs::remove_file(&path);
         }
 
+        #[test]
+        fn test_load_endpoints_defaults_without_overrides() {
+            let _guard = lock_env();
+            let _base = EnvScope::save("STEAMFETCH_API_BASE_URL");
+            let _games = EnvScope::save("STEAMFETCH_GAMES_LIST_URL");
+
+            let path = unique_path("endpoints-default");
+            fs::write(&path, "").unwrap();
+
+            let endpoints =
+                Config::load_endpoints(Some(path.clone())).expect("load should succeed");
+            assert_eq!(endpoints, Endpoints::default());
+            assert_eq!(endpoints.api_base_url, "https://api.steampowered.com");
+
+            let _ = fs::remove_file(&path);
+        }
+
+        #[test]
+        fn test_load_endpoints_prefers_env_over_config_file() {
+            let _guard = lock_env();
+            let _base = EnvScope::set("STEAMFETCH_API_BASE_URL", "http://127.0.0.1:9000/");
+            let _games = EnvScope::save("STEAMFETCH_GAMES_LIST_URL");
+
+            let path = unique_path("endpoints-env");
+            fs::write(
+                &path,
+                r#"
+[api]
+base_url = "https://proxy.example"
+games_list_url = "https://proxy.example/games.xml"
+"#,
+            )
+            .unwrap();
+
+            let endpoints =
+                Config::load_endpoints(Some(path.clone())).expect("load should succeed");
+            assert_eq!(endpoints.api_base_url, "http://127.0.0.1:9000");
+            assert_eq!(endpoints.games_list_url, "https://proxy.example/games.xml");
+
+            let _ = fs::remove_file(&path);
+        }
+
+        #[test]
+        fn test_load_endpoints_rejects_invalid_url() {
+            let _guard = lock_env();
+            let _base = EnvScope::set("STEAMFETCH_API_BASE_URL", "localhost:9000");
+            let _games = EnvScope::save("STEAMFETCH_GAMES_LIST_URL");
+
+            let path = unique_path("endpoints-invalid");
+            fs::write(&path, "").unwrap();
+
+            let err = Config::load_endpoints(Some(path.clone())).expect_err("invalid URL");
+            assert!(format!("{:#}", err).contains("Invalid Steam API base URL"));
+
+            let _ = fs::remove_file(&path);
+        }
+
         #[test]
         fn test_load_errors_with_help_when_api_key_missing() {
             let _guard = lock_env();
@@ -482,6 +925,9 @@ steam_id = "file-sid"
             let _guard = lock_env();
             let _api = EnvScope::set("STEAM_API_KEY", "env-key");
             let _sid = EnvScope::save("STEAM_ID");
+            let home = unique_path("no-sid-home");
+            fs::create_dir_all(&home).unwrap();
+            let _home = EnvScope::set("HOME", home.to_str().unwrap());
 
             let path = unique_path("no-sid");
             fs::write(&path, "").unwrap();
@@ -497,6 +943,33 @@ steam_id = "file-sid"
             );
 
             let _ = fs::remove_file(&path);
+            let _ = fs::remove_dir_all(&home);
+        }
+
+        #[cfg(target_os = "linux")]
+        #[test]
+        fn test_load_falls_back_to_local_steam_login() {
+            let _guard = lock_env();
+            let _api = EnvScope::set("STEAM_API_KEY", "env-key");
+            let _sid = EnvScope::save("STEAM_ID");
+            let home = unique_path("local-login-home");
+            let config_dir = home.join(".local/share/Steam/config");
+            fs::create_dir_all(&config_dir).unwrap();
+            fs::write(
+                config_dir.join("loginusers.vdf"),
+                r#""users" { "76561197960287930" { "MostRecent" "1" } }"#,
+            )
+            .unwrap();
+            let _home = EnvScope::set("HOME", home.to_str().unwrap());
+
+            let path = unique_path("local-login");
+            fs::write(&path, "").unwrap();
+
+            let cfg = Config::load(Some(path.clone())).expect("local login should be used");
+            assert_eq!(cfg.steam_id, "76561197960287930");
+
+            let _ = fs::remove_file(&path);
+            let _ = fs::remove_dir_all(&home);
         }
 
         #[test]
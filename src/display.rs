@@ -3,49 +3,70 @@ use std::io::{self, Write};
 use terminal_size::{terminal_size, Width};
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-use crate::image_display;
-use crate::steam::{GameStat, SteamStats};
-use crate::ImageProtocol;
+use crate::charts::{self, ChartRow};
+use crate::color::{self, ColorDepth};
+use crate::config::DisplayConfig;
+use crate::games::format_date;
+use crate::image_display::{self, ImageProtocol};
+use crate::logo::Logo;
+use crate::modules::{Module, ModuleKind};
+use crate::steam::{
+    AchievementStats, GameCompletion, GameStat, HistoryDelta, InstalledGame, InstalledGames,
+    LibraryUsage, PlaytimeBucket, RarestAchievement, SteamStats,
+};
+use crate::theme::{repeat_to_width, Rgb, Theme};
 
 const IMAGE_COLS: u32 = 34;
 const IMAGE_ROWS: u32 = 18;
-const LEFT_OFFSET: usize = IMAGE_COLS as usize + 3; // image/logo width + gap
+/// Between the image or logo and the stats.
+const GAP: &str = "   ";
 const DEFAULT_TERMINAL_WIDTH: u16 = 120;
 const MIN_NAME_WIDTH: usize = 8;
+/// Label (13) plus the space after it in a [`stat_line`].
+const STAT_VALUE_COLUMN: usize = 14;
+/// 13 (label) + 1 (space) + 14 (value) + 2 (space) + ~20 (title) = 50
+const HEADER_WIDTH: usize = 50;
 
 pub struct ImageConfig {
     pub enabled: bool,
     pub protocol: ImageProtocol,
 }
 
-pub async fn render(stats: &SteamStats, image_config: &ImageConfig) {
-    let info_lines = build_info_lines(stats, inner_width());
-
-    if image_config.enabled {
-        render_with_image(stats, &info_lines, image_config).await;
+pub async fn render(stats: &SteamStats, display: &DisplayConfig, image_config: &ImageConfig) {
+    // Block images are drawn with colors, so they need them.
+    if image_config.enabled && color::depth() != ColorDepth::None {
+        let info_lines =
+            build_info_lines(stats, display, inner_width(IMAGE_COLS as usize + GAP.len()));
+        render_with_image(stats, &info_lines, image_config, display).await;
     } else {
-        render_with_ascii(&info_lines);
+        let info_lines = build_info_lines(stats, display, inner_width(left_offset(&display.logo)));
+        render_with_ascii(&info_lines, display);
     }
 }
 
-fn inner_width() -> usize {
+fn inner_width(left_offset: usize) -> usize {
     let total = terminal_size()
         .map(|(Width(w), _)| w)
         .unwrap_or(DEFAULT_TERMINAL_WIDTH) as usize;
-    total.saturating_sub(LEFT_OFFSET)
+    total.saturating_sub(left_offset)
 }
 
-async fn render_with_image(stats: &SteamStats, info_lines: &[String], config: &ImageConfig) {
-    let avatar = match &stats.avatar_url {
-        Some(url) => {
-            let cache_key = format!("avatar_{}.png", stats.username);
-            image_display::load_cached_or_download(url, &cache_key).await
-        }
-        None => None,
-    };
+/// Column where the stats start next to `logo`; 0 without a logo.
+fn left_offset(logo: &Logo) -> usize {
+    match logo.width() {
+        0 => 0,
+        width => width + GAP.len(),
+    }
+}
 
-    let Some(img) = avatar else {
-        return render_with_ascii(info_lines);
+async fn render_with_image(
+    stats: &SteamStats,
+    info_lines: &[String],
+    config: &ImageConfig,
+    display: &DisplayConfig,
+) {
+    let Some(img) = image_display::load_avatar(stats).await else {
+        return render_with_ascii(info_lines, display);
     };
 
     println!();
@@ -55,10 +76,10 @@ async fn render_with_image(stats: &SteamStats, info_lines: &[String], config: &I
         image_display::print_image_and_rewind(&img, &config.protocol, IMAGE_COLS, IMAGE_ROWS);
 
     let Some(image_rows) = image_rows else {
-        return render_with_ascii(info_lines);
+        return render_with_ascii(info_lines, display);
     };
 
-    let col_offset = IMAGE_COLS + 3; // image width + gap
+    let col_offset = IMAGE_COLS + GAP.len() as u32;
     let mut stdout = io::stdout().lock();
 
     // Print info lines to the right of the image
@@ -78,193 +99,645 @@ async fn render_with_image(stats: &SteamStats, info_lines: &[String], config: &I
     stdout.flush().unwrap();
 }
 
-fn render_with_ascii(info_lines: &[String]) {
-    let logo_lines = build_logo();
+fn render_with_ascii(info_lines: &[String], display: &DisplayConfig) {
+    print!(
+        "{}",
+        ascii_layout(info_lines, &display.theme, &display.logo)
+    );
+}
+
+/// Renders the ASCII logo and stats as a string instead of printing it.
+///
+/// `inner_width` is the space available to the right of the logo; the CLI
+/// derives it from the terminal width.
+pub fn render_to_string(stats: &SteamStats, display: &DisplayConfig, inner_width: usize) -> String {
+    ascii_layout(
+        &build_info_lines(stats, display, inner_width),
+        &display.theme,
+        &display.logo,
+    )
+}
+
+/// The logo with the stats beside it, starting on its second row; stats
+/// that run past the bottom of the logo stay in the same column.
+fn ascii_layout(info_lines: &[String], theme: &Theme, logo: &Logo) -> String {
+    let logo_lines = logo.render(theme);
+    let mut out = String::from("\n");
 
-    println!();
     for (i, logo_line) in logo_lines.iter().enumerate() {
         let info = if i == 0 {
             ""
         } else {
             info_lines.get(i - 1).map(String::as_str).unwrap_or("")
         };
-        println!("{}   {}", logo_line, info);
+        out.push_str(&format!("{}{}{}\n", logo_line, GAP, info));
     }
 
-    if info_lines.len() > logo_lines.len() - 1 {
-        render_remaining_info(&info_lines[logo_lines.len() - 1..], logo_width());
+    let beside_logo = logo_lines.len().saturating_sub(1);
+    if info_lines.len() > beside_logo {
+        out.push_str(&remaining_info(
+            &info_lines[beside_logo..],
+            left_offset(logo),
+        ));
     }
-    println!();
+    out.push('\n');
+    out
 }
 
-fn build_logo() -> Vec<String> {
-    let lines = vec![
-        "              .,,,,.              ",
-        "        .,'############',.        ",
-        "     .'####################'.     ",
-        "   .'#########################.   ",
-        "  ;###############'' ,.., '####,  ",
-        " ;###############' .#;'':#, '###, ",
-        ",###############,  #:    :#, ####,",
-        "'##############,   #.    ,#' #####",
-        "  '*#########*'     '*,,*' .######",
-        "     `'*###*'          ,.,;#######",
-        "         .,;;,      .;############",
-        ",',.         ';  ,###############'",
-        " '####. :,. .,; ,###############' ",
-        "  '####.. `'' .,###############'  ",
-        "    '########################'    ",
-        "      '*##################*'      ",
-        "         ''*##########*''         ",
-        "              ''''''              ",
-    ];
+/// The colored stat lines shown next to the logo, one entry per row, in the
+/// order of `display.modules`.
+pub fn build_info_lines(
+    stats: &SteamStats,
+    display: &DisplayConfig,
+    inner_width: usize,
+) -> Vec<String> {
+    let mut lines: Vec<String> = display
+        .modules
+        .iter()
+        .flat_map(|module| module_lines(module, stats, display, inner_width))
+        .collect();
+    // Sections open with a blank line, which is not needed at the top.
+    let leading_blanks = lines.iter().take_while(|l| l.is_empty()).count();
+    lines.drain(..leading_blanks);
+    lines
+}
 
-    lines.into_iter().map(colorize_logo_line).collect()
+fn module_lines(
+    module: &Module,
+    stats: &SteamStats,
+    display: &DisplayConfig,
+    inner_width: usize,
+) -> Vec<String> {
+    let theme = &display.theme;
+    let achievements = stats
+        .achievement_stats
+        .as_ref()
+        .filter(|_| display.show_achievements);
+
+    match module.kind {
+        ModuleKind::Header => vec![format!(
+            "{}@{}",
+            theme.accent(&stats.username).bold(),
+            theme.accent(module.label()).bold()
+        )],
+        ModuleKind::Separator => vec![match &module.separator {
+            Some(glyph) => repeat_to_width(glyph, HEADER_WIDTH),
+            None => theme.separator_line(HEADER_WIDTH),
+        }],
+        ModuleKind::Break => vec![String::new()],
+        ModuleKind::Member
+        | ModuleKind::Level
+        | ModuleKind::Games
+        | ModuleKind::Unplayed
+        | ModuleKind::Playtime
+        | ModuleKind::Perfect => stat_row(module, stats, display)
+            .map(|row| stat_row_line(module, &row, theme))
+            .into_iter()
+            .collect(),
+        ModuleKind::Achievements => achievements
+            .zip(stat_row(module, stats, display))
+            .map(|(achievements, row)| {
+                achievement_lines(module, &row, achievements, display, inner_width)
+            })
+            .unwrap_or_default(),
+        ModuleKind::History => stats
+            .history
+            .as_ref()
+            .filter(|_| display.show_history)
+            .map(|history| history_lines(module, history, theme))
+            .unwrap_or_default(),
+        ModuleKind::TopPlayed => {
+            let top_games = top_games(stats, display);
+            if top_games.is_empty() {
+                return Vec::new();
+            }
+            let top_times: Vec<String> = top_games
+                .iter()
+                .map(|g| format!("{}h", format_number(g.playtime_hours())))
+                .collect();
+            let mut lines = section_heading(module, theme);
+            lines.extend(tree_lines(top_games, &top_times, inner_width));
+            lines
+        }
+        ModuleKind::PlaytimeShare => {
+            let top_games = top_games(stats, display);
+            if !display.show_charts || top_games.is_empty() || stats.total_playtime_minutes == 0 {
+                return Vec::new();
+            }
+            let chart =
+                playtime_share_lines(top_games, stats.total_playtime_minutes, inner_width, theme);
+            with_heading(module, chart, theme)
+        }
+        ModuleKind::PlaytimeDistribution => {
+            if !display.show_charts
+                || stats.game_count == 0
+                || stats.playtime_distribution.is_empty()
+            {
+                return Vec::new();
+            }
+            let chart = distribution_lines(&stats.playtime_distribution, inner_width, theme);
+            with_heading(module, chart, theme)
+        }
+        ModuleKind::RecentlyPlayed => {
+            let recent = recently_played(stats, display);
+            if recent.is_empty() {
+                return Vec::new();
+            }
+            let recent_times: Vec<String> = recent
+                .iter()
+                .map(|g| format_playtime(g.playtime_minutes))
+                .collect();
+            let mut lines = section_heading(module, theme);
+            lines.extend(tree_lines(recent, &recent_times, inner_width));
+            lines
+        }
+        ModuleKind::Installed => stats
+            .installed
+            .as_ref()
+            .filter(|i| display.show_installed && i.game_count > 0)
+            .map(|installed| installed_lines(module, installed, inner_width, theme))
+            .unwrap_or_default(),
+        ModuleKind::NearComplete => {
+            let Some(achievements) = stats.achievement_stats.as_ref() else {
+                return Vec::new();
+            };
+            let near_complete = &achievements.near_complete[..achievements
+                .near_complete
+                .len()
+                .min(display.near_complete_count())];
+            if near_complete.is_empty() {
+                return Vec::new();
+            }
+            let progress: Vec<String> = near_complete
+                .iter()
+                .map(|g| format!("{}/{} ({} left)", g.achieved, g.total, g.remaining()))
+                .collect();
+            let mut lines = section_heading(module, theme);
+            lines.extend(tree_lines(near_complete, &progress, inner_width));
+            lines
+        }
+        ModuleKind::Rarest => rarest_lines(
+            module,
+            rarest_shown(stats, display),
+            inner_width,
+            &display.theme,
+        ),
+    }
 }
 
-fn colorize_logo_line(line: &str) -> String {
-    format!("\x1b[38;2;27;75;103m{}\x1b[0m", line) // #1b4b67
+/// The list sections below are shared with the other output formats, so
+/// they honor the same `[display]` settings.
+pub(crate) fn top_games<'a>(stats: &'a SteamStats, display: &DisplayConfig) -> &'a [GameStat] {
+    &stats.top_games[..stats.top_games.len().min(display.show_top_games)]
 }
 
-fn logo_width() -> usize {
-    35
+pub(crate) fn recently_played<'a>(
+    stats: &'a SteamStats,
+    display: &DisplayConfig,
+) -> &'a [GameStat] {
+    if display.show_recently_played {
+        &stats.recently_played
+    } else {
+        &[]
+    }
 }
 
-fn build_info_lines(stats: &SteamStats, inner_width: usize) -> Vec<String> {
-    // 13 (label) + 1 (space) + 14 (value) + 2 (space) + ~20 (title) = 50
-    let line_width = 50;
-    let mut lines = vec![
-        format!("{}@{}", stats.username.bold().cyan(), "Steam".bold().cyan()),
-        "─".repeat(line_width),
-    ];
+/// Up to `rarest_count` rarest unlocks, rarest first.
+pub(crate) fn rarest_shown<'a>(
+    stats: &'a SteamStats,
+    display: &DisplayConfig,
+) -> &'a [RarestAchievement] {
+    let Some(achievements) = stats
+        .achievement_stats
+        .as_ref()
+        .filter(|_| display.show_rarest)
+    else {
+        return &[];
+    };
+    let unlocks =
+        &achievements.rarest_unlocks[..achievements.rarest_unlocks.len().min(display.rarest_count)];
+    if !unlocks.is_empty() {
+        return unlocks;
+    }
+    achievements
+        .rarest
+        .as_ref()
+        .filter(|_| display.rarest_count > 0)
+        .map(std::slice::from_ref)
+        .unwrap_or(&[])
+}
 
-    // Account age
-    if let Some(created) = stats.account_created {
-        let years = account_age_years(created);
-        let (title, color) = account_age_title(years);
-        lines.push(stat_line(
-            "Member",
-            &format!("{} years", years),
-            colorize_title(title, color),
-        ));
+/// A single rarest unlock is labelled "Rarest" rather than with the
+/// section heading.
+pub(crate) fn rarest_label(module: &Module, count: usize) -> &str {
+    match &module.label {
+        Some(label) => label,
+        None if count == 1 => "Rarest",
+        None => module.kind.default_label(),
     }
+}
 
-    // Steam Level
-    if let Some(level) = stats.steam_level {
-        let (title, color) = steam_level_title(level);
-        lines.push(stat_line(
-            "Level",
-            &level.to_string(),
-            colorize_title(title, color),
+/// The blank line and heading that open a section; only the blank line
+/// when the module hides its title.
+fn section_heading(module: &Module, theme: &Theme) -> Vec<String> {
+    let mut lines = vec![String::new()];
+    if module.title {
+        lines.push(theme.label(&heading_text(module)));
+    }
+    lines
+}
+
+fn heading_text(module: &Module) -> String {
+    format!(
+        "{}{}",
+        module.label(),
+        module.separator.as_deref().unwrap_or("")
+    )
+}
+
+fn achievement_lines(
+    module: &Module,
+    row: &StatRow,
+    achievements: &AchievementStats,
+    display: &DisplayConfig,
+    inner_width: usize,
+) -> Vec<String> {
+    let mut lines = vec![stat_row_line(module, row, &display.theme)];
+    if display.show_charts && achievements.total_possible > 0 {
+        // Starts under the value column of the stat line.
+        let column = value_column(module);
+        if let Some(width) = charts::chart_width(inner_width.saturating_sub(column)) {
+            lines.push(format!(
+                "{}{}",
+                " ".repeat(column),
+                charts::meter(
+                    achievements.total_achieved as f64 / achievements.total_possible as f64,
+                    width
+                )
+            ));
+        }
+    }
+    if achievements.failed_games > 0 {
+        let noun = if achievements.failed_games == 1 {
+            "game"
+        } else {
+            "games"
+        };
+        lines.push(format!(
+            "  {}",
+            format!(
+                "({} {} could not be fetched)",
+                format_number(achievements.failed_games),
+                noun
+            )
+            .dimmed()
         ));
     }
+    lines
+}
+
+fn rarest_lines(
+    module: &Module,
+    unlocks: &[RarestAchievement],
+    inner_width: usize,
+    theme: &Theme,
+) -> Vec<String> {
+    if unlocks.len() > 1 {
+        return rarest_unlock_lines(module, unlocks, inner_width, theme);
+    }
+    let Some(rarest) = unlocks.first() else {
+        return Vec::new();
+    };
 
-    // Games
-    let (title, color) = games_title(stats.game_count);
-    lines.push(stat_line(
-        "Games",
-        &format_number(stats.game_count),
-        colorize_title(title, color),
-    ));
-
-    // Unplayed
-    let unplayed_pct = stats.unplayed_count as f64 / stats.game_count as f64 * 100.0;
-    let (title, color) = unplayed_title(unplayed_pct);
-    let value = format!(
-        "{} ({:.0}%)",
-        format_number(stats.unplayed_count),
-        unplayed_pct
+    let percent_len = format!("{:.1}", rarest.percent).len();
+    let name_max = inner_width
+        .saturating_sub(percent_len + 14)
+        .max(MIN_NAME_WIDTH);
+    let game_max = inner_width.saturating_sub(5).max(MIN_NAME_WIDTH);
+    let name = format!(
+        "\"{}\" ({:.1}%)",
+        truncate(&rarest.name, name_max).trim(),
+        rarest.percent
     );
-    lines.push(stat_line(
-        "Unplayed",
-        &value,
-        colorize_title_reverse(title, color),
-    ));
-
-    // Playtime
-    let hours = stats.playtime_hours();
-    let (title, color) = playtime_title(hours);
-    lines.push(stat_line(
-        "Playtime",
-        &format!("{}h", format_number(hours)),
-        colorize_title(title, color),
-    ));
-
-    if let Some(ref achievements) = stats.achievement_stats {
-        // Perfect games
-        let (title, color) = perfect_title(achievements.perfect_games);
-        lines.push(stat_line(
-            "Perfect",
-            &format_number(achievements.perfect_games),
-            colorize_title(title, color),
+    let label = rarest_label(module, 1);
+    let separator = module.separator.as_deref().unwrap_or(":");
+
+    vec![
+        String::new(),
+        if module.title {
+            format!("{}{} {}", theme.label(label), separator, name)
+        } else {
+            name
+        },
+        format!("  in {}", truncate(&rarest.game, game_max).trim().dimmed()),
+    ]
+}
+
+/// A titled stat: the shared source of the terminal stat lines and the
+/// stats table of the other output formats.
+pub struct StatRow {
+    /// The module's label, without the separator.
+    pub label: String,
+    pub value: String,
+    /// The tier title; None when the module hides it.
+    pub title: Option<&'static str>,
+    /// The title's base color in the configured theme.
+    pub color: Rgb,
+}
+
+/// The stat rows of `display.modules`, in order, for renderers other than
+/// the terminal. Sections, and stats that are unavailable or turned off,
+/// are left out.
+pub fn stat_rows(stats: &SteamStats, display: &DisplayConfig) -> Vec<StatRow> {
+    display
+        .modules
+        .iter()
+        .filter_map(|module| stat_row(module, stats, display))
+        .collect()
+}
+
+fn stat_row(module: &Module, stats: &SteamStats, display: &DisplayConfig) -> Option<StatRow> {
+    let achievements = stats
+        .achievement_stats
+        .as_ref()
+        .filter(|_| display.show_achievements);
+    let (value, tiers, tier_value) = match module.kind {
+        ModuleKind::Member => {
+            let years = account_age_years(stats.account_created?);
+            (format!("{} years", years), ACCOUNT_AGE_TIERS, years)
+        }
+        ModuleKind::Level => {
+            let level = stats.steam_level?;
+            (level.to_string(), LEVEL_TIERS, level)
+        }
+        ModuleKind::Games => (
+            format_number(stats.game_count),
+            GAMES_TIERS,
+            stats.game_count,
+        ),
+        ModuleKind::Unplayed => {
+            let pct = percent(stats.unplayed_count, stats.game_count);
+            let value = format!("{} ({:.0}%)", format_number(stats.unplayed_count), pct);
+            (value, UNPLAYED_TIERS, pct as u32)
+        }
+        ModuleKind::Playtime => {
+            let hours = stats.playtime_hours();
+            (format!("{}h", format_number(hours)), PLAYTIME_TIERS, hours)
+        }
+        ModuleKind::Perfect => {
+            let perfect = achievements?.perfect_games;
+            (format_number(perfect), PERFECT_TIERS, perfect)
+        }
+        ModuleKind::Achievements => {
+            let achievements = achievements?;
+            let pct = percent(achievements.total_achieved, achievements.total_possible);
+            let value = format!(
+                "{} ({:.0}%)",
+                format_number(achievements.total_achieved),
+                pct
+            );
+            (value, ACHIEVEMENT_TIERS, pct as u32)
+        }
+        _ => return None,
+    };
+    let (title, color) = themed_title(&display.theme, tiers, tier_value);
+    Some(StatRow {
+        label: module.label().to_string(),
+        value,
+        title: module.title.then_some(title),
+        color,
+    })
+}
+
+/// `part` as a percentage of `whole`; 0 when `whole` is 0.
+fn percent(part: u32, whole: u32) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64 * 100.0
+    }
+}
+
+fn stat_row_line(module: &Module, row: &StatRow, theme: &Theme) -> String {
+    let title = match row.title {
+        // Fewer unplayed games is better, so that title brightens instead.
+        Some(title) if module.kind == ModuleKind::Unplayed => {
+            colorize_title_reverse(title, row.color, theme)
+        }
+        Some(title) => colorize_title(title, row.color, theme),
+        None => String::new(),
+    };
+    stat_line(module, &row.value, title, theme)
+}
+
+/// A chart under its section heading; nothing when the chart did not fit.
+fn with_heading(module: &Module, chart: Vec<String>, theme: &Theme) -> Vec<String> {
+    if chart.is_empty() {
+        return chart;
+    }
+    let mut lines = section_heading(module, theme);
+    lines.extend(chart);
+    lines
+}
+
+/// Each top game's share of the total playtime, plus everything else.
+fn playtime_share_lines(
+    top_games: &[GameStat],
+    total_minutes: u32,
+    inner_width: usize,
+    theme: &Theme,
+) -> Vec<String> {
+    let share = |minutes: u32| minutes as f64 / total_minutes as f64;
+    let mut rows: Vec<ChartRow> = top_games
+        .iter()
+        .map(|g| ChartRow {
+            label: g.name.clone(),
+            fraction: share(g.playtime_minutes),
+            value: format!("{:.0}%", share(g.playtime_minutes) * 100.0),
+        })
+        .collect();
+    let top_minutes: u32 = top_games.iter().map(|g| g.playtime_minutes).sum();
+    let other = total_minutes.saturating_sub(top_minutes);
+    if other > 0 {
+        rows.push(ChartRow {
+            label: "Other".to_string(),
+            fraction: share(other),
+            value: format!("{:.0}%", share(other) * 100.0),
+        });
+    }
+    charts::bar_chart(&rows, inner_width, theme)
+}
+
+/// Games per playtime range, scaled to the largest range.
+fn distribution_lines(
+    buckets: &[PlaytimeBucket],
+    inner_width: usize,
+    theme: &Theme,
+) -> Vec<String> {
+    let largest = buckets
+        .iter()
+        .map(|b| b.game_count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let rows: Vec<ChartRow> = buckets
+        .iter()
+        .map(|b| ChartRow {
+            label: b.label.clone(),
+            fraction: b.game_count as f64 / largest as f64,
+            value: format_number(b.game_count),
+        })
+        .collect();
+    charts::bar_chart(&rows, inner_width, theme)
+}
+
+fn history_lines(module: &Module, history: &HistoryDelta, theme: &Theme) -> Vec<String> {
+    let signed = |n: i64, noun: &str| format!("{:+} {}{}", n, noun, plural(n));
+    let mut changes = Vec::new();
+    if history.playtime_minutes > 0 {
+        changes.push(format!(
+            "+{} playtime",
+            format_playtime(history.playtime_minutes)
         ));
+    }
+    if let Some(n) = history.achievements.filter(|&n| n != 0) {
+        changes.push(signed(n, "achievement"));
+    }
+    if history.games != 0 {
+        changes.push(signed(history.games, "game"));
+    }
+    if let Some(n) = history.steam_level.filter(|&n| n != 0) {
+        changes.push(signed(n, "level"));
+    }
+    if changes.is_empty() && history.week_playtime_minutes == 0 {
+        return Vec::new();
+    }
 
-        // Achievements
-        let ach_pct =
-            achievements.total_achieved as f64 / achievements.total_possible as f64 * 100.0;
-        let (title, color) = achievement_title(ach_pct);
-        let value = format!(
-            "{} ({:.0}%)",
-            format_number(achievements.total_achieved),
-            ach_pct
-        );
-        lines.push(stat_line(
-            "Achievements",
-            &value,
-            colorize_title(title, color),
+    let mut lines = vec![String::new()];
+    if module.title {
+        lines.push(format!(
+            "{} {}",
+            theme.label(&heading_text(module)),
+            format!("({})", format_date(history.since)).dimmed()
+        ));
+    }
+    if changes.is_empty() {
+        lines.push(format!("{}", "No changes".dimmed()));
+    } else {
+        lines.push(changes.join(" · "));
+    }
+    if history.week_playtime_minutes > 0 {
+        lines.push(format!(
+            "+{} this week",
+            format_playtime(history.week_playtime_minutes)
         ));
     }
+    lines
+}
 
-    lines.push(String::new());
-    lines.push(format!("{}", "Top Played".bold()));
-    let top_times: Vec<String> = stats
-        .top_games
+fn rarest_unlock_lines(
+    module: &Module,
+    unlocks: &[RarestAchievement],
+    inner_width: usize,
+    theme: &Theme,
+) -> Vec<String> {
+    let game_max = (inner_width / 3).max(MIN_NAME_WIDTH);
+    let details: Vec<String> = unlocks
+        .iter()
+        .map(|r| format!("{:.1}% · {}", r.percent, truncate(&r.game, game_max).trim()))
+        .collect();
+    let mut lines = section_heading(module, theme);
+    lines.extend(tree_lines(unlocks, &details, inner_width));
+    lines
+}
+
+fn installed_lines(
+    module: &Module,
+    installed: &InstalledGames,
+    inner_width: usize,
+    theme: &Theme,
+) -> Vec<String> {
+    let noun = if installed.game_count == 1 {
+        "game"
+    } else {
+        "games"
+    };
+    let totals = format!(
+        "({} {}, {})",
+        format_number(installed.game_count),
+        noun,
+        format_bytes(installed.total_bytes)
+    );
+    let mut lines = vec![
+        String::new(),
+        if module.title {
+            format!("{} {}", theme.label(&heading_text(module)), totals)
+        } else {
+            totals
+        },
+    ];
+    let sizes: Vec<String> = installed
+        .largest
         .iter()
-        .map(|g| format!("{}h", format_number(g.playtime_hours())))
+        .map(|g| format_bytes(g.size_bytes))
         .collect();
-    lines.extend(tree_lines(&stats.top_games, &top_times, inner_width));
+    lines.extend(tree_lines(&installed.largest, &sizes, inner_width));
 
-    if !stats.recently_played.is_empty() {
-        lines.push(String::new());
-        lines.push(format!("{}", "Recently Played (2 weeks)".bold()));
-        let recent_times: Vec<String> = stats
-            .recently_played
+    // A single library would just repeat the totals.
+    if installed.libraries.len() > 1 {
+        lines.push(theme.label("Libraries"));
+        let usage: Vec<String> = installed
+            .libraries
             .iter()
-            .map(|g| format_playtime(g.playtime_minutes))
+            .map(|l| {
+                format!(
+                    "{} · {}",
+                    format_number(l.game_count),
+                    format_bytes(l.size_bytes)
+                )
+            })
             .collect();
-        lines.extend(tree_lines(
-            &stats.recently_played,
-            &recent_times,
-            inner_width,
-        ));
+        lines.extend(tree_lines(&installed.libraries, &usage, inner_width));
     }
+    lines
+}
 
-    if let Some(ref achievements) = stats.achievement_stats {
-        if let Some(ref rarest) = achievements.rarest {
-            let percent_len = format!("{:.1}", rarest.percent).len();
-            let name_max = inner_width
-                .saturating_sub(percent_len + 14)
-                .max(MIN_NAME_WIDTH);
-            let game_max = inner_width.saturating_sub(5).max(MIN_NAME_WIDTH);
+/// Anything listed as a `├─ name value` tree entry.
+trait TreeItem {
+    fn label(&self) -> &str;
+}
 
-            lines.push(String::new());
-            lines.push(format!(
-                "{}: \"{}\" ({:.1}%)",
-                "Rarest".bold().yellow(),
-                truncate(&rarest.name, name_max).trim(),
-                rarest.percent
-            ));
-            lines.push(format!(
-                "  in {}",
-                truncate(&rarest.game, game_max).trim().dimmed()
-            ));
-        }
+impl TreeItem for GameStat {
+    fn label(&self) -> &str {
+        &self.name
     }
+}
 
-    lines
+impl TreeItem for GameCompletion {
+    fn label(&self) -> &str {
+        &self.name
+    }
+}
+
+impl TreeItem for RarestAchievement {
+    fn label(&self) -> &str {
+        &self.name
+    }
+}
+
+impl TreeItem for InstalledGame {
+    fn label(&self) -> &str {
+        &self.name
+    }
+}
+
+impl TreeItem for LibraryUsage {
+    fn label(&self) -> &str {
+        &self.path
+    }
 }
 
-fn tree_lines(items: &[GameStat], times: &[String], inner_width: usize) -> Vec<String> {
+fn tree_lines<T: TreeItem>(items: &[T], times: &[String], inner_width: usize) -> Vec<String> {
     let name_width = tree_name_width(items, times, inner_width);
     items
         .iter()
@@ -276,20 +749,20 @@ fn tree_lines(items: &[GameStat], times: &[String], inner_width: usize) -> Vec<S
             } else {
                 "├─"
             };
-            format!("{} {} {}", prefix, truncate(&game.name, name_width), time)
+            format!("{} {} {}", prefix, truncate(game.label(), name_width), time)
         })
         .collect()
 }
 
-fn tree_name_width(items: &[GameStat], times: &[String], inner_width: usize) -> usize {
+fn tree_name_width<T: TreeItem>(items: &[T], times: &[String], inner_width: usize) -> usize {
     let max_time = times.iter().map(|t| t.width()).max().unwrap_or(0);
-    let max_name = items.iter().map(|g| g.name.width()).max().unwrap_or(0);
+    let max_name = items.iter().map(|g| g.label().width()).max().unwrap_or(0);
     // prefix(2) + space(1) + name + space(1) + time
     let available = inner_width.saturating_sub(4 + max_time);
     available.min(max_name).max(MIN_NAME_WIDTH)
 }
 
-fn format_playtime(minutes: u32) -> String {
+pub(crate) fn format_playtime(minutes: u32) -> String {
     let hours = minutes / 60;
     let mins = minutes % 60;
     if hours > 0 {
@@ -299,108 +772,148 @@ fn format_playtime(minutes: u32) -> String {
     }
 }
 
-fn games_title(count: u32) -> (&'static str, (u8, u8, u8)) {
-    match count {
-        0..=5 => ("Fledgling Spirit", (200, 220, 255)),
-        6..=15 => ("Awakened Soul", (180, 200, 255)),
-        16..=30 => ("Wandering Phantom", (160, 180, 255)),
-        31..=50 => ("Shadow Initiate", (140, 160, 255)),
-        51..=75 => ("Void Walker", (120, 140, 255)),
-        76..=100 => ("Digital Specter", (100, 120, 255)),
-        101..=150 => ("Realm Collector", (80, 100, 255)),
-        151..=200 => ("Soul Harvester", (100, 80, 255)),
-        201..=300 => ("Chaos Bringer", (120, 60, 255)),
-        301..=400 => ("Dimension Hoarder", (140, 40, 255)),
-        401..=500 => ("Abyss Keeper", (160, 60, 200)),
-        501..=650 => ("Wallet Slayer", (180, 80, 180)),
-        651..=800 => ("Forbidden Archivist", (200, 60, 160)),
-        801..=1000 => ("Eternal Curator", (220, 40, 140)),
-        1001..=1250 => ("Void Emperor", (240, 60, 120)),
-        1251..=1500 => ("Infinite Library", (255, 80, 100)),
-        1501..=2000 => ("Reality Distorter", (255, 60, 80)),
-        2001..=3000 => ("Steam Leviathan", (255, 40, 60)),
-        3001..=5000 => ("Cosmic Devourer", (255, 20, 40)),
-        _ => ("GabeN's Chosen One", (255, 0, 30)),
-    }
+/// `(inclusive upper bound, title, base color)`, lowest tier first; the
+/// last bound is `u32::MAX`.
+type TitleTiers = &'static [(u32, &'static str, (u8, u8, u8))];
+
+/// Index, title and base color of the tier `value` falls in.
+fn tier(tiers: TitleTiers, value: u32) -> (usize, &'static str, (u8, u8, u8)) {
+    let index = tiers
+        .iter()
+        .position(|&(upper, _, _)| value <= upper)
+        .unwrap_or(tiers.len() - 1);
+    let (_, title, color) = tiers[index];
+    (index, title, color)
 }
 
-fn unplayed_title(pct: f64) -> (&'static str, (u8, u8, u8)) {
-    match pct as u32 {
-        0 => ("Actually Plays Games", (50, 255, 100)),
-        1..=5 => ("Rare Specimen", (70, 250, 110)),
-        6..=10 => ("Impressive Self-Control", (90, 240, 120)),
-        11..=15 => ("Mostly Functional", (110, 230, 130)),
-        16..=20 => ("Could Be Worse", (130, 220, 140)),
-        21..=25 => ("Starting to Slip", (150, 210, 150)),
-        26..=30 => ("I'll Play Tomorrow", (170, 200, 140)),
-        31..=35 => ("Just One More Sale", (190, 190, 130)),
-        36..=40 => ("Someday Maybe", (210, 180, 120)),
-        41..=45 => ("Buying Is Playing", (230, 170, 110)),
-        46..=50 => ("It Was On Sale OK", (250, 160, 100)),
-        51..=55 => ("Send Help", (255, 150, 90)),
-        56..=60 => ("My Wallet Weeps", (255, 130, 80)),
-        61..=65 => ("Professional Dust Farmer", (255, 110, 70)),
-        66..=70 => ("Why Am I Like This", (255, 90, 60)),
-        71..=75 => ("Bundle Addiction", (255, 70, 55)),
-        76..=80 => ("Gaming? What's That", (255, 50, 50)),
-        81..=85 => ("Digital Landfill", (255, 30, 50)),
-        86..=90 => ("Steam Sale Victim", (255, 20, 60)),
-        91..=95 => ("Collecting Dust Pro", (255, 10, 70)),
-        _ => ("Why Do I Even Bother", (255, 0, 80)),
-    }
+/// Title for `value` in the theme's title colors.
+fn themed_title(theme: &Theme, tiers: TitleTiers, value: u32) -> (&'static str, Rgb) {
+    let (index, title, color) = tier(tiers, value);
+    (title, theme.title_color(index, tiers.len(), color.into()))
 }
 
-fn playtime_title(hours: u32) -> (&'static str, (u8, u8, u8)) {
-    match hours {
-        0..=10 => ("Newborn Shadow", (200, 230, 255)),
-        11..=50 => ("Passing Specter", (180, 220, 255)),
-        51..=100 => ("Fleeting Presence", (160, 210, 255)),
-        101..=200 => ("Wandering Spirit", (140, 200, 255)),
-        201..=350 => ("Devoted Phantom", (120, 190, 255)),
-        351..=500 => ("Bound Soul", (100, 180, 255)),
-        501..=750 => ("Chained Existence", (80, 170, 255)),
-        751..=1000 => ("Eternal Prisoner", (100, 150, 255)),
-        1001..=1500 => ("Time Devourer", (120, 130, 255)),
-        1501..=2000 => ("Reality Forsaker", (140, 110, 255)),
-        2001..=3000 => ("Dimension Exile", (160, 90, 255)),
-        3001..=4000 => ("Void Dweller", (180, 70, 255)),
-        4001..=5000 => ("Sunlight Deserter", (200, 50, 255)),
-        5001..=7500 => ("Nocturnal Overlord", (220, 70, 200)),
-        7501..=10000 => ("Crimson Night King", (240, 90, 150)),
-        10001..=15000 => ("Grass Myth Believer", (255, 80, 100)),
-        15001..=20000 => ("Hermit of Eternity", (255, 60, 80)),
-        20001..=30000 => ("Ascended Beyond", (255, 40, 60)),
-        30001..=50000 => ("Timeless One", (255, 20, 40)),
-        _ => ("Chronos Incarnate", (255, 0, 30)),
-    }
+/// Titles for the number of owned games.
+const GAMES_TIERS: TitleTiers = &[
+    (5, "Fledgling Spirit", (200, 220, 255)),
+    (15, "Awakened Soul", (180, 200, 255)),
+    (30, "Wandering Phantom", (160, 180, 255)),
+    (50, "Shadow Initiate", (140, 160, 255)),
+    (75, "Void Walker", (120, 140, 255)),
+    (100, "Digital Specter", (100, 120, 255)),
+    (150, "Realm Collector", (80, 100, 255)),
+    (200, "Soul Harvester", (100, 80, 255)),
+    (300, "Chaos Bringer", (120, 60, 255)),
+    (400, "Dimension Hoarder", (140, 40, 255)),
+    (500, "Abyss Keeper", (160, 60, 200)),
+    (650, "Wallet Slayer", (180, 80, 180)),
+    (800, "Forbidden Archivist", (200, 60, 160)),
+    (1000, "Eternal Curator", (220, 40, 140)),
+    (1250, "Void Emperor", (240, 60, 120)),
+    (1500, "Infinite Library", (255, 80, 100)),
+    (2000, "Reality Distorter", (255, 60, 80)),
+    (3000, "Steam Leviathan", (255, 40, 60)),
+    (5000, "Cosmic Devourer", (255, 20, 40)),
+    (u32::MAX, "GabeN's Chosen One", (255, 0, 30)),
+];
+
+/// Title and base color for the number of owned games.
+pub fn games_title(count: u32) -> (&'static str, (u8, u8, u8)) {
+    let (_, title, color) = tier(GAMES_TIERS, count);
+    (title, color)
 }
 
-fn perfect_title(count: u32) -> (&'static str, (u8, u8, u8)) {
-    match count {
-        0 => ("Unawakened", (200, 220, 255)),
-        1..=3 => ("First Blood", (180, 210, 255)),
-        4..=7 => ("Rising Hunter", (160, 200, 255)),
-        8..=12 => ("Soul Seeker", (140, 190, 255)),
-        13..=20 => ("Dark Pursuer", (120, 180, 255)),
-        21..=30 => ("Shadow Stalker", (100, 170, 255)),
-        31..=45 => ("Relentless Blade", (80, 160, 255)),
-        46..=60 => ("Trophy Reaper", (100, 140, 255)),
-        61..=80 => ("Glory Collector", (120, 120, 255)),
-        81..=100 => ("Perfection Seeker", (140, 100, 255)),
-        101..=130 => ("Flawless Executor", (160, 80, 255)),
-        131..=170 => ("Grandmaster of 100%", (180, 60, 255)),
-        171..=210 => ("Eternal Perfectionist", (200, 80, 220)),
-        211..=260 => ("Platinum Overlord", (220, 100, 180)),
-        261..=320 => ("Supreme Completionist", (240, 80, 140)),
-        321..=400 => ("Legendary Finisher", (255, 60, 100)),
-        401..=500 => ("Mythical Achiever", (255, 40, 80)),
-        501..=650 => ("Godslayer", (255, 20, 60)),
-        651..=800 => ("Beyond Perfection", (255, 10, 50)),
-        _ => ("Achievement Deity", (255, 0, 40)),
-    }
+/// Titles for the percentage of unplayed games.
+const UNPLAYED_TIERS: TitleTiers = &[
+    (0, "Actually Plays Games", (50, 255, 100)),
+    (5, "Rare Specimen", (70, 250, 110)),
+    (10, "Impressive Self-Control", (90, 240, 120)),
+    (15, "Mostly Functional", (110, 230, 130)),
+    (20, "Could Be Worse", (130, 220, 140)),
+    (25, "Starting to Slip", (150, 210, 150)),
+    (30, "I'll Play Tomorrow", (170, 200, 140)),
+    (35, "Just One More Sale", (190, 190, 130)),
+    (40, "Someday Maybe", (210, 180, 120)),
+    (45, "Buying Is Playing", (230, 170, 110)),
+    (50, "It Was On Sale OK", (250, 160, 100)),
+    (55, "Send Help", (255, 150, 90)),
+    (60, "My Wallet Weeps", (255, 130, 80)),
+    (65, "Professional Dust Farmer", (255, 110, 70)),
+    (70, "Why Am I Like This", (255, 90, 60)),
+    (75, "Bundle Addiction", (255, 70, 55)),
+    (80, "Gaming? What's That", (255, 50, 50)),
+    (85, "Digital Landfill", (255, 30, 50)),
+    (90, "Steam Sale Victim", (255, 20, 60)),
+    (95, "Collecting Dust Pro", (255, 10, 70)),
+    (u32::MAX, "Why Do I Even Bother", (255, 0, 80)),
+];
+
+/// Title and base color for the percentage of unplayed games.
+pub fn unplayed_title(pct: f64) -> (&'static str, (u8, u8, u8)) {
+    let (_, title, color) = tier(UNPLAYED_TIERS, pct as u32);
+    (title, color)
 }
 
-fn account_age_years(created: u64) -> u32 {
+/// Titles for total playtime in hours.
+const PLAYTIME_TIERS: TitleTiers = &[
+    (10, "Newborn Shadow", (200, 230, 255)),
+    (50, "Passing Specter", (180, 220, 255)),
+    (100, "Fleeting Presence", (160, 210, 255)),
+    (200, "Wandering Spirit", (140, 200, 255)),
+    (350, "Devoted Phantom", (120, 190, 255)),
+    (500, "Bound Soul", (100, 180, 255)),
+    (750, "Chained Existence", (80, 170, 255)),
+    (1000, "Eternal Prisoner", (100, 150, 255)),
+    (1500, "Time Devourer", (120, 130, 255)),
+    (2000, "Reality Forsaker", (140, 110, 255)),
+    (3000, "Dimension Exile", (160, 90, 255)),
+    (4000, "Void Dweller", (180, 70, 255)),
+    (5000, "Sunlight Deserter", (200, 50, 255)),
+    (7500, "Nocturnal Overlord", (220, 70, 200)),
+    (10000, "Crimson Night King", (240, 90, 150)),
+    (15000, "Grass Myth Believer", (255, 80, 100)),
+    (20000, "Hermit of Eternity", (255, 60, 80)),
+    (30000, "Ascended Beyond", (255, 40, 60)),
+    (50000, "Timeless One", (255, 20, 40)),
+    (u32::MAX, "Chronos Incarnate", (255, 0, 30)),
+];
+
+/// Title and base color for total playtime in hours.
+pub fn playtime_title(hours: u32) -> (&'static str, (u8, u8, u8)) {
+    let (_, title, color) = tier(PLAYTIME_TIERS, hours);
+    (title, color)
+}
+
+/// Titles for the number of 100%-completed games.
+const PERFECT_TIERS: TitleTiers = &[
+    (0, "Unawakened", (200, 220, 255)),
+    (3, "First Blood", (180, 210, 255)),
+    (7, "Rising Hunter", (160, 200, 255)),
+    (12, "Soul Seeker", (140, 190, 255)),
+    (20, "Dark Pursuer", (120, 180, 255)),
+    (30, "Shadow Stalker", (100, 170, 255)),
+    (45, "Relentless Blade", (80, 160, 255)),
+    (60, "Trophy Reaper", (100, 140, 255)),
+    (80, "Glory Collector", (120, 120, 255)),
+    (100, "Perfection Seeker", (140, 100, 255)),
+    (130, "Flawless Executor", (160, 80, 255)),
+    (170, "Grandmaster of 100%", (180, 60, 255)),
+    (210, "Eternal Perfectionist", (200, 80, 220)),
+    (260, "Platinum Overlord", (220, 100, 180)),
+    (320, "Supreme Completionist", (240, 80, 140)),
+    (400, "Legendary Finisher", (255, 60, 100)),
+    (500, "Mythical Achiever", (255, 40, 80)),
+    (650, "Godslayer", (255, 20, 60)),
+    (800, "Beyond Perfection", (255, 10, 50)),
+    (u32::MAX, "Achievement Deity", (255, 0, 40)),
+];
+
+/// Title and base color for the number of 100%-completed games.
+pub fn perfect_title(count: u32) -> (&'static str, (u8, u8, u8)) {
+    let (_, title, color) = tier(PERFECT_TIERS, count);
+    (title, color)
+}
+
+pub(crate) fn account_age_years(created: u64) -> u32 {
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
@@ -408,123 +921,166 @@ fn account_age_years(created: u64) -> u32 {
     ((now - created) / 60 / 60 / 24 / 365) as u32
 }
 
-fn steam_level_title(level: u32) -> (&'static str, (u8, u8, u8)) {
-    match level {
-        0..=5 => ("Lurker", (200, 230, 255)),
-        6..=10 => ("Novice", (180, 220, 255)),
-        11..=15 => ("Apprentice", (160, 210, 255)),
-        16..=20 => ("Regular", (140, 200, 255)),
-        21..=25 => ("Established", (120, 190, 255)),
-        26..=30 => ("Dedicated", (100, 180, 255)),
-        31..=40 => ("Respected", (80, 170, 255)),
-        41..=50 => ("Distinguished", (100, 150, 255)),
-        51..=60 => ("Prestigious", (120, 130, 255)),
-        61..=75 => ("Elite", (140, 110, 255)),
-        76..=90 => ("Master", (160, 90, 255)),
-        91..=100 => ("Grandmaster", (180, 70, 255)),
-        101..=125 => ("Legend", (200, 50, 255)),
-        126..=150 => ("Mythical", (220, 70, 200)),
-        151..=200 => ("Immortal", (240, 90, 150)),
-        201..=300 => ("Godlike", (255, 80, 100)),
-        301..=500 => ("Ascended", (255, 60, 80)),
-        501..=1000 => ("Whale Supreme", (255, 40, 60)),
-        _ => ("Touch Grass Please", (255, 0, 30)),
-    }
+/// Titles for the Steam level.
+const LEVEL_TIERS: TitleTiers = &[
+    (5, "Lurker", (200, 230, 255)),
+    (10, "Novice", (180, 220, 255)),
+    (15, "Apprentice", (160, 210, 255)),
+    (20, "Regular", (140, 200, 255)),
+    (25, "Established", (120, 190, 255)),
+    (30, "Dedicated", (100, 180, 255)),
+    (40, "Respected", (80, 170, 255)),
+    (50, "Distinguished", (100, 150, 255)),
+    (60, "Prestigious", (120, 130, 255)),
+    (75, "Elite", (140, 110, 255)),
+    (90, "Master", (160, 90, 255)),
+    (100, "Grandmaster", (180, 70, 255)),
+    (125, "Legend", (200, 50, 255)),
+    (150, "Mythical", (220, 70, 200)),
+    (200, "Immortal", (240, 90, 150)),
+    (300, "Godlike", (255, 80, 100)),
+    (500, "Ascended", (255, 60, 80)),
+    (1000, "Whale Supreme", (255, 40, 60)),
+    (u32::MAX, "Touch Grass Please", (255, 0, 30)),
+];
+
+/// Title and base color for the Steam level.
+pub fn steam_level_title(level: u32) -> (&'static str, (u8, u8, u8)) {
+    let (_, title, color) = tier(LEVEL_TIERS, level);
+    (title, color)
 }
 
-fn account_age_title(years: u32) -> (&'static str, (u8, u8, u8)) {
-    match years {
-        0 => ("Fresh Blood", (200, 230, 255)),
-        1 => ("Newcomer", (180, 220, 255)),
-        2 => ("Getting Hooked", (160, 210, 255)),
-        3 => ("Loyal Customer", (140, 200, 255)),
-        4 => ("Seasoned Gamer", (120, 190, 255)),
-        5 => ("Veteran", (100, 180, 255)),
-        6 => ("Battle-Hardened", (80, 170, 255)),
-        7 => ("Old Guard", (100, 150, 255)),
-        8 => ("Ancient One", (120, 130, 255)),
-        9 => ("Living Legend", (140, 110, 255)),
-        10 => ("Decade Survivor", (160, 90, 255)),
-        11 => ("Time Traveler", (180, 70, 255)),
-        12 => ("Eternal Witness", (200, 50, 255)),
-        13 => ("Unlucky Thirteen", (220, 70, 200)),
-        14 => ("Steam Fossil", (240, 90, 150)),
-        15 => ("Digital Dinosaur", (255, 80, 100)),
-        16 => ("Prehistoric Gamer", (255, 60, 80)),
-        17 => ("Before It Was Cool", (255, 40, 60)),
-        18 => ("OG Steam User", (255, 20, 40)),
-        19 => ("Founding Father", (255, 10, 30)),
-        _ => ("Primordial Entity", (255, 0, 20)),
-    }
+/// Titles for the account age in years.
+const ACCOUNT_AGE_TIERS: TitleTiers = &[
+    (0, "Fresh Blood", (200, 230, 255)),
+    (1, "Newcomer", (180, 220, 255)),
+    (2, "Getting Hooked", (160, 210, 255)),
+    (3, "Loyal Customer", (140, 200, 255)),
+    (4, "Seasoned Gamer", (120, 190, 255)),
+    (5, "Veteran", (100, 180, 255)),
+    (6, "Battle-Hardened", (80, 170, 255)),
+    (7, "Old Guard", (100, 150, 255)),
+    (8, "Ancient One", (120, 130, 255)),
+    (9, "Living Legend", (140, 110, 255)),
+    (10, "Decade Survivor", (160, 90, 255)),
+    (11, "Time Traveler", (180, 70, 255)),
+    (12, "Eternal Witness", (200, 50, 255)),
+    (13, "Unlucky Thirteen", (220, 70, 200)),
+    (14, "Steam Fossil", (240, 90, 150)),
+    (15, "Digital Dinosaur", (255, 80, 100)),
+    (16, "Prehistoric Gamer", (255, 60, 80)),
+    (17, "Before It Was Cool", (255, 40, 60)),
+    (18, "OG Steam User", (255, 20, 40)),
+    (19, "Founding Father", (255, 10, 30)),
+    (u32::MAX, "Primordial Entity", (255, 0, 20)),
+];
+
+/// Title and base color for the account age in years.
+pub fn account_age_title(years: u32) -> (&'static str, (u8, u8, u8)) {
+    let (_, title, color) = tier(ACCOUNT_AGE_TIERS, years);
+    (title, color)
 }
 
-fn achievement_title(pct: f64) -> (&'static str, (u8, u8, u8)) {
-    match pct as u32 {
-        0..=5 => ("Empty Vessel", (200, 220, 255)),
-        6..=10 => ("Dormant Power", (180, 215, 255)),
-        11..=15 => ("Stirring Darkness", (160, 210, 255)),
-        16..=20 => ("Awakening Force", (140, 205, 255)),
-        21..=25 => ("Rising Shadow", (120, 200, 255)),
-        26..=30 => ("Hungry Spirit", (100, 195, 255)),
-        31..=35 => ("Growing Ambition", (80, 190, 255)),
-        36..=40 => ("Burning Desire", (100, 175, 255)),
-        41..=45 => ("Unstoppable Will", (120, 160, 255)),
-        46..=50 => ("Half-Awakened", (140, 145, 255)),
-        51..=55 => ("Power Unleashed", (160, 130, 255)),
-        56..=60 => ("Chaos Rising", (180, 115, 255)),
-        61..=65 => ("Dark Dominator", (200, 100, 255)),
-        66..=70 => ("Realm Conqueror", (220, 100, 220)),
-        71..=75 => ("Relentless Force", (240, 100, 180)),
-        76..=80 => ("Apex Predator", (255, 90, 140)),
-        81..=85 => ("Obsidian Emperor", (255, 70, 100)),
-        86..=90 => ("Chaos Incarnate", (255, 50, 80)),
-        91..=95 => ("Near-Omniscient", (255, 30, 60)),
-        96..=99 => ("Edge of Infinity", (255, 15, 45)),
-        _ => ("The Absolute One", (255, 0, 30)),
-    }
+/// Titles for the overall achievement percentage.
+const ACHIEVEMENT_TIERS: TitleTiers = &[
+    (5, "Empty Vessel", (200, 220, 255)),
+    (10, "Dormant Power", (180, 215, 255)),
+    (15, "Stirring Darkness", (160, 210, 255)),
+    (20, "Awakening Force", (140, 205, 255)),
+    (25, "Rising Shadow", (120, 200, 255)),
+    (30, "Hungry Spirit", (100, 195, 255)),
+    (35, "Growing Ambition", (80, 190, 255)),
+    (40, "Burning Desire", (100, 175, 255)),
+    (45, "Unstoppable Will", (120, 160, 255)),
+    (50, "Half-Awakened", (140, 145, 255)),
+    (55, "Power Unleashed", (160, 130, 255)),
+    (60, "Chaos Rising", (180, 115, 255)),
+    (65, "Dark Dominator", (200, 100, 255)),
+    (70, "Realm Conqueror", (220, 100, 220)),
+    (75, "Relentless Force", (240, 100, 180)),
+    (80, "Apex Predator", (255, 90, 140)),
+    (85, "Obsidian Emperor", (255, 70, 100)),
+    (90, "Chaos Incarnate", (255, 50, 80)),
+    (95, "Near-Omniscient", (255, 30, 60)),
+    (99, "Edge of Infinity", (255, 15, 45)),
+    (u32::MAX, "The Absolute One", (255, 0, 30)),
+];
+
+/// Title and base color for the overall achievement percentage.
+pub fn achievement_title(pct: f64) -> (&'static str, (u8, u8, u8)) {
+    let (_, title, color) = tier(ACHIEVEMENT_TIERS, pct as u32);
+    (title, color)
 }
 
-fn colorize_title(title: &str, base_color: (u8, u8, u8)) -> String {
-    gradient_text(title, base_color, true)
+fn colorize_title(title: &str, base_color: Rgb, theme: &Theme) -> String {
+    gradient_text(title, base_color, theme.title_fade, true)
 }
 
-fn colorize_title_reverse(title: &str, base_color: (u8, u8, u8)) -> String {
-    gradient_text(title, base_color, false)
+fn colorize_title_reverse(title: &str, base_color: Rgb, theme: &Theme) -> String {
+    gradient_text(title, base_color, theme.title_fade, false)
 }
 
-fn gradient_text(title: &str, base: (u8, u8, u8), darken: bool) -> String {
+/// `fade` is how much darker the dim end is than the bright end.
+fn gradient_text(title: &str, base: Rgb, fade: f64, darken: bool) -> String {
     let chars: Vec<char> = title.chars().collect();
     let len = chars.len().max(1);
 
     let mut result = String::new();
     for (i, c) in chars.iter().enumerate() {
         let t = i as f64 / len as f64;
-        let factor = if darken { 1.0 - t * 0.4 } else { 0.6 + t * 0.4 };
-        let r = (base.0 as f64 * factor).min(255.0) as u8;
-        let g = (base.1 as f64 * factor).min(255.0) as u8;
-        let b = (base.2 as f64 * factor).min(255.0) as u8;
-        result.push_str(&format!("\x1b[38;2;{};{};{}m{}", r, g, b, c));
+        let factor = if darken {
+            1.0 - t * fade
+        } else {
+            1.0 - fade + t * fade
+        };
+        result.push_str(&format!("{}{}", color::fg(base.scale(factor)), c));
     }
-    result.push_str("\x1b[0m");
+    result.push_str(color::reset());
     result
 }
 
-fn stat_line(label: &str, value: &str, title: String) -> String {
+fn stat_line(module: &Module, value: &str, title: String, theme: &Theme) -> String {
     // Pad before applying colors
-    let label_with_colon = format!("{}:", label);
-    let label_padded = format!("{:<13}", label_with_colon);
+    let label = stat_label(module);
+    let label_padded = format!("{:<width$}", label, width = value_column(module) - 1);
+    if !module.title {
+        return format!("{} {}", theme.label(&label_padded), value);
+    }
     let value_padded = format!("{:<14}", value);
-    format!("{} {}  {}", label_padded.bold(), value_padded, title)
+    format!("{} {}  {}", theme.label(&label_padded), value_padded, title)
+}
+
+fn stat_label(module: &Module) -> String {
+    format!(
+        "{}{}",
+        module.label(),
+        module.separator.as_deref().unwrap_or(":")
+    )
+}
+
+/// Where the value of a [`stat_line`] starts; long labels push it right.
+fn value_column(module: &Module) -> usize {
+    STAT_VALUE_COLUMN.max(stat_label(module).width() + 1)
 }
 
-fn render_remaining_info(lines: &[String], width: usize) {
-    let padding = " ".repeat(width);
+fn remaining_info(lines: &[String], left_offset: usize) -> String {
+    let padding = " ".repeat(left_offset);
     lines
         .iter()
-        .for_each(|line| println!("{}  {}", padding, line));
+        .map(|line| format!("{}{}\n", padding, line))
+        .collect()
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
+/// The plural suffix for a count of `n`.
+pub(crate) fn plural(n: i64) -> &'static str {
+    if n.abs() == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+pub(crate) fn truncate(s: &str, max_len: usize) -> String {
     let text_width = s.width();
     if text_width <= max_len {
         return format!("{s}{}", " ".repeat(max_len - text_width));
@@ -541,7 +1097,17 @@ fn truncate(s: &str, max_len: usize) -> String {
     format!("{truncated}...{padding}")
 }
 
-fn format_number(n: u32) -> String {
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const MB: f64 = 1_000_000.0;
+    const GB: f64 = 1_000_000_000.0;
+    if bytes as f64 >= GB {
+        format!("{:.1} GB", bytes as f64 / GB)
+    } else {
+        format!("{:.0} MB", bytes as f64 / MB)
+    }
+}
+
+pub(crate) fn format_number(n: u32) -> String {
     let s = n.to_string();
     let mut result = String::new();
     for (i, c) in s.chars().rev().enumerate() {
@@ -650,22 +1216,67 @@ mod tests {
     }
 
     #[test]
-    fn test_logo_width_constant() {
-        assert_eq!(logo_width(), 35);
+    fn test_left_offset_follows_logo_width() {
+        assert_eq!(left_offset(&Logo::default()), 37);
+        let small = Logo::builtin("small").unwrap();
+        assert_eq!(left_offset(&small), small.width() + 3);
+        assert_eq!(left_offset(&Logo::builtin("none").unwrap()), 0);
     }
 
     #[test]
-    fn test_build_logo_returns_18_lines() {
-        let lines = build_logo();
+    fn test_default_logo_renders_18_colored_lines() {
+        let lines = Logo::default().render(&Theme::default());
         assert_eq!(lines.len(), 18);
+        assert!(lines[0].contains(".,,,,."));
+        assert!(lines[0].contains("\x1b["));
+        assert!(lines.iter().all(|l| strip_ansi(l).width() == 34));
     }
 
     #[test]
-    fn test_colorize_logo_line_wraps_with_ansi() {
-        let s = colorize_logo_line("hello");
-        assert!(s.contains("hello"));
-        assert!(s.contains("\x1b["));
-        assert!(s.ends_with("\x1b[0m"));
+    fn test_stat_rows_skip_missing_stats() {
+        let mut stats = crate::demo_stats();
+        let display = DisplayConfig::default();
+        let labels = |stats: &SteamStats, display: &DisplayConfig| -> Vec<String> {
+            stat_rows(stats, display)
+                .into_iter()
+                .map(|r| r.label)
+                .collect()
+        };
+        assert_eq!(
+            labels(&stats, &display),
+            [
+                "Member",
+                "Level",
+                "Games",
+                "Unplayed",
+                "Playtime",
+                "Perfect",
+                "Achievements"
+            ]
+        );
+        let games = &stat_rows(&stats, &display)[2];
+        assert_eq!(
+            (games.value.as_str(), games.title),
+            ("486", Some(games_title(486).0))
+        );
+
+        stats.account_created = None;
+        stats.steam_level = None;
+        stats.achievement_stats = None;
+        assert_eq!(labels(&stats, &display), ["Games", "Unplayed", "Playtime"]);
+    }
+
+    #[test]
+    fn test_stat_rows_follow_modules_and_toggles() {
+        let stats = crate::demo_stats();
+        let display: DisplayConfig = toml::from_str(
+            r#"modules = ["top_played", { type = "playtime", label = "Hours", title = false }, "achievements"]
+show_achievements = false"#,
+        )
+        .unwrap();
+        let rows = stat_rows(&stats, &display);
+        assert_eq!(rows.len(), 1);
+        assert_eq!((rows[0].label.as_str(), rows[0].title), ("Hours", None));
     }
 
     #[test]
@@ -852,28 +1463,28 @@ mod tests {
 
     #[test]
     fn test_gradient_text_preserves_chars() {
-        let result = gradient_text("hi", (255, 128, 64), true);
+        let result = gradient_text("hi", Rgb(255, 128, 64), 0.4, true);
         assert_eq!(strip_ansi(&result), "hi");
     }
 
     #[test]
     fn test_gradient_text_empty_string() {
-        let result = gradient_text("", (100, 100, 100), false);
+        let result = gradient_text("", Rgb(100, 100, 100), 0.4, false);
         assert_eq!(strip_ansi(&result), "");
         assert!(result.contains("\x1b[0m"));
     }
 
     #[test]
     fn test_gradient_text_reverse_starts_dimmer() {
-        let darken = gradient_text("X", (200, 200, 200), true);
-        let brighten = gradient_text("X", (200, 200, 200), false);
+        let darken = gradient_text("X", Rgb(200, 200, 200), 0.4, true);
+        let brighten = gradient_text("X", Rgb(200, 200, 200), 0.4, false);
         assert_ne!(darken, brighten);
     }
 
     #[test]
     fn test_colorize_title_and_reverse_differ() {
-        let a = colorize_title("Test", (200, 100, 50));
-        let b = colorize_title_reverse("Test", (200, 100, 50));
+        let a = colorize_title("Test", Rgb(200, 100, 50), &Theme::default());
+        let b = colorize_title_reverse("Test", Rgb(200, 100, 50), &Theme::default());
         assert_ne!(a, b);
         assert_eq!(strip_ansi(&a), "Test");
         assert_eq!(strip_ansi(&b), "Test");
@@ -881,7 +1492,12 @@ mod tests {
 
     #[test]
     fn test_stat_line_formats_label_and_value() {
-        let line = stat_line("Games", "42", "Title".to_string());
+        let line = stat_line(
+            &Module::new(ModuleKind::Games),
+            "42",
+            "Title".to_string(),
+            &Theme::default(),
+        );
         let stripped = strip_ansi(&line);
         assert!(stripped.contains("Games:"));
         assert!(stripped.contains("42"));
@@ -968,6 +1584,10 @@ mod tests {
             steam_level: None,
             recently_played: Vec::new(),
             avatar_url: None,
+            installed: None,
+            history: None,
+            playtime_distribution: Vec::new(),
+            games: Vec::new(),
         }
     }
 
@@ -982,7 +1602,7 @@ mod tests {
     #[test]
     fn test_build_info_lines_minimal_includes_required_sections() {
         let stats = make_minimal_stats();
-        let lines = build_info_lines(&stats, 80);
+        let lines = build_info_lines(&stats, &DisplayConfig::default(), 80);
         let text = lines_text(&lines);
         assert!(text.contains("alice@Steam"));
         assert!(text.contains("Games:"));
@@ -1002,7 +1622,7 @@ mod tests {
     fn test_build_info_lines_with_steam_level_adds_level() {
         let mut stats = make_minimal_stats();
         stats.steam_level = Some(42);
-        let lines = build_info_lines(&stats, 80);
+        let lines = build_info_lines(&stats, &DisplayConfig::default(), 80);
         let text = lines_text(&lines);
         assert!(text.contains("Level:"));
         assert!(text.contains("42"));
@@ -1016,7 +1636,7 @@ mod tests {
             .unwrap()
             .as_secs();
         stats.account_created = Some(now);
-        let lines = build_info_lines(&stats, 80);
+        let lines = build_info_lines(&stats, &DisplayConfig::default(), 80);
         let text = lines_text(&lines);
         assert!(text.contains("Member:"));
         assert!(text.contains("0 years"));
@@ -1030,8 +1650,11 @@ mod tests {
             total_possible: 100,
             perfect_games: 3,
             rarest: None,
+            failed_games: 0,
+            rarest_unlocks: Vec::new(),
+            near_complete: Vec::new(),
         });
-        let lines = build_info_lines(&stats, 80);
+        let lines = build_info_lines(&stats, &DisplayConfig::default(), 80);
         let text = lines_text(&lines);
         assert!(text.contains("Perfect:"));
         assert!(text.contains("Achievements:"));
@@ -1040,6 +1663,43 @@ mod tests {
         assert!(!text.contains("Rarest"));
     }
 
+    #[test]
+    fn test_build_info_lines_reports_failed_games() {
+        let mut stats = make_minimal_stats();
+        stats.achievement_stats = Some(AchievementStats {
+            total_achieved: 5,
+            total_possible: 10,
+            perfect_games: 0,
+            rarest: None,
+            failed_games: 2,
+            rarest_unlocks: Vec::new(),
+            near_complete: Vec::new(),
+        });
+        let text = lines_text(&build_info_lines(&stats, &DisplayConfig::default(), 80));
+        assert!(text.contains("(2 games could not be fetched)"));
+
+        stats.achievement_stats.as_mut().unwrap().failed_games = 0;
+        let text = lines_text(&build_info_lines(&stats, &DisplayConfig::default(), 80));
+        assert!(!text.contains("could not be fetched"));
+    }
+
+    #[test]
+    fn test_build_info_lines_handles_crawl_where_every_game_failed() {
+        let mut stats = make_minimal_stats();
+        stats.achievement_stats = Some(AchievementStats {
+            total_achieved: 0,
+            total_possible: 0,
+            perfect_games: 0,
+            rarest: None,
+            failed_games: 1,
+            rarest_unlocks: Vec::new(),
+            near_complete: Vec::new(),
+        });
+        let text = lines_text(&build_info_lines(&stats, &DisplayConfig::default(), 80));
+        assert!(text.contains("(0%)"));
+        assert!(text.contains("(1 game could not be fetched)"));
+    }
+
     #[test]
     fn test_build_info_lines_with_rarest_adds_rarest_section() {
         let mut stats = make_minimal_stats();
@@ -1052,8 +1712,11 @@ mod tests {
                 game: "Mystery Game".to_string(),
                 percent: 0.7,
             }),
+            failed_games: 0,
+            rarest_unlocks: Vec::new(),
+            near_complete: Vec::new(),
         });
-        let lines = build_info_lines(&stats, 80);
+        let lines = build_info_lines(&stats, &DisplayConfig::default(), 80);
         let text = lines_text(&lines);
         assert!(text.contains("Rarest"));
         assert!(text.contains("Hidden Gem"));
@@ -1073,9 +1736,12 @@ mod tests {
                 game: "A Very Long Game Title".to_string(),
                 percent: 12.3,
             }),
+            failed_games: 0,
+            rarest_unlocks: Vec::new(),
+            near_complete: Vec::new(),
         });
 
-        let lines = build_info_lines(&stats, 20);
+        let lines = build_info_lines(&stats, &DisplayConfig::default(), 20);
         let text = lines_text(&lines);
         let rarest_line = text.lines().find(|line| line.contains("Rarest")).unwrap();
         let game_line = text.lines().find(|line| line.contains("in ")).unwrap();
@@ -1094,19 +1760,455 @@ mod tests {
             name: "Recent Game".to_string(),
             playtime_minutes: 75,
         }];
-        let lines = build_info_lines(&stats, 80);
+        let lines = build_info_lines(&stats, &DisplayConfig::default(), 80);
         let text = lines_text(&lines);
         assert!(text.contains("Recently Played"));
         assert!(text.contains("Recent Game"));
         assert!(text.contains("1h 15m"));
     }
 
+    fn make_full_stats() -> SteamStats {
+        let mut stats = make_minimal_stats();
+        stats.top_games = (1..=5)
+            .map(|i| GameStat {
+                name: format!("Top {}", i),
+                playtime_minutes: 600 - i * 60,
+            })
+            .collect();
+        stats.recently_played = vec![GameStat {
+            name: "Recent Game".to_string(),
+            playtime_minutes: 75,
+        }];
+        stats.achievement_stats = Some(AchievementStats {
+            total_achieved: 5,
+            total_possible: 10,
+            perfect_games: 1,
+            rarest: Some(RarestAchievement {
+                name: "Hidden Gem".to_string(),
+                game: "Mystery Game".to_string(),
+                percent: 0.7,
+            }),
+            failed_games: 0,
+            rarest_unlocks: Vec::new(),
+            near_complete: Vec::new(),
+        });
+        stats
+    }
+
+    #[test]
+    fn test_build_info_lines_draws_charts() {
+        let mut stats = make_full_stats();
+        stats.playtime_distribution = vec![
+            PlaytimeBucket {
+                label: "0h".to_string(),
+                min_minutes: 0,
+                max_minutes: Some(1),
+                game_count: 2,
+            },
+            PlaytimeBucket {
+                label: "1-10h".to_string(),
+                min_minutes: 60,
+                max_minutes: Some(600),
+                game_count: 8,
+            },
+        ];
+        let display = DisplayConfig {
+            show_charts: true,
+            ..DisplayConfig::default()
+        };
+        let lines = build_info_lines(&stats, &display, 60);
+        let text = lines_text(&lines);
+
+        // 5 of 10 achievements: half of a 24-cell meter.
+        assert!(text.contains(&format!("{}{}", "█".repeat(12), "░".repeat(12))));
+        assert!(text.contains("Playtime Share"));
+        assert!(text.contains("Top 1"));
+        assert!(text.contains("45%"));
+        // Top games add up to 1500 of 1200 minutes, so there is no "Other".
+        assert!(!text.contains("Other"));
+        assert!(text.contains("Playtime Distribution"));
+        let full_row = lines
+            .iter()
+            .map(|l| strip_ansi(l))
+            .find(|l| l.starts_with("1-10h"))
+            .expect("distribution row");
+        assert_eq!(full_row, format!("1-10h {} 8", "█".repeat(24)));
+        assert!(lines.iter().all(|l| strip_ansi(l).width() <= 60));
+
+        let text = lines_text(&build_info_lines(&stats, &DisplayConfig::default(), 60));
+        assert!(!text.contains("Playtime Share"), "charts are opt-in");
+        assert!(!text.contains("Playtime Distribution"));
+        assert!(!text.contains('░'));
+    }
+
+    #[test]
+    fn test_build_info_lines_uses_theme_palette_and_separator() {
+        let display = DisplayConfig {
+            theme: Theme {
+                title_palette: vec![Rgb(9, 9, 9)],
+                title_fade: 0.0,
+                separator: "=".to_string(),
+                ..Theme::default()
+            },
+            ..DisplayConfig::default()
+        };
+        let lines = build_info_lines(&make_full_stats(), &display, 80);
+        assert_eq!(lines[1], "=".repeat(50));
+        let games = lines
+            .iter()
+            .find(|l| strip_ansi(l).starts_with("Games:"))
+            .expect("games line");
+        // A single-color palette with no fade paints every title character alike.
+        assert_eq!(
+            games.matches("\x1b[38;2;9;9;9m").count(),
+            "Awakened Soul".len()
+        );
+    }
+
+    #[test]
+    fn test_rarest_headings_follow_theme() {
+        let unlocks = crate::demo_stats()
+            .achievement_stats
+            .expect("demo achievements")
+            .rarest_unlocks;
+        let module = Module::new(ModuleKind::Rarest);
+        let monochrome = Theme::builtin("monochrome").unwrap();
+        for shown in [&unlocks[..1], &unlocks[..3]] {
+            let lines = rarest_lines(&module, shown, 60, &monochrome);
+            let heading = &lines[1];
+            assert!(
+                heading.starts_with(&monochrome.label(rarest_label(&module, shown.len()))),
+                "{heading:?}"
+            );
+            assert!(!heading.contains("33m"), "yellow heading: {heading:?}");
+        }
+
+        let labelled = Theme {
+            label: Some(Rgb(1, 2, 3)),
+            ..Theme::default()
+        };
+        for shown in [&unlocks[..1], &unlocks[..3]] {
+            let lines = rarest_lines(&module, shown, 60, &labelled);
+            assert!(lines[1].contains("\x1b[38;2;1;2;3m"), "{:?}", lines[1]);
+        }
+    }
+
+    #[test]
+    fn test_themed_title_picks_palette_by_tier() {
+        let theme = Theme {
+            title_palette: vec![Rgb(0, 0, 0), Rgb(190, 0, 0)],
+            ..Theme::default()
+        };
+        assert_eq!(
+            themed_title(&theme, GAMES_TIERS, 0),
+            ("Fledgling Spirit", Rgb(0, 0, 0))
+        );
+        assert_eq!(themed_title(&theme, GAMES_TIERS, 10).1, Rgb(10, 0, 0));
+        assert_eq!(themed_title(&theme, GAMES_TIERS, 9999).1, Rgb(190, 0, 0));
+        assert_eq!(
+            themed_title(&Theme::default(), GAMES_TIERS, 10),
+            ("Awakened Soul", Rgb(180, 200, 255))
+        );
+    }
+
+    #[test]
+    fn test_playtime_share_lines_adds_other_row() {
+        let top = [GameStat {
+            name: "Main".to_string(),
+            playtime_minutes: 300,
+        }];
+        let lines: Vec<String> = playtime_share_lines(&top, 1200, 40, &Theme::default())
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("Main  ") && lines[0].ends_with(" 25%"));
+        assert!(lines[1].starts_with("Other ") && lines[1].ends_with(" 75%"));
+    }
+
+    #[test]
+    fn test_build_info_lines_limits_top_games_to_config() {
+        let display = DisplayConfig {
+            show_top_games: 2,
+            ..DisplayConfig::default()
+        };
+        let text = lines_text(&build_info_lines(&make_full_stats(), &display, 80));
+        assert!(text.contains("Top 1"));
+        assert!(text.contains("Top 2"));
+        assert!(!text.contains("Top 3"));
+    }
+
+    #[test]
+    fn test_build_info_lines_zero_top_games_hides_section() {
+        let display = DisplayConfig {
+            show_top_games: 0,
+            ..DisplayConfig::default()
+        };
+        let text = lines_text(&build_info_lines(&make_full_stats(), &display, 80));
+        assert!(!text.contains("Top Played"));
+    }
+
+    #[test]
+    fn test_build_info_lines_hides_disabled_sections() {
+        let display = DisplayConfig {
+            show_top_games: 5,
+            show_recently_played: false,
+            show_achievements: false,
+            show_rarest: false,
+            rarest_count: 5,
+            show_installed: false,
+            show_near_complete: 0,
+            show_history: false,
+            show_charts: false,
+            modules: crate::modules::default_modules(),
+            theme: Theme::default(),
+            logo: Logo::default(),
+        };
+        let text = lines_text(&build_info_lines(&make_full_stats(), &display, 80));
+        assert!(text.contains("Top Played"));
+        assert!(!text.contains("Recently Played"));
+        assert!(!text.contains("Perfect:"));
+        assert!(!text.contains("Achievements:"));
+        assert!(!text.contains("Rarest"));
+        assert!(!text.contains("Installed"));
+    }
+
+    #[test]
+    fn test_build_info_lines_follows_module_order_and_options() {
+        let display: DisplayConfig = toml::from_str(
+            r#"modules = [
+                { type = "playtime", title = false, separator = " =" },
+                { type = "games", label = "Library" },
+                "top_played",
+                "break",
+                { type = "recently_played", title = false },
+            ]"#,
+        )
+        .unwrap();
+        let lines: Vec<String> = build_info_lines(&crate::demo_stats(), &display, 80)
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+        // The blank line that opens a section is dropped at the top.
+        assert_eq!(lines[0], format!("{:<13} 2,847h", "Playtime ="));
+        assert!(lines[1].starts_with("Library:      486"));
+        assert_eq!(lines[2], "");
+        assert_eq!(lines[3], "Top Played");
+        let recent = lines.iter().position(|l| l.contains("Elden Ring")).unwrap();
+        assert_eq!(lines[recent - 2..recent], ["", ""]);
+        assert!(!lines.iter().any(|l| l.contains("Recently Played")));
+        assert!(!lines.iter().any(|l| l.contains("Member")));
+    }
+
+    #[test]
+    fn test_build_info_lines_long_labels_shift_the_value_column() {
+        let display: DisplayConfig = toml::from_str(
+            r#"show_charts = true
+            modules = [
+                { type = "achievements", label = "Trophies unlocked" },
+                { type = "separator", separator = "=" },
+            ]"#,
+        )
+        .unwrap();
+        let lines: Vec<String> = build_info_lines(&crate::demo_stats(), &display, 80)
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+        assert!(lines[0].starts_with("Trophies unlocked: 3,241 (55%)"));
+        assert!(lines[1].starts_with(&" ".repeat(19)));
+        assert!(lines[1].contains('█'));
+        assert_eq!(lines[2], "=".repeat(HEADER_WIDTH));
+    }
+
+    #[test]
+    fn test_build_info_lines_shows_installed_games_and_libraries() {
+        let mut stats = make_minimal_stats();
+        stats.installed = crate::demo_stats().installed;
+        let text = lines_text(&build_info_lines(&stats, &DisplayConfig::default(), 80));
+        assert!(!text.contains("Installed"), "installed games are opt-in");
+
+        let display = DisplayConfig {
+            show_installed: true,
+            ..DisplayConfig::default()
+        };
+        let text = lines_text(&build_info_lines(&stats, &display, 80));
+        assert!(text.contains("Installed (38 games, 612.4 GB)"));
+        assert!(text.contains("├─ Baldur's Gate 3"));
+        assert!(text.contains("149.8 GB"));
+        assert!(text.contains("Libraries"));
+        assert!(text.contains("/mnt/games/SteamLibrary 14 · 314.3 GB"));
+    }
+
+    #[test]
+    fn test_build_info_lines_skips_empty_install_and_single_library() {
+        let display = DisplayConfig {
+            show_installed: true,
+            ..DisplayConfig::default()
+        };
+        let mut stats = make_minimal_stats();
+        stats.installed = Some(InstalledGames {
+            game_count: 0,
+            total_bytes: 0,
+            largest: Vec::new(),
+            libraries: Vec::new(),
+        });
+        let text = lines_text(&build_info_lines(&stats, &display, 80));
+        assert!(!text.contains("Installed"));
+
+        stats.installed = Some(InstalledGames {
+            game_count: 1,
+            total_bytes: 500_000_000,
+            largest: vec![InstalledGame {
+                appid: 10,
+                name: "Counter-Strike".to_string(),
+                size_bytes: 500_000_000,
+            }],
+            libraries: vec![LibraryUsage {
+                path: "/steam".to_string(),
+                game_count: 1,
+                size_bytes: 500_000_000,
+            }],
+        });
+        let text = lines_text(&build_info_lines(&stats, &display, 80));
+        assert!(text.contains("Installed (1 game, 500 MB)"));
+        assert!(text.contains("└─ Counter-Strike 500 MB"));
+        assert!(!text.contains("Libraries"));
+    }
+
+    #[test]
+    fn test_build_info_lines_shows_changes_since_last_run() {
+        let stats = crate::demo_stats();
+        let display = DisplayConfig {
+            show_history: true,
+            ..DisplayConfig::default()
+        };
+        let text = lines_text(&build_info_lines(&stats, &display, 80));
+        assert!(text.contains("Since last run (2025-10-09)"));
+        assert!(text.contains("+2h 30m playtime · +3 achievements · +2 games"));
+        assert!(!text.contains("level"));
+        assert!(text.contains("+12h 0m this week"));
+
+        let text = lines_text(&build_info_lines(&stats, &DisplayConfig::default(), 80));
+        assert!(!text.contains("Since last run"), "history is opt-in");
+    }
+
+    #[test]
+    fn test_history_lines_singular_negative_and_empty() {
+        let delta = HistoryDelta {
+            since: 0,
+            playtime_minutes: 0,
+            achievements: None,
+            games: -1,
+            steam_level: Some(1),
+            week_playtime_minutes: 0,
+        };
+        let module = Module::new(ModuleKind::History);
+        let text = lines_text(&history_lines(&module, &delta, &Theme::default()));
+        assert!(text.contains("-1 game · +1 level"));
+        assert!(!text.contains("this week"));
+
+        let quiet = HistoryDelta {
+            games: 0,
+            steam_level: Some(0),
+            ..delta.clone()
+        };
+        assert!(history_lines(&module, &quiet, &Theme::default()).is_empty());
+
+        let week_only = HistoryDelta {
+            week_playtime_minutes: 45,
+            ..quiet
+        };
+        let text = lines_text(&history_lines(&module, &week_only, &Theme::default()));
+        assert!(text.contains("No changes"));
+        assert!(text.contains("+45m this week"));
+    }
+
+    #[test]
+    fn test_build_info_lines_rarest_unlocks_tree() {
+        let stats = crate::demo_stats();
+        let display = DisplayConfig {
+            rarest_count: 5,
+            ..DisplayConfig::default()
+        };
+        let text = lines_text(&build_info_lines(&stats, &display, 80));
+        assert!(text.contains("Rarest Unlocks"));
+        assert!(text.contains("├─ Impossible Task"));
+        assert!(text.contains("0.1% · Dark Souls III"));
+        assert!(text.contains("└─ Tourist"));
+
+        let display = DisplayConfig {
+            rarest_count: 2,
+            ..DisplayConfig::default()
+        };
+        let text = lines_text(&build_info_lines(&stats, &display, 80));
+        assert!(text.contains("└─ Lord of Hollownest"));
+        assert!(!text.contains("Tourist"));
+
+        // A single unlock keeps the compact one-line form.
+        let text = lines_text(&build_info_lines(&stats, &DisplayConfig::default(), 80));
+        assert!(!text.contains("Rarest Unlocks"));
+        assert!(text.contains("Rarest: \"Impossible Task\" (0.1%)"));
+
+        let display = DisplayConfig {
+            rarest_count: 0,
+            ..DisplayConfig::default()
+        };
+        let text = lines_text(&build_info_lines(&stats, &display, 80));
+        assert!(!text.contains("Rarest"));
+    }
+
+    #[test]
+    fn test_build_info_lines_lists_near_complete_games_up_to_config() {
+        let stats = crate::demo_stats();
+        let text = lines_text(&build_info_lines(&stats, &DisplayConfig::default(), 80));
+        assert!(
+            !text.contains("Almost Perfect"),
+            "near-complete games are opt-in"
+        );
+
+        let display = DisplayConfig {
+            show_near_complete: 3,
+            ..DisplayConfig::default()
+        };
+        let text = lines_text(&build_info_lines(&stats, &display, 80));
+        assert!(text.contains("Almost Perfect"));
+        assert!(text.contains("├─ Elden Ring"));
+        assert!(text.contains("41/42 (1 left)"));
+        assert!(text.contains("└─ Hollow Knight"));
+        assert!(text.contains("├─ Hades"));
+
+        let display = DisplayConfig {
+            show_near_complete: 1,
+            ..DisplayConfig::default()
+        };
+        let text = lines_text(&build_info_lines(&stats, &display, 80));
+        assert!(text.contains("└─ Elden Ring"));
+        assert!(
+            !text
+                .lines()
+                .any(|l| l.contains("─ Hades") && l.contains("left)")),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn test_build_info_lines_rarest_without_achievement_totals() {
+        let display = DisplayConfig {
+            show_achievements: false,
+            ..DisplayConfig::default()
+        };
+        let text = lines_text(&build_info_lines(&make_full_stats(), &display, 80));
+        assert!(!text.contains("Achievements:"));
+        assert!(text.contains("Rarest"));
+        assert!(text.contains("Hidden Gem"));
+    }
+
     #[test]
     fn test_build_info_lines_unplayed_percentage_rounds() {
         let mut stats = make_minimal_stats();
         stats.game_count = 4;
         stats.unplayed_count = 1;
-        let lines = build_info_lines(&stats, 80);
+        let lines = build_info_lines(&stats, &DisplayConfig::default(), 80);
         let text = lines_text(&lines);
         assert!(text.contains("(25%)"));
     }
@@ -1134,36 +2236,81 @@ mod tests {
     fn test_inner_width_is_non_negative_and_bounded() {
         // `inner_width()` reads `terminal_size()` which in a non-TTY test
         // environment falls back to `DEFAULT_TERMINAL_WIDTH` (120) minus
-        // `LEFT_OFFSET`. Either way the value is a valid usize ≤ u16::MAX.
-        let w = inner_width();
+        // the offset. Either way the value is a valid usize ≤ u16::MAX.
+        let w = inner_width(left_offset(&Logo::default()));
         assert!(w <= u16::MAX as usize);
     }
 
     #[test]
     fn test_render_with_ascii_does_not_panic_with_empty_info() {
         // Smoke test: should print the logo block without panicking.
-        render_with_ascii(&[]);
+        render_with_ascii(&[], &DisplayConfig::default());
     }
 
     #[test]
     fn test_render_with_ascii_handles_info_longer_than_logo() {
         // 30 info lines exceeds the 18-line logo, exercising the
-        // `render_remaining_info` branch inside `render_with_ascii`.
+        // `remaining_info` branch inside `ascii_layout`.
         let info: Vec<String> = (0..30).map(|i| format!("info {}", i)).collect();
-        render_with_ascii(&info);
+        render_with_ascii(&info, &DisplayConfig::default());
+        let text = ascii_layout(&info, &Theme::default(), &Logo::default());
+        assert!(text.contains("info 16"));
+        assert!(text.contains(&format!("{}info 29", " ".repeat(37))));
+    }
+
+    #[test]
+    fn test_ascii_layout_fits_any_logo_size() {
+        let info: Vec<String> = (0..12).map(|i| format!("info {}", i)).collect();
+
+        let small = Logo::builtin("small").unwrap();
+        let text = strip_ansi(&ascii_layout(&info, &Theme::default(), &small));
+        let lines: Vec<&str> = text.lines().collect();
+        let column = small.width() + 3;
+        assert_eq!(lines.len(), info.len() + 3);
+        assert_eq!(&lines[2][column..], "info 0");
+        assert_eq!(
+            lines[lines.len() - 2],
+            format!("{}info 11", " ".repeat(column))
+        );
+
+        let none = Logo::builtin("none").unwrap();
+        let text = ascii_layout(&info, &Theme::default(), &none);
+        assert_eq!(text, format!("\n{}\n", info.join("\n") + "\n"));
     }
 
     #[test]
-    fn test_render_remaining_info_pads_with_logo_width() {
-        // Direct call covers the helper's iteration + println! path.
+    fn test_remaining_info_pads_to_left_offset() {
         let lines = vec!["alpha".to_string(), "beta".to_string()];
-        render_remaining_info(&lines, logo_width());
+        let padding = " ".repeat(37);
+        assert_eq!(
+            remaining_info(&lines, 37),
+            format!("{padding}alpha\n{padding}beta\n")
+        );
     }
 
     #[test]
-    fn test_render_remaining_info_empty_input_is_noop() {
-        // No iteration; covers the early-exit shape of for_each on empty.
-        render_remaining_info(&[], 0);
+    fn test_remaining_info_empty_input_is_empty() {
+        assert_eq!(remaining_info(&[], 0), "");
+    }
+
+    #[test]
+    fn test_render_to_string_contains_logo_and_stats() {
+        let stats = make_full_stats();
+        let text = strip_ansi(&render_to_string(&stats, &DisplayConfig::default(), 80));
+        assert!(text.starts_with('\n'));
+        assert!(text.contains(".,,,,."));
+        assert!(text.contains("Games:"));
+        assert!(text.contains("Top Played"));
+        assert_eq!(
+            text.lines().count(),
+            ascii_layout(
+                &build_info_lines(&stats, &DisplayConfig::default(), 80),
+                &Theme::default(),
+                &Logo::default()
+            )
+            .lines()
+            .count()
+        );
     }
 
     #[tokio::test]
@@ -1175,7 +2322,7 @@ mod tests {
             enabled: false,
             protocol: ImageProtocol::Auto,
         };
-        render(&stats, &config).await;
+        render(&stats, &DisplayConfig::default(), &config).await;
     }
 
     #[tokio::test]
@@ -1188,7 +2335,7 @@ mod tests {
             enabled: true,
             protocol: ImageProtocol::Auto,
         };
-        render(&stats, &config).await;
+        render(&stats, &DisplayConfig::default(), &config).await;
     }
 
     #[cfg(target_os = "linux")]
@@ -1283,7 +2430,7 @@ mod tests {
                 enabled: true,
                 protocol: ImageProtocol::Sixel,
             };
-            run_async(render(&stats, &config));
+            run_async(render(&stats, &DisplayConfig::default(), &config));
 
             let _ = std::fs::remove_dir_all(&root);
         }
@@ -1302,7 +2449,7 @@ mod tests {
                 enabled: true,
                 protocol: ImageProtocol::Sixel,
             };
-            run_async(render(&stats, &config));
+            run_async(render(&stats, &DisplayConfig::default(), &config));
 
             let _ = std::fs::remove_dir_all(&root);
         }
@@ -1372,7 +2519,7 @@ mod tests {
                 enabled: true,
                 protocol: ImageProtocol::Sixel,
             };
-            run_async(render(&stats, &config));
+            run_async(render(&stats, &DisplayConfig::default(), &config));
 
             let _ = std::fs::remove_dir_all(&root);
         }
@@ -0,0 +1,366 @@
+use clap::ValueEnum;
+use colored::Colorize;
+use serde::Serialize;
+use unicode_width::UnicodeWidthStr;
+
+use crate::display::{format_number, format_playtime, truncate};
+use crate::steam::Game;
+
+const MAX_NAME_WIDTH: usize = 40;
+
+/// One row of `steamfetch games`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GameEntry {
+    pub appid: u32,
+    pub name: String,
+    pub playtime_minutes: u32,
+    pub playtime_2weeks_minutes: u32,
+    /// Unix timestamp; None if never played.
+    pub last_played: Option<u64>,
+}
+
+impl From<Game> for GameEntry {
+    fn from(game: Game) -> Self {
+        Self {
+            name: game.name.unwrap_or_else(|| format!("App {}", game.appid)),
+            appid: game.appid,
+            playtime_minutes: game.playtime_forever,
+            playtime_2weeks_minutes: game.playtime_2weeks,
+            last_played: (game.rtime_last_played > 0).then_some(game.rtime_last_played),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SortKey {
+    /// Total playtime, most first
+    #[default]
+    Playtime,
+    /// Playtime in the last 2 weeks, most first
+    Recent,
+    /// Last played date, newest first
+    LastPlayed,
+    /// Name, A to Z
+    Name,
+    /// App ID, ascending
+    Appid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PlayedFilter {
+    Unplayed,
+    Played,
+}
+
+/// Sorting and filtering applied to the owned games list.
+#[derive(Debug, Clone, Default)]
+pub struct GamesQuery {
+    pub sort: SortKey,
+    pub filter: Option<PlayedFilter>,
+    pub min_hours: Option<u32>,
+    /// Case-insensitive substring of the name.
+    pub search: Option<String>,
+    pub limit: Option<usize>,
+}
+
+impl GamesQuery {
+    pub fn apply(&self, games: Vec<GameEntry>) -> Vec<GameEntry> {
+        let search = self.search.as_deref().map(str::to_lowercase);
+        let mut games: Vec<GameEntry> = games
+            .into_iter()
+            .filter(|g| match self.filter {
+                Some(PlayedFilter::Unplayed) => g.playtime_minutes == 0,
+                Some(PlayedFilter::Played) => g.playtime_minutes > 0,
+                None => true,
+            })
+            .filter(|g| {
+                self.min_hours
+                    .is_none_or(|hours| g.playtime_minutes >= hours.saturating_mul(60))
+            })
+            .filter(|g| {
+                search
+                    .as_deref()
+                    .is_none_or(|s| g.name.to_lowercase().contains(s))
+            })
+            .collect();
+
+        // Ties fall back to name so the order is stable across runs.
+        let by_name = |a: &GameEntry, b: &GameEntry| {
+            a.name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then(a.appid.cmp(&b.appid))
+        };
+        games.sort_by(|a, b| match self.sort {
+            SortKey::Playtime => b
+                .playtime_minutes
+                .cmp(&a.playtime_minutes)
+                .then_with(|| by_name(a, b)),
+            SortKey::Recent => b
+                .playtime_2weeks_minutes
+                .cmp(&a.playtime_2weeks_minutes)
+                .then_with(|| by_name(a, b)),
+            SortKey::LastPlayed => b
+                .last_played
+                .cmp(&a.last_played)
+                .then_with(|| by_name(a, b)),
+            SortKey::Name => by_name(a, b),
+            SortKey::Appid => a.appid.cmp(&b.appid),
+        });
+
+        if let Some(limit) = self.limit {
+            games.truncate(limit);
+        }
+        games
+    }
+}
+
+/// Renders the games as an aligned table with a count footer.
+pub fn render_table(games: &[GameEntry], owned: u32) -> String {
+    let name_width = games
+        .iter()
+        .map(|g| g.name.width())
+        .max()
+        .unwrap_or(0)
+        .clamp("NAME".len(), MAX_NAME_WIDTH);
+
+    let mut out = format!(
+        "{}\n",
+        format!(
+            "{:>8}  {}  {:>9}  {:>9}  {}",
+            "APPID",
+            truncate("NAME", name_width),
+            "PLAYTIME",
+            "2 WEEKS",
+            "LAST PLAYED"
+        )
+        .bold()
+    );
+    for game in games {
+        out.push_str(&format!(
+            "{:>8}  {}  {:>9}  {:>9}  {}\n",
+            game.appid,
+            truncate(&game.name, name_width),
+            format_total(game.playtime_minutes),
+            format_recent(game.playtime_2weeks_minutes),
+            game.last_played.map_or("never".to_string(), format_date),
+        ));
+    }
+    out.push_str(&format!(
+        "{}\n",
+        format!(
+            "{} of {} games",
+            format_number(games.len() as u32),
+            format_number(owned)
+        )
+        .dimmed()
+    ));
+    out
+}
+
+fn format_total(minutes: u32) -> String {
+    match minutes {
+        0 => "-".to_string(),
+        1..=59 => format_playtime(minutes),
+        _ => format!("{}h", format_number(minutes / 60)),
+    }
+}
+
+fn format_recent(minutes: u32) -> String {
+    if minutes == 0 {
+        "-".to_string()
+    } else {
+        format_playtime(minutes)
+    }
+}
+
+/// `YYYY-MM-DD` (UTC) for a Unix timestamp.
+pub(crate) fn format_date(timestamp: u64) -> String {
+    // Howard Hinnant's days-to-civil algorithm.
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Unix timestamp of midnight (UTC) on a `YYYY-MM-DD` date.
+pub(crate) fn parse_date(value: &str) -> Option<u64> {
+    let mut parts = value.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // Inverse of `format_date`.
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    let timestamp = u64::try_from(days).ok()? * 86_400;
+    // Rejects dates such as February 30th, which would roll over.
+    (format_date(timestamp) == format!("{:04}-{:02}-{:02}", year, month, day)).then_some(timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(appid: u32, name: &str, minutes: u32, recent: u32, last: u64) -> GameEntry {
+        GameEntry {
+            appid,
+            name: name.to_string(),
+            playtime_minutes: minutes,
+            playtime_2weeks_minutes: recent,
+            last_played: (last > 0).then_some(last),
+        }
+    }
+
+    fn library() -> Vec<GameEntry> {
+        vec![
+            entry(570, "Dota 2", 90_000, 0, 1_600_000_000),
+            entry(10, "Counter-Strike", 0, 0, 0),
+            entry(1086940, "Baldur's Gate 3", 12_000, 600, 1_700_000_000),
+            entry(620, "Portal 2", 30, 30, 1_500_000_000),
+            entry(400, "Portal", 0, 0, 0),
+        ]
+    }
+
+    fn names(games: &[GameEntry]) -> Vec<&str> {
+        games.iter().map(|g| g.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_game_entry_from_api_game() {
+        let entry = GameEntry::from(Game {
+            appid: 42,
+            name: None,
+            playtime_forever: 5,
+            playtime_2weeks: 1,
+            rtime_last_played: 0,
+        });
+        assert_eq!(entry.name, "App 42");
+        assert_eq!(entry.playtime_minutes, 5);
+        assert_eq!(entry.playtime_2weeks_minutes, 1);
+        assert_eq!(entry.last_played, None);
+    }
+
+    #[test]
+    fn test_default_query_sorts_by_playtime_then_name() {
+        let games = GamesQuery::default().apply(library());
+        assert_eq!(
+            names(&games),
+            vec![
+                "Dota 2",
+                "Baldur's Gate 3",
+                "Portal 2",
+                "Counter-Strike",
+                "Portal"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_keys() {
+        let sorted = |sort| {
+            let query = GamesQuery {
+                sort,
+                ..GamesQuery::default()
+            };
+            names(&query.apply(library()))
+                .into_iter()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            sorted(SortKey::Recent)[..2],
+            ["Baldur's Gate 3", "Portal 2"]
+        );
+        assert_eq!(
+            sorted(SortKey::LastPlayed)[..3],
+            ["Baldur's Gate 3", "Dota 2", "Portal 2"]
+        );
+        assert_eq!(sorted(SortKey::Name)[0], "Baldur's Gate 3");
+        assert_eq!(sorted(SortKey::Appid)[0], "Counter-Strike");
+    }
+
+    #[test]
+    fn test_filters_min_hours_search_and_limit() {
+        let unplayed = GamesQuery {
+            filter: Some(PlayedFilter::Unplayed),
+            ..GamesQuery::default()
+        };
+        assert_eq!(
+            names(&unplayed.apply(library())),
+            vec!["Counter-Strike", "Portal"]
+        );
+
+        let played = GamesQuery {
+            filter: Some(PlayedFilter::Played),
+            min_hours: Some(200),
+            ..GamesQuery::default()
+        };
+        assert_eq!(
+            names(&played.apply(library())),
+            vec!["Dota 2", "Baldur's Gate 3"]
+        );
+
+        let search = GamesQuery {
+            search: Some("PORTAL".to_string()),
+            limit: Some(1),
+            ..GamesQuery::default()
+        };
+        assert_eq!(names(&search.apply(library())), vec!["Portal 2"]);
+    }
+
+    #[test]
+    fn test_render_table_lists_rows_and_footer() {
+        let games = GamesQuery::default().apply(library());
+        let table = render_table(&games[..2], 5);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].contains("APPID") && lines[0].contains("LAST PLAYED"));
+        assert!(lines[1].contains("Dota 2"));
+        assert!(lines[1].contains("1,500h"));
+        assert!(lines[1].contains("2020-09-13"));
+        assert!(lines[2].contains("200h"));
+        assert!(lines[2].contains("10h 0m"));
+        assert!(lines[3].contains("2 of 5 games"));
+    }
+
+    #[test]
+    fn test_format_columns() {
+        assert_eq!(format_total(0), "-");
+        assert_eq!(format_total(45), "45m");
+        assert_eq!(format_total(125), "2h");
+        assert_eq!(format_recent(0), "-");
+        assert_eq!(format_recent(75), "1h 15m");
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+        assert_eq!(format_date(1_700_000_000), "2023-11-14");
+    }
+
+    #[test]
+    fn test_parse_date_round_trips_and_rejects_invalid() {
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(parse_date("2000-02-29"), Some(951_782_400));
+        assert_eq!(parse_date("2023-11-14"), Some(1_699_920_000));
+        for invalid in [
+            "2023-02-30",
+            "2023-13-01",
+            "1969-12-31",
+            "2023-11",
+            "yesterday",
+        ] {
+            assert_eq!(parse_date(invalid), None, "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,814 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use unicode_width::UnicodeWidthStr;
+
+use crate::display::{format_playtime, plural, truncate};
+use crate::games::{format_date, parse_date};
+use crate::steam::{HistoryDelta, SteamStats};
+
+const DAY_SECS: u64 = 86_400;
+const WEEK_SECS: u64 = 7 * DAY_SECS;
+/// Games listed per period in the table; JSON has all of them.
+const TOP_GAMES_PER_PERIOD: usize = 5;
+const MAX_NAME_WIDTH: usize = 40;
+
+/// Account totals and per-game playtime at one point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Unix timestamp of the run.
+    pub timestamp: u64,
+    pub game_count: u32,
+    pub total_playtime_minutes: u32,
+    pub steam_level: Option<u32>,
+    /// None when some games' achievements could not be fetched.
+    pub achievements_unlocked: Option<u32>,
+    pub games: Vec<GameSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub appid: u32,
+    pub name: String,
+    pub playtime_minutes: u32,
+}
+
+impl Snapshot {
+    pub fn from_stats(stats: &SteamStats, timestamp: u64) -> Self {
+        let mut games: Vec<GameSnapshot> = stats
+            .games
+            .iter()
+            .map(|g| GameSnapshot {
+                appid: g.appid,
+                name: g.name.clone().unwrap_or_else(|| format!("App {}", g.appid)),
+                playtime_minutes: g.playtime_forever,
+            })
+            .collect();
+        games.sort_by_key(|g| g.appid);
+        Self {
+            timestamp,
+            game_count: stats.game_count,
+            total_playtime_minutes: stats.total_playtime_minutes,
+            steam_level: stats.steam_level,
+            achievements_unlocked: stats
+                .achievement_stats
+                .as_ref()
+                .filter(|a| a.failed_games == 0)
+                .map(|a| a.total_achieved),
+            games,
+        }
+    }
+
+    /// Whether both snapshots hold the same data, whenever they were taken.
+    fn same_data(&self, other: &Snapshot) -> bool {
+        Snapshot {
+            timestamp: other.timestamp,
+            ..self.clone()
+        } == *other
+    }
+}
+
+/// One line of the history file. After the first line, `games` usually only
+/// lists the games that are new or changed since the line before, so the
+/// file grows with what was played rather than with the library size.
+#[derive(Serialize, Deserialize)]
+struct StoredSnapshot {
+    #[serde(flatten)]
+    snapshot: Snapshot,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    changed_games_only: bool,
+}
+
+impl StoredSnapshot {
+    fn full(snapshot: &Snapshot) -> Self {
+        Self {
+            snapshot: snapshot.clone(),
+            changed_games_only: false,
+        }
+    }
+
+    /// Keeps only the games that differ from `previous`. Removed games need
+    /// the full list, as do snapshots after one without games.
+    fn changes_since(snapshot: &Snapshot, previous: &Snapshot) -> Self {
+        let before: BTreeMap<u32, &GameSnapshot> =
+            previous.games.iter().map(|g| (g.appid, g)).collect();
+        let after: BTreeMap<u32, &GameSnapshot> =
+            snapshot.games.iter().map(|g| (g.appid, g)).collect();
+        if before.is_empty() || before.keys().any(|appid| !after.contains_key(appid)) {
+            return Self::full(snapshot);
+        }
+        let games = snapshot
+            .games
+            .iter()
+            .filter(|g| before.get(&g.appid) != Some(g))
+            .cloned()
+            .collect();
+        Self {
+            snapshot: Snapshot {
+                games,
+                ..snapshot.clone()
+            },
+            changed_games_only: true,
+        }
+    }
+
+    /// The full snapshot, given the games of the line before it.
+    fn expand(self, games: &mut BTreeMap<u32, GameSnapshot>) -> Snapshot {
+        let mut snapshot = self.snapshot;
+        if !self.changed_games_only {
+            games.clear();
+        }
+        games.extend(snapshot.games.drain(..).map(|g| (g.appid, g)));
+        snapshot.games = games.values().cloned().collect();
+        snapshot
+    }
+}
+
+/// Snapshots stored one per line in `~/.local/share/steamfetch/history.jsonl`.
+pub struct HistoryStore {
+    path: PathBuf,
+}
+
+impl HistoryStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|p| p.join("steamfetch").join("history.jsonl"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Oldest first. Unreadable lines are skipped so one interrupted write
+    /// does not lose the whole history.
+    pub fn load(&self) -> Result<Vec<Snapshot>> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", self.path.display()))
+            }
+        };
+        let mut stored: Vec<StoredSnapshot> = text
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        stored.sort_by_key(|s| s.snapshot.timestamp);
+        let mut games = BTreeMap::new();
+        Ok(stored.into_iter().map(|s| s.expand(&mut games)).collect())
+    }
+
+    /// Appends `snapshot` with its full game list.
+    pub fn append(&self, snapshot: &Snapshot) -> Result<()> {
+        self.write(&StoredSnapshot::full(snapshot))
+    }
+
+    fn write(&self, stored: &StoredSnapshot) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open {}", self.path.display()))?;
+        let line = serde_json::to_string(stored).context("Failed to serialize snapshot")?;
+        writeln!(file, "{}", line)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    /// Compares `stats` with the stored history, then saves it. Nothing is
+    /// appended when the data has not changed since the last snapshot, and
+    /// otherwise only the games that changed are written.
+    pub fn record(&self, stats: &SteamStats, now: u64) -> Result<Option<HistoryDelta>> {
+        let history = self.load()?;
+        let current = Snapshot::from_stats(stats, now);
+        let delta = delta(&history, &current);
+        match history.last() {
+            Some(last) if last.same_data(&current) => {}
+            Some(last) => self.write(&StoredSnapshot::changes_since(&current, last))?,
+            None => self.append(&current)?,
+        }
+        Ok(delta)
+    }
+}
+
+/// Changes from the latest snapshot in `history` to `current`, or None
+/// without history.
+pub fn delta(history: &[Snapshot], current: &Snapshot) -> Option<HistoryDelta> {
+    let previous = history.last()?;
+    // The latest snapshot at least a week old, or the oldest one if history
+    // is younger than that.
+    let cutoff = current.timestamp.saturating_sub(WEEK_SECS);
+    let week_base = history
+        .iter()
+        .rev()
+        .find(|s| s.timestamp <= cutoff)
+        .unwrap_or(&history[0]);
+
+    Some(HistoryDelta {
+        since: previous.timestamp,
+        playtime_minutes: current
+            .total_playtime_minutes
+            .saturating_sub(previous.total_playtime_minutes),
+        achievements: current
+            .achievements_unlocked
+            .zip(previous.achievements_unlocked)
+            .map(|(now, before)| i64::from(now) - i64::from(before)),
+        games: i64::from(current.game_count) - i64::from(previous.game_count),
+        steam_level: current
+            .steam_level
+            .zip(previous.steam_level)
+            .map(|(now, before)| i64::from(now) - i64::from(before)),
+        week_playtime_minutes: current
+            .total_playtime_minutes
+            .saturating_sub(week_base.total_playtime_minutes),
+    })
+}
+
+/// Current Unix time in seconds.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Bucket size of `steamfetch history`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    Day,
+    /// Weeks start on Monday
+    Week,
+    Month,
+}
+
+impl GroupBy {
+    /// Start of the bucket containing `timestamp`, and its label.
+    fn bucket(self, timestamp: u64) -> (u64, String) {
+        let day = timestamp - timestamp % DAY_SECS;
+        match self {
+            GroupBy::Day => (day, format_date(day)),
+            GroupBy::Week => {
+                // 1970-01-01 was a Thursday.
+                let monday = day - ((day / DAY_SECS + 3) % 7) * DAY_SECS;
+                (monday, format_date(monday))
+            }
+            GroupBy::Month => {
+                let label = format_date(day)[..7].to_string();
+                let start = parse_date(&format!("{}-01", label)).unwrap_or(day);
+                (start, label)
+            }
+        }
+    }
+}
+
+/// A point in time given as `YYYY-MM-DD` (UTC) or relative to now, like
+/// `7d` or `4w`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeArg {
+    Date(u64),
+    Ago(u64),
+}
+
+impl FromStr for TimeArg {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let relative = |suffix: char, unit: u64| {
+            value
+                .strip_suffix(suffix)
+                .and_then(|n| n.parse::<u64>().ok())
+                .map(|n| TimeArg::Ago(n.saturating_mul(unit)))
+        };
+        parse_date(value)
+            .map(TimeArg::Date)
+            .or_else(|| relative('d', DAY_SECS))
+            .or_else(|| relative('w', WEEK_SECS))
+            .ok_or_else(|| {
+                format!(
+                    "'{}' is not a date (YYYY-MM-DD) or age like 7d or 2w",
+                    value
+                )
+            })
+    }
+}
+
+impl TimeArg {
+    /// `end_of_day` makes a date inclusive, for `--until`.
+    pub fn resolve(self, now: u64, end_of_day: bool) -> u64 {
+        match self {
+            TimeArg::Date(midnight) if end_of_day => midnight + DAY_SECS - 1,
+            TimeArg::Date(midnight) => midnight,
+            TimeArg::Ago(secs) => now.saturating_sub(secs),
+        }
+    }
+}
+
+/// Changes between snapshots, grouped into periods.
+#[derive(Debug, Serialize)]
+pub struct HistoryReport {
+    pub group_by: Option<GroupBy>,
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+    /// Snapshots inside the window.
+    pub snapshot_count: usize,
+    pub periods: Vec<Period>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Period {
+    /// Unix timestamp of the period start (of the first change without grouping).
+    pub start: u64,
+    pub label: String,
+    pub playtime_minutes: u32,
+    /// None when no pair of snapshots in the period had complete counts.
+    pub achievements: Option<i64>,
+    /// Playtime gained per game, most first.
+    pub games: Vec<GameSnapshot>,
+    /// Games that appeared in the library during the period.
+    pub new_games: Vec<NewGame>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NewGame {
+    pub appid: u32,
+    pub name: String,
+}
+
+/// Compares each snapshot in `[since, until]` with the one before it (which
+/// may predate `since`) and adds the changes to the period of the later one.
+pub fn report(
+    history: &[Snapshot],
+    since: Option<u64>,
+    until: Option<u64>,
+    group_by: Option<GroupBy>,
+) -> HistoryReport {
+    let in_window = |s: &Snapshot| {
+        since.is_none_or(|t| s.timestamp >= t) && until.is_none_or(|t| s.timestamp <= t)
+    };
+
+    let mut periods: BTreeMap<u64, Period> = BTreeMap::new();
+    let mut gains: BTreeMap<u64, BTreeMap<u32, GameSnapshot>> = BTreeMap::new();
+    for pair in history.windows(2) {
+        let (before, after) = (&pair[0], &pair[1]);
+        if !in_window(after) {
+            continue;
+        }
+        let (start, label) = match group_by {
+            Some(group_by) => group_by.bucket(after.timestamp),
+            None => (0, String::new()),
+        };
+        let period = periods.entry(start).or_insert_with(|| Period {
+            start: if group_by.is_some() {
+                start
+            } else {
+                before.timestamp
+            },
+            label,
+            playtime_minutes: 0,
+            achievements: None,
+            games: Vec::new(),
+            new_games: Vec::new(),
+        });
+
+        period.playtime_minutes += after
+            .total_playtime_minutes
+            .saturating_sub(before.total_playtime_minutes);
+        if let (Some(now), Some(then)) = (after.achievements_unlocked, before.achievements_unlocked)
+        {
+            *period.achievements.get_or_insert(0) += i64::from(now) - i64::from(then);
+        }
+
+        let previous: BTreeMap<u32, u32> = before
+            .games
+            .iter()
+            .map(|g| (g.appid, g.playtime_minutes))
+            .collect();
+        let period_gains = gains.entry(start).or_default();
+        for game in &after.games {
+            let played_before = match previous.get(&game.appid) {
+                Some(&minutes) => minutes,
+                // Snapshots without a game list cannot tell what is new.
+                None if before.games.is_empty() => continue,
+                None => {
+                    period.new_games.push(NewGame {
+                        appid: game.appid,
+                        name: game.name.clone(),
+                    });
+                    0
+                }
+            };
+            let gained = game.playtime_minutes.saturating_sub(played_before);
+            if gained > 0 {
+                period_gains
+                    .entry(game.appid)
+                    .or_insert_with(|| GameSnapshot {
+                        playtime_minutes: 0,
+                        ..game.clone()
+                    })
+                    .playtime_minutes += gained;
+            }
+        }
+    }
+
+    let mut periods: Vec<Period> = periods.into_values().collect();
+    for period in &mut periods {
+        let key = if group_by.is_some() { period.start } else { 0 };
+        let mut games: Vec<GameSnapshot> = gains
+            .remove(&key)
+            .unwrap_or_default()
+            .into_values()
+            .collect();
+        games.sort_by(|a, b| {
+            b.playtime_minutes
+                .cmp(&a.playtime_minutes)
+                .then(a.name.cmp(&b.name))
+        });
+        period.games = games;
+        if group_by.is_none() {
+            period.label = format!(
+                "{} – {}",
+                format_date(period.start),
+                history
+                    .iter()
+                    .rev()
+                    .find(|s| in_window(s))
+                    .map_or(String::new(), |s| format_date(s.timestamp))
+            );
+        }
+    }
+
+    HistoryReport {
+        group_by,
+        since,
+        until,
+        snapshot_count: history.iter().filter(|s| in_window(s)).count(),
+        periods,
+    }
+}
+
+/// Renders each period with its top games and new purchases.
+pub fn render_report(report: &HistoryReport) -> String {
+    if report.periods.is_empty() {
+        return format!(
+            "{}\n",
+            "No changes recorded in this range. History is saved each time steamfetch runs."
+                .dimmed()
+        );
+    }
+
+    let mut out = String::new();
+    for period in &report.periods {
+        let mut summary = vec![format!(
+            "+{} playtime",
+            format_playtime(period.playtime_minutes)
+        )];
+        if let Some(n) = period.achievements {
+            summary.push(format!("{:+} achievement{}", n, plural(n)));
+        }
+        if !period.new_games.is_empty() {
+            let count = period.new_games.len();
+            summary.push(format!("{} new game{}", count, plural(count as i64)));
+        }
+        out.push_str(&format!(
+            "{}  {}\n",
+            period.label.bold().cyan(),
+            summary.join(" · ")
+        ));
+
+        let shown = &period.games[..period.games.len().min(TOP_GAMES_PER_PERIOD)];
+        let name_width = shown
+            .iter()
+            .map(|g| g.name.width())
+            .max()
+            .unwrap_or(0)
+            .min(MAX_NAME_WIDTH);
+        for game in shown {
+            out.push_str(&format!(
+                "  {}  +{}\n",
+                truncate(&game.name, name_width),
+                format_playtime(game.playtime_minutes)
+            ));
+        }
+        if period.games.len() > shown.len() {
+            out.push_str(&format!(
+                "  {}\n",
+                format!("and {} more", period.games.len() - shown.len()).dimmed()
+            ));
+        }
+        if !period.new_games.is_empty() {
+            let names: Vec<&str> = period.new_games.iter().map(|g| g.name.as_str()).collect();
+            out.push_str(&format!("  {} {}\n", "New:".green(), names.join(", ")));
+        }
+    }
+    out.push_str(&format!(
+        "{}\n",
+        format!("{} snapshots", report.snapshot_count).dimmed()
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_path;
+
+    const DAY: u64 = 86_400;
+
+    fn snapshot(timestamp: u64, playtime: u32, achievements: Option<u32>) -> Snapshot {
+        Snapshot {
+            timestamp,
+            game_count: 10,
+            total_playtime_minutes: playtime,
+            steam_level: Some(5),
+            achievements_unlocked: achievements,
+            games: Vec::new(),
+        }
+    }
+
+    fn stats_with_games() -> SteamStats {
+        let mut stats = crate::demo_stats();
+        stats.games = vec![
+            crate::steam::Game {
+                appid: 570,
+                name: Some("Dota 2".to_string()),
+                playtime_forever: 600,
+                playtime_2weeks: 0,
+                rtime_last_played: 0,
+            },
+            crate::steam::Game {
+                appid: 10,
+                name: None,
+                playtime_forever: 0,
+                playtime_2weeks: 0,
+                rtime_last_played: 0,
+            },
+        ];
+        stats
+    }
+
+    #[test]
+    fn test_snapshot_from_stats_keeps_games_and_totals() {
+        let snapshot = Snapshot::from_stats(&stats_with_games(), 42);
+        assert_eq!(snapshot.timestamp, 42);
+        assert_eq!(snapshot.game_count, 486);
+        assert_eq!(snapshot.steam_level, Some(42));
+        assert_eq!(snapshot.achievements_unlocked, Some(3241));
+        assert_eq!(snapshot.games[0].name, "App 10");
+        assert_eq!(snapshot.games[1].playtime_minutes, 600);
+    }
+
+    #[test]
+    fn test_snapshot_skips_incomplete_achievement_counts() {
+        let mut stats = stats_with_games();
+        if let Some(achievements) = stats.achievement_stats.as_mut() {
+            achievements.failed_games = 1;
+        }
+        assert_eq!(Snapshot::from_stats(&stats, 0).achievements_unlocked, None);
+    }
+
+    #[test]
+    fn test_delta_compares_with_last_and_week_old_snapshots() {
+        let now = 100 * DAY;
+        let history = vec![
+            snapshot(now - 30 * DAY, 1_000, Some(10)),
+            snapshot(now - 8 * DAY, 1_200, Some(12)),
+            snapshot(now - 2 * DAY, 1_500, None),
+        ];
+        let mut current = snapshot(now, 1_800, Some(15));
+        current.game_count = 12;
+        current.steam_level = Some(6);
+
+        let delta = delta(&history, &current).expect("history exists");
+        assert_eq!(delta.since, now - 2 * DAY);
+        assert_eq!(delta.playtime_minutes, 300);
+        assert_eq!(delta.achievements, None);
+        assert_eq!(delta.games, 2);
+        assert_eq!(delta.steam_level, Some(1));
+        assert_eq!(delta.week_playtime_minutes, 600);
+    }
+
+    #[test]
+    fn test_delta_week_falls_back_to_oldest_snapshot() {
+        let history = vec![
+            snapshot(10 * DAY, 100, Some(1)),
+            snapshot(11 * DAY, 150, Some(2)),
+        ];
+        let delta = delta(&history, &snapshot(12 * DAY, 200, Some(4))).unwrap();
+        assert_eq!(delta.week_playtime_minutes, 100);
+        assert_eq!(delta.achievements, Some(2));
+        assert!(super::delta(&[], &snapshot(0, 0, None)).is_none());
+    }
+
+    #[test]
+    fn test_record_appends_only_changed_snapshots() {
+        let dir = unique_temp_path("record");
+        let path = dir.join("history.jsonl");
+        let store = HistoryStore::new(path.clone());
+        let mut stats = stats_with_games();
+
+        assert_eq!(store.record(&stats, 1_000).unwrap(), None);
+        let unchanged = store.record(&stats, 2_000).unwrap().expect("delta");
+        assert_eq!(unchanged.since, 1_000);
+        assert_eq!(unchanged.playtime_minutes, 0);
+        assert_eq!(store.load().unwrap().len(), 1);
+
+        stats.total_playtime_minutes += 90;
+        let changed = store.record(&stats, 3_000).unwrap().expect("delta");
+        assert_eq!(changed.playtime_minutes, 90);
+        let history = store.load().unwrap();
+        assert_eq!(
+            history.iter().map(|s| s.timestamp).collect::<Vec<_>>(),
+            vec![1_000, 3_000]
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_record_writes_only_changed_games() {
+        let dir = unique_temp_path("record-changes");
+        let path = dir.join("history.jsonl");
+        let store = HistoryStore::new(path.clone());
+        let mut stats = stats_with_games();
+        stats.games = (1..=500)
+            .map(|appid| crate::steam::Game {
+                appid,
+                name: Some(format!("Game {}", appid)),
+                playtime_forever: 60,
+                playtime_2weeks: 0,
+                rtime_last_played: 0,
+            })
+            .collect();
+
+        store.record(&stats, 0).unwrap();
+        let first_line = fs::read_to_string(&path).unwrap().len();
+        for run in 1..=100 {
+            stats.games[run % 7].playtime_forever += 30;
+            stats.total_playtime_minutes += 30;
+            store.record(&stats, run as u64 * DAY).unwrap();
+        }
+        let size = fs::read_to_string(&path).unwrap().len();
+        assert!(
+            size < first_line * 2,
+            "100 runs grew the file from {} to {} bytes",
+            first_line,
+            size
+        );
+
+        let history = store.load().unwrap();
+        assert_eq!(history.len(), 101);
+        assert_eq!(history[100], Snapshot::from_stats(&stats, 100 * DAY));
+        assert_eq!(history[1].games.len(), 500);
+        assert_eq!(history[1].games[1].playtime_minutes, 90);
+
+        // A game leaving the library needs the full list again.
+        stats.games.pop();
+        store.record(&stats, 101 * DAY).unwrap();
+        let history = store.load().unwrap();
+        assert_eq!(history[101].games.len(), 499);
+        assert_eq!(history[101], Snapshot::from_stats(&stats, 101 * DAY));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_load_skips_corrupt_lines_and_missing_file() {
+        let dir = unique_temp_path("corrupt");
+        let path = dir.join("history.jsonl");
+        let store = HistoryStore::new(path.clone());
+        assert!(store.load().unwrap().is_empty());
+
+        store.append(&snapshot(20, 5, None)).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "{{\"timestamp\":").unwrap();
+        store.append(&snapshot(10, 1, None)).unwrap();
+
+        let history = store.load().unwrap();
+        assert_eq!(
+            history.iter().map(|s| s.timestamp).collect::<Vec<_>>(),
+            vec![10, 20]
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    fn with_games(mut snapshot: Snapshot, games: &[(u32, &str, u32)]) -> Snapshot {
+        snapshot.games = games
+            .iter()
+            .map(|&(appid, name, playtime_minutes)| GameSnapshot {
+                appid,
+                name: name.to_string(),
+                playtime_minutes,
+            })
+            .collect();
+        snapshot
+    }
+
+    // 2024-01-01 was a Monday.
+    const JAN_1: u64 = 1_704_067_200;
+
+    fn library_history() -> Vec<Snapshot> {
+        vec![
+            with_games(snapshot(JAN_1, 100, Some(10)), &[(1, "Alpha", 100)]),
+            with_games(snapshot(JAN_1 + DAY, 160, Some(12)), &[(1, "Alpha", 160)]),
+            with_games(
+                snapshot(JAN_1 + 8 * DAY, 190, Some(13)),
+                &[(1, "Alpha", 170), (2, "Beta", 20)],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_time_arg_parses_dates_and_relative_ages() {
+        assert_eq!("2024-01-01".parse(), Ok(TimeArg::Date(JAN_1)));
+        assert_eq!("3d".parse(), Ok(TimeArg::Ago(3 * DAY)));
+        assert_eq!("2w".parse(), Ok(TimeArg::Ago(14 * DAY)));
+        assert!("yesterday".parse::<TimeArg>().is_err());
+        assert!("2024-13-01".parse::<TimeArg>().is_err());
+
+        assert_eq!(TimeArg::Date(JAN_1).resolve(0, true), JAN_1 + DAY - 1);
+        assert_eq!(TimeArg::Ago(DAY).resolve(JAN_1, false), JAN_1 - DAY);
+    }
+
+    #[test]
+    fn test_group_by_buckets_start_on_monday_and_first_of_month() {
+        let wednesday = JAN_1 + 2 * DAY + 3600;
+        assert_eq!(
+            GroupBy::Day.bucket(wednesday),
+            (JAN_1 + 2 * DAY, "2024-01-03".into())
+        );
+        assert_eq!(
+            GroupBy::Week.bucket(wednesday),
+            (JAN_1, "2024-01-01".into())
+        );
+        assert_eq!(GroupBy::Month.bucket(JAN_1 + 40 * DAY).1, "2024-02");
+        assert_eq!(GroupBy::Month.bucket(JAN_1 + 40 * DAY).0, JAN_1 + 31 * DAY);
+    }
+
+    #[test]
+    fn test_report_sums_whole_range_without_grouping() {
+        let report = report(&library_history(), None, None, None);
+        assert_eq!(report.snapshot_count, 3);
+        assert_eq!(report.periods.len(), 1);
+        let period = &report.periods[0];
+        assert_eq!(period.label, "2024-01-01 – 2024-01-09");
+        assert_eq!(period.playtime_minutes, 90);
+        assert_eq!(period.achievements, Some(3));
+        assert_eq!(
+            period.new_games,
+            vec![NewGame {
+                appid: 2,
+                name: "Beta".into()
+            }]
+        );
+        let games: Vec<(&str, u32)> = period
+            .games
+            .iter()
+            .map(|g| (g.name.as_str(), g.playtime_minutes))
+            .collect();
+        assert_eq!(games, vec![("Alpha", 70), ("Beta", 20)]);
+    }
+
+    #[test]
+    fn test_report_groups_by_week_and_respects_since() {
+        let history = library_history();
+        let weekly = report(&history, None, None, Some(GroupBy::Week));
+        let labels: Vec<&str> = weekly.periods.iter().map(|p| p.label.as_str()).collect();
+        assert_eq!(labels, vec!["2024-01-01", "2024-01-08"]);
+        assert_eq!(weekly.periods[0].playtime_minutes, 60);
+        assert!(weekly.periods[0].new_games.is_empty());
+        assert_eq!(weekly.periods[1].achievements, Some(1));
+
+        // The snapshot before `since` is still the baseline for the first change.
+        let recent = report(&history, Some(JAN_1 + 2 * DAY), None, None);
+        assert_eq!(recent.snapshot_count, 1);
+        assert_eq!(recent.periods[0].playtime_minutes, 30);
+
+        let empty = report(&history, None, Some(JAN_1), None);
+        assert!(empty.periods.is_empty());
+        assert!(render_report(&empty).contains("No changes"));
+    }
+
+    #[test]
+    fn test_render_report_lists_games_and_purchases() {
+        let text = render_report(&report(&library_history(), None, None, None));
+        assert!(text.contains("+1h 30m playtime · +3 achievements · 1 new game\n"));
+        assert!(text.contains("  Alpha  +1h 10m"));
+        assert!(text.contains("New: Beta"));
+        assert!(text.contains("3 snapshots"));
+    }
+}
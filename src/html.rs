@@ -0,0 +1,177 @@
+//! `--format html`: a self-contained `<section>` with inline colors, for
+//! personal sites.
+
+use crate::config::DisplayConfig;
+use crate::display::{
+    format_number, format_playtime, rarest_label, rarest_shown, recently_played, stat_rows,
+    top_games,
+};
+use crate::modules::{Module, ModuleKind};
+use crate::steam::SteamStats;
+
+/// The stats table, then the list sections in `display.modules` order.
+pub fn render(stats: &SteamStats, display: &DisplayConfig) -> String {
+    let mut out = String::from("<section class=\"steamfetch\">\n");
+    out.push_str(&format!("<h2>{}@Steam</h2>\n", escape(&stats.username)));
+    let rows = stat_rows(stats, display);
+    if !rows.is_empty() {
+        out.push_str("<table>\n");
+        for row in rows {
+            let title = match row.title {
+                Some(title) => format!(
+                    "<td class=\"title\" style=\"color: {}\">{}</td>",
+                    row.color, title
+                ),
+                None => String::new(),
+            };
+            out.push_str(&format!(
+                "<tr><th>{}</th><td>{}</td>{}</tr>\n",
+                escape(&row.label),
+                escape(&row.value),
+                title
+            ));
+        }
+        out.push_str("</table>\n");
+    }
+
+    for module in &display.modules {
+        match module.kind {
+            ModuleKind::TopPlayed => {
+                let games = top_games(stats, display);
+                if games.is_empty() {
+                    continue;
+                }
+                heading(&mut out, module, module.label());
+                out.push_str("<ol>\n");
+                for game in games {
+                    out.push_str(&format!(
+                        "<li>{} <small>{}h</small></li>\n",
+                        escape(&game.name),
+                        format_number(game.playtime_hours())
+                    ));
+                }
+                out.push_str("</ol>\n");
+            }
+            ModuleKind::RecentlyPlayed => {
+                let games = recently_played(stats, display);
+                if games.is_empty() {
+                    continue;
+                }
+                heading(&mut out, module, module.label());
+                out.push_str("<ul>\n");
+                for game in games {
+                    out.push_str(&format!(
+                        "<li>{} <small>{}</small></li>\n",
+                        escape(&game.name),
+                        format_playtime(game.playtime_minutes)
+                    ));
+                }
+                out.push_str("</ul>\n");
+            }
+            ModuleKind::Rarest => match rarest_shown(stats, display) {
+                [] => {}
+                [rarest] => {
+                    heading(&mut out, module, rarest_label(module, 1));
+                    out.push_str(&format!(
+                        "<p><strong>{}</strong> in {} <small>{:.1}% of players</small></p>\n",
+                        escape(&rarest.name),
+                        escape(&rarest.game),
+                        rarest.percent
+                    ));
+                }
+                unlocks => {
+                    heading(&mut out, module, module.label());
+                    out.push_str("<ol>\n");
+                    for unlock in unlocks {
+                        out.push_str(&format!(
+                            "<li><strong>{}</strong> {} <small>{:.1}%</small></li>\n",
+                            escape(&unlock.name),
+                            escape(&unlock.game),
+                            unlock.percent
+                        ));
+                    }
+                    out.push_str("</ol>\n");
+                }
+            },
+            _ => {}
+        }
+    }
+    out.push_str("</section>\n");
+    out
+}
+
+/// Modules with `title = false` get no heading.
+fn heading(out: &mut String, module: &Module, label: &str) {
+    if module.title {
+        out.push_str(&format!("<h3>{}</h3>\n", escape(label)));
+    }
+}
+
+/// Escapes text for HTML and SVG content and attribute values.
+pub(crate) fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::{Rgb, Theme};
+
+    #[test]
+    fn test_render_demo_colors_titles_inline() {
+        let display = DisplayConfig {
+            rarest_count: 5,
+            ..DisplayConfig::default()
+        };
+        let html = render(&crate::demo_stats(), &display);
+        let (title, color) = crate::display::games_title(486);
+        assert!(html.starts_with("<section class=\"steamfetch\">\n<h2>unhappychoice@Steam</h2>"));
+        assert!(html.contains(&format!(
+            "<tr><th>Games</th><td>486</td><td class=\"title\" style=\"color: {}\">{}</td></tr>",
+            Rgb::from(color),
+            title
+        )));
+        assert!(html.contains("<li>Borderlands 3 <small>478h</small></li>"));
+        assert!(html.contains("<li>Hades II <small>8h 0m</small></li>"));
+        assert!(html.contains("<h3>Rarest Unlocks</h3>"));
+        assert!(html.ends_with("</section>\n"));
+    }
+
+    #[test]
+    fn test_render_follows_theme_and_toggles() {
+        let display = DisplayConfig {
+            show_rarest: false,
+            show_top_games: 0,
+            theme: Theme {
+                title_palette: vec![Rgb(1, 2, 3)],
+                ..Theme::default()
+            },
+            ..DisplayConfig::default()
+        };
+        let html = render(&crate::demo_stats(), &display);
+        assert!(html.contains("<td class=\"title\" style=\"color: #010203\">Abyss Keeper</td>"));
+        assert!(!html.contains("Top Played"));
+        assert!(!html.contains("Rarest"));
+        assert!(html.contains("Recently Played"));
+    }
+
+    #[test]
+    fn test_render_escapes_names() {
+        let mut stats = crate::demo_stats();
+        stats.top_games[0].name = "<b>Tom & Jerry's</b>".to_string();
+        let html = render(&stats, &DisplayConfig::default());
+        assert!(html.contains("<li>&lt;b&gt;Tom &amp; Jerry&#39;s&lt;/b&gt; <small>"));
+        assert!(!html.contains("<b>Tom"));
+    }
+}
@@ -1,13 +1,25 @@
 use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::ValueEnum;
 use image::DynamicImage;
 use std::fs;
 use std::io::{self, Cursor, Write};
 use std::path::PathBuf;
 
-use crate::ImageProtocol;
+use crate::color;
+use crate::steam::SteamStats;
+use crate::theme::Rgb;
 
 const KITTY_CHUNK_SIZE: usize = 4096;
 
+/// Terminal graphics protocol used to draw the avatar.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ImageProtocol {
+    Auto,
+    Kitty,
+    Iterm,
+    Sixel,
+}
+
 pub async fn load_cached_or_download(url: &str, cache_key: &str) -> Option<DynamicImage> {
     let safe_key = sanitize_cache_key(cache_key);
     if let Some(img) = load_from_cache(&safe_key) {
@@ -18,6 +30,12 @@ pub async fn load_cached_or_download(url: &str, cache_key: &str) -> Option<Dynam
     Some(img)
 }
 
+/// The profile avatar, from the image cache when it was downloaded before.
+pub async fn load_avatar(stats: &SteamStats) -> Option<DynamicImage> {
+    let url = stats.avatar_url.as_ref()?;
+    load_cached_or_download(url, &format!("avatar_{}.png", stats.username)).await
+}
+
 fn sanitize_cache_key(key: &str) -> String {
     key.chars()
         .map(|c| {
@@ -326,11 +344,12 @@ fn print_block(img: &DynamicImage, max_cols: u32) -> io::Result<u32> {
             };
             write!(
                 stdout,
-                "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m▄",
-                bot[0], bot[1], bot[2], top[0], top[1], top[2]
+                "{}{}▄",
+                color::fg(Rgb(bot[0], bot[1], bot[2])),
+                color::bg(Rgb(top[0], top[1], top[2]))
             )?;
         }
-        writeln!(stdout, "\x1b[0m")?;
+        writeln!(stdout, "{}", color::reset())?;
     }
     stdout.flush()?;
     Ok(term_rows)
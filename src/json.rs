@@ -0,0 +1,198 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::games::GameEntry;
+use crate::history::HistoryReport;
+use crate::steam::{GameAchievements, SteamStats};
+
+/// Version of the JSON document emitted by `--format json`.
+///
+/// Bump this whenever a field is renamed, removed or changes type so that
+/// consumers can detect breaking changes. Adding new fields is not breaking.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct JsonDocument<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    stats: &'a SteamStats,
+}
+
+pub fn render(stats: &SteamStats) -> Result<String> {
+    let document = JsonDocument {
+        schema_version: SCHEMA_VERSION,
+        stats,
+    };
+    serde_json::to_string_pretty(&document).context("Failed to serialize stats as JSON")
+}
+
+#[derive(Serialize)]
+struct GamesDocument<'a> {
+    schema_version: u32,
+    game_count: u32,
+    games: &'a [GameEntry],
+}
+
+/// JSON for `steamfetch games`; `game_count` is the number owned, before filters.
+pub fn render_games(games: &[GameEntry], owned: u32) -> Result<String> {
+    let document = GamesDocument {
+        schema_version: SCHEMA_VERSION,
+        game_count: owned,
+        games,
+    };
+    serde_json::to_string_pretty(&document).context("Failed to serialize games as JSON")
+}
+
+#[derive(Serialize)]
+struct AchievementsDocument<'a> {
+    schema_version: u32,
+    unlocked_count: usize,
+    total_count: usize,
+    #[serde(flatten)]
+    details: &'a GameAchievements,
+}
+
+/// JSON for `steamfetch achievements`.
+pub fn render_achievements(details: &GameAchievements) -> Result<String> {
+    let document = AchievementsDocument {
+        schema_version: SCHEMA_VERSION,
+        unlocked_count: details.unlocked_count(),
+        total_count: details.achievements.len(),
+        details,
+    };
+    serde_json::to_string_pretty(&document).context("Failed to serialize achievements as JSON")
+}
+
+#[derive(Serialize)]
+struct HistoryDocument<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    report: &'a HistoryReport,
+}
+
+/// JSON for `steamfetch history`.
+pub fn render_history(report: &HistoryReport) -> Result<String> {
+    let document = HistoryDocument {
+        schema_version: SCHEMA_VERSION,
+        report,
+    };
+    serde_json::to_string_pretty(&document).context("Failed to serialize history as JSON")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn render_demo() -> Value {
+        let json = render(&crate::demo_stats()).expect("demo stats should serialize");
+        serde_json::from_str(&json).expect("output should be valid JSON")
+    }
+
+    #[test]
+    fn test_render_includes_schema_version() {
+        let value = render_demo();
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_render_includes_profile_fields() {
+        let value = render_demo();
+        assert_eq!(value["username"], "unhappychoice");
+        assert_eq!(value["game_count"], 486);
+        assert_eq!(value["unplayed_count"], 123);
+        assert_eq!(value["total_playtime_minutes"], 170820);
+        assert_eq!(value["account_created"], 1234567890);
+        assert_eq!(value["steam_level"], 42);
+        assert!(value["avatar_url"].is_null());
+    }
+
+    #[test]
+    fn test_render_includes_game_lists() {
+        let value = render_demo();
+        assert_eq!(value["top_games"][0]["name"], "Borderlands 3");
+        assert_eq!(value["top_games"][0]["playtime_minutes"], 28680);
+        assert_eq!(value["top_games"].as_array().map(Vec::len), Some(3));
+        assert_eq!(value["recently_played"][1]["name"], "Hades II");
+        assert_eq!(value["recently_played"][1]["playtime_minutes"], 480);
+        assert_eq!(value["playtime_distribution"][2]["label"], "1-10h");
+        assert_eq!(value["playtime_distribution"][2]["game_count"], 142);
+        assert!(value["playtime_distribution"][5]["max_minutes"].is_null());
+    }
+
+    #[test]
+    fn test_render_includes_achievement_stats() {
+        let value = render_demo();
+        let achievements = &value["achievement_stats"];
+        assert_eq!(achievements["total_achieved"], 3241);
+        assert_eq!(achievements["total_possible"], 5892);
+        assert_eq!(achievements["perfect_games"], 24);
+        assert_eq!(achievements["rarest"]["name"], "Impossible Task");
+        assert_eq!(achievements["rarest"]["game"], "Dark Souls III");
+        assert_eq!(achievements["rarest"]["percent"], 0.1);
+        assert_eq!(achievements["failed_games"], 0);
+        assert_eq!(achievements["rarest_unlocks"][0]["name"], "Impossible Task");
+        assert_eq!(
+            achievements["rarest_unlocks"].as_array().map(Vec::len),
+            Some(5)
+        );
+        assert_eq!(achievements["near_complete"][0]["name"], "Elden Ring");
+        assert_eq!(achievements["near_complete"][0]["achieved"], 41);
+        assert_eq!(achievements["near_complete"][0]["total"], 42);
+    }
+
+    #[test]
+    fn test_render_missing_optionals_are_null() {
+        let mut stats = crate::demo_stats();
+        stats.achievement_stats = None;
+        stats.steam_level = None;
+        stats.account_created = None;
+        let value: Value = serde_json::from_str(&render(&stats).unwrap()).unwrap();
+        assert!(value["achievement_stats"].is_null());
+        assert!(value["steam_level"].is_null());
+        assert!(value["account_created"].is_null());
+    }
+
+    #[test]
+    fn test_render_games_wraps_entries() {
+        let games = vec![GameEntry {
+            appid: 570,
+            name: "Dota 2".to_string(),
+            playtime_minutes: 600,
+            playtime_2weeks_minutes: 0,
+            last_played: None,
+        }];
+        let value: Value = serde_json::from_str(&render_games(&games, 3).unwrap()).unwrap();
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+        assert_eq!(value["game_count"], 3);
+        assert_eq!(value["games"][0]["appid"], 570);
+        assert_eq!(value["games"][0]["playtime_2weeks_minutes"], 0);
+        assert!(value["games"][0]["last_played"].is_null());
+    }
+
+    #[test]
+    fn test_render_achievements_includes_counts() {
+        let details = GameAchievements {
+            appid: 620,
+            game: "Portal 2".to_string(),
+            achievements: vec![crate::steam::AchievementDetail {
+                api_name: "ACH_WAKE_UP".to_string(),
+                name: "Wake Up Call".to_string(),
+                description: None,
+                hidden: true,
+                achieved: true,
+                unlock_time: Some(1_700_000_000),
+                global_percent: Some(75.5),
+            }],
+        };
+        let value: Value = serde_json::from_str(&render_achievements(&details).unwrap()).unwrap();
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+        assert_eq!(value["appid"], 620);
+        assert_eq!(value["game"], "Portal 2");
+        assert_eq!(value["unlocked_count"], 1);
+        assert_eq!(value["total_count"], 1);
+        assert_eq!(value["achievements"][0]["api_name"], "ACH_WAKE_UP");
+        assert_eq!(value["achievements"][0]["unlock_time"], 1_700_000_000);
+        assert!(value["achievements"][0]["description"].is_null());
+    }
+}
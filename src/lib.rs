@@ -0,0 +1,317 @@
+//! Display your Steam stats in the terminal, or embed them in your own tools.
+//!
+//! The [`steam::SteamClient`] builder fetches an aggregated [`steam::SteamStats`]
+//! from the Steam Web API, which [`display`] and [`json`] turn into text.
+//!
+//! ```no_run
+//! use steamfetch::config::DisplayConfig;
+//! use steamfetch::steam::SteamClient;
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let client = SteamClient::new("API_KEY".into(), "76561197960287930".into())
+//!     .with_concurrency(4);
+//! let stats = client.fetch_stats().await?;
+//!
+//! let card = steamfetch::display::render_to_string(&stats, &DisplayConfig::default(), 80);
+//! println!("{}", card);
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod achievements;
+pub mod cache;
+pub mod card;
+pub mod charts;
+pub mod color;
+pub mod config;
+pub mod display;
+pub mod games;
+pub mod history;
+pub mod html;
+pub mod image_display;
+pub mod json;
+pub mod logo;
+pub mod markdown;
+pub mod modules;
+pub mod steam;
+pub mod template;
+#[cfg(test)]
+mod test_support;
+pub mod theme;
+
+use steam::SteamStats;
+
+/// Sample stats used by `--demo`; handy for previews and tests.
+pub fn demo_stats() -> SteamStats {
+    SteamStats {
+        username: "unhappychoice".to_string(),
+        game_count: 486,
+        unplayed_count: 123,
+        total_playtime_minutes: 170820,
+        top_games: vec![
+            steam::GameStat {
+                name: "Borderlands 3".to_string(),
+                playtime_minutes: 28680,
+            },
+            steam::GameStat {
+                name: "Coin Push RPG".to_string(),
+                playtime_minutes: 22620,
+            },
+            steam::GameStat {
+                name: "DRG Survivor".to_string(),
+                playtime_minutes: 15120,
+            },
+        ],
+        achievement_stats: Some(steam::AchievementStats {
+            total_achieved: 3241,
+            total_possible: 5892,
+            perfect_games: 24,
+            rarest: Some(steam::RarestAchievement {
+                name: "Impossible Task".to_string(),
+                game: "Dark Souls III".to_string(),
+                percent: 0.1,
+            }),
+            rarest_unlocks: vec![
+                steam::RarestAchievement {
+                    name: "Impossible Task".to_string(),
+                    game: "Dark Souls III".to_string(),
+                    percent: 0.1,
+                },
+                steam::RarestAchievement {
+                    name: "Lord of Hollownest".to_string(),
+                    game: "Hollow Knight".to_string(),
+                    percent: 0.4,
+                },
+                steam::RarestAchievement {
+                    name: "Is It Over Yet?".to_string(),
+                    game: "Hades".to_string(),
+                    percent: 0.9,
+                },
+                steam::RarestAchievement {
+                    name: "Elden Lord".to_string(),
+                    game: "Elden Ring".to_string(),
+                    percent: 1.7,
+                },
+                steam::RarestAchievement {
+                    name: "Tourist".to_string(),
+                    game: "Borderlands 3".to_string(),
+                    percent: 2.3,
+                },
+            ],
+            failed_games: 0,
+            near_complete: vec![
+                steam::GameCompletion {
+                    appid: 1245620,
+                    name: "Elden Ring".to_string(),
+                    achieved: 41,
+                    total: 42,
+                },
+                steam::GameCompletion {
+                    appid: 1145360,
+                    name: "Hades".to_string(),
+                    achieved: 47,
+                    total: 49,
+                },
+                steam::GameCompletion {
+                    appid: 367520,
+                    name: "Hollow Knight".to_string(),
+                    achieved: 60,
+                    total: 63,
+                },
+            ],
+        }),
+        account_created: Some(1234567890),
+        steam_level: Some(42),
+        recently_played: vec![
+            steam::GameStat {
+                name: "Elden Ring".to_string(),
+                playtime_minutes: 1200,
+            },
+            steam::GameStat {
+                name: "Hades II".to_string(),
+                playtime_minutes: 480,
+            },
+        ],
+        avatar_url: None,
+        installed: Some(steam::InstalledGames {
+            game_count: 38,
+            total_bytes: 612_400_000_000,
+            largest: vec![
+                steam::InstalledGame {
+                    appid: 1086940,
+                    name: "Baldur's Gate 3".to_string(),
+                    size_bytes: 149_800_000_000,
+                },
+                steam::InstalledGame {
+                    appid: 1091500,
+                    name: "Cyberpunk 2077".to_string(),
+                    size_bytes: 78_300_000_000,
+                },
+                steam::InstalledGame {
+                    appid: 397540,
+                    name: "Borderlands 3".to_string(),
+                    size_bytes: 61_200_000_000,
+                },
+            ],
+            libraries: vec![
+                steam::LibraryUsage {
+                    path: "~/.local/share/Steam".to_string(),
+                    game_count: 24,
+                    size_bytes: 298_100_000_000,
+                },
+                steam::LibraryUsage {
+                    path: "/mnt/games/SteamLibrary".to_string(),
+                    game_count: 14,
+                    size_bytes: 314_300_000_000,
+                },
+            ],
+        }),
+        history: Some(steam::HistoryDelta {
+            since: 1_760_000_000,
+            playtime_minutes: 150,
+            achievements: Some(3),
+            games: 2,
+            steam_level: Some(0),
+            week_playtime_minutes: 720,
+        }),
+        playtime_distribution: [
+            ("0h", 0, Some(1), 123),
+            ("<1h", 1, Some(60), 71),
+            ("1-10h", 60, Some(600), 142),
+            ("10-50h", 600, Some(3000), 98),
+            ("50-100h", 3000, Some(6000), 28),
+            ("100h+", 6000, None, 24),
+        ]
+        .into_iter()
+        .map(
+            |(label, min_minutes, max_minutes, game_count)| steam::PlaytimeBucket {
+                label: label.to_string(),
+                min_minutes,
+                max_minutes,
+                game_count,
+            },
+        )
+        .collect(),
+        games: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demo_stats_returns_expected_username() {
+        let stats = demo_stats();
+        assert_eq!(stats.username, "unhappychoice");
+    }
+
+    #[test]
+    fn test_demo_stats_top_games_in_descending_playtime() {
+        let stats = demo_stats();
+        assert!(stats.top_games.len() >= 2);
+        for window in stats.top_games.windows(2) {
+            assert!(window[0].playtime_minutes >= window[1].playtime_minutes);
+        }
+    }
+
+    #[test]
+    fn test_demo_stats_installed_libraries_add_up() {
+        let installed = demo_stats().installed.expect("demo has installed games");
+        let games: u32 = installed.libraries.iter().map(|l| l.game_count).sum();
+        let bytes: u64 = installed.libraries.iter().map(|l| l.size_bytes).sum();
+        assert_eq!(games, installed.game_count);
+        assert_eq!(bytes, installed.total_bytes);
+    }
+
+    #[test]
+    fn test_demo_stats_unplayed_does_not_exceed_total() {
+        let stats = demo_stats();
+        assert!(stats.unplayed_count <= stats.game_count);
+    }
+
+    #[test]
+    fn test_demo_stats_achievements_consistent() {
+        let stats = demo_stats();
+        let ach = stats.achievement_stats.expect("demo has achievements");
+        assert!(ach.total_achieved <= ach.total_possible);
+        assert!(ach.perfect_games <= stats.game_count);
+        let rarest = ach.rarest.expect("demo has rarest achievement");
+        assert!(rarest.percent >= 0.0 && rarest.percent <= 100.0);
+        assert!(!rarest.name.is_empty());
+        assert!(!rarest.game.is_empty());
+    }
+
+    #[test]
+    fn test_demo_stats_playtime_distribution_covers_library() {
+        let stats = demo_stats();
+        let total: u32 = stats
+            .playtime_distribution
+            .iter()
+            .map(|b| b.game_count)
+            .sum();
+        assert_eq!(total, stats.game_count);
+        assert_eq!(
+            stats.playtime_distribution[0].game_count,
+            stats.unplayed_count
+        );
+    }
+
+    #[test]
+    fn test_demo_stats_recently_played_has_entries() {
+        let stats = demo_stats();
+        assert!(!stats.recently_played.is_empty());
+        for game in &stats.recently_played {
+            assert!(!game.name.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_demo_stats_avatar_url_is_none() {
+        assert!(demo_stats().avatar_url.is_none());
+    }
+
+    #[test]
+    fn test_demo_stats_profile_details_are_present() {
+        let stats = demo_stats();
+        let achievements = stats.achievement_stats.as_ref().expect("demo achievements");
+        let rarest = achievements.rarest.as_ref().expect("demo rarest");
+
+        assert_eq!(stats.account_created, Some(1234567890));
+        assert_eq!(stats.steam_level, Some(42));
+        assert_eq!(rarest.name, "Impossible Task");
+        assert_eq!(rarest.game, "Dark Souls III");
+        assert_eq!(stats.recently_played[0].playtime_minutes, 1200);
+    }
+
+    #[test]
+    fn test_demo_stats_fixture_values_are_stable() {
+        let stats = demo_stats();
+        let top_games = stats
+            .top_games
+            .iter()
+            .map(|game| (game.name.as_str(), game.playtime_minutes))
+            .collect::<Vec<_>>();
+        let recently_played = stats
+            .recently_played
+            .iter()
+            .map(|game| (game.name.as_str(), game.playtime_minutes))
+            .collect::<Vec<_>>();
+
+        assert_eq!(stats.game_count, 486);
+        assert_eq!(stats.unplayed_count, 123);
+        assert_eq!(stats.total_playtime_minutes, 170820);
+        assert_eq!(
+            top_games,
+            vec![
+                ("Borderlands 3", 28680),
+                ("Coin Push RPG", 22620),
+                ("DRG Survivor", 15120),
+            ],
+        );
+        assert_eq!(
+            recently_played,
+            vec![("Elden Ring", 1200), ("Hades II", 480)]
+        );
+    }
+}
@@ -0,0 +1,353 @@
+//! ASCII logos drawn next to the stats, from the `[logo]` config table.
+//!
+//! Logo text may switch colors with `$1`..`$9`, as in neofetch; `$$` is a
+//! literal `$`.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use unicode_width::UnicodeWidthStr;
+
+use crate::color;
+use crate::theme::{Rgb, Theme};
+
+/// Names accepted by `[logo] name` and `--logo`.
+pub const BUILTIN_LOGOS: [&str; 5] = ["steam", "small", "deck", "steamos", "none"];
+
+const STEAM: &str = r"              .,,,,.
+        .,'############',.
+     .'####################'.
+   .'#########################.
+  ;###############'' ,.., '####,
+ ;###############' .#;'':#, '###,
+,###############,  #:    :#, ####,
+'##############,   #.    ,#' #####
+  '*#########*'     '*,,*' .######
+     `'*###*'          ,.,;#######
+         .,;;,      .;############
+,',.         ';  ,###############'
+ '####. :,. .,; ,###############'
+  '####.. `'' .,###############'
+    '########################'
+      '*##################*'
+         ''*##########*''
+              ''''''";
+
+const SMALL: &str = r"       .,;####;,.
+    .;###########;.
+  ;#########' .,. '#;
+ ;########' .#'  '#.#;
+,########   #.    .# ##,
+'*#####*'    '*..*' ###
+  `'*'   .,,.    ,####'
+ ',.  '; .###########'
+   '#.;' '#######*'
+       ''''''''";
+
+const DECK: &str = r"$2       .######.
+$2     ####''''####.
+$1  .####.$2      '###.
+$1 ########$2      ###
+$1 ########$2      ###
+$1  '####'$2      .###'
+$2     ####....####'
+$2       '######'";
+
+const STEAMOS: &str = r"$1 ___ _                   $2  ___  ___
+$1/ __| |_ ___ __ _ _ __   $2 / _ \/ __|
+$1\__ \  _/ -_) _` | '  \  $2| (_) \__ \
+$1|___/\__\___\__,_|_|_|_| $2 \___/|___/";
+
+/// A logo's lines with their color placeholders, plus the colors of
+/// `$1`..`$9` set in the `[logo]` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Logo {
+    lines: Vec<String>,
+    colors: Vec<Rgb>,
+}
+
+impl Default for Logo {
+    fn default() -> Self {
+        Self::parse(STEAM)
+    }
+}
+
+impl Logo {
+    pub fn builtin(name: &str) -> Option<Self> {
+        let text = match name {
+            "steam" => STEAM,
+            "small" => SMALL,
+            "deck" => DECK,
+            "steamos" => STEAMOS,
+            "none" => "",
+            _ => return None,
+        };
+        Some(Self::parse(text))
+    }
+
+    /// Logo text; trailing blank lines are dropped.
+    pub fn parse(text: &str) -> Self {
+        let mut lines: Vec<String> = text
+            .lines()
+            .map(|line| line.trim_end_matches('\r').to_string())
+            .collect();
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+        Self {
+            lines,
+            colors: Vec::new(),
+        }
+    }
+
+    /// A built-in logo name, or else the path of a logo file.
+    pub fn load(name_or_path: &str) -> Result<Self> {
+        if let Some(logo) = Self::builtin(name_or_path) {
+            return Ok(logo);
+        }
+        let path = Path::new(name_or_path);
+        if !path.exists() {
+            anyhow::bail!(
+                "Unknown logo '{}' (built-in logos: {})",
+                name_or_path,
+                BUILTIN_LOGOS.join(", ")
+            );
+        }
+        Self::from_file(path)
+    }
+
+    fn from_file(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read logo file: {}", path.display()))?;
+        Ok(Self::parse(&text))
+    }
+
+    /// Colors for `$1`, `$2`, ... in order, overriding the theme's.
+    pub fn with_colors(mut self, colors: Vec<Rgb>) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Columns taken by the widest line.
+    pub fn width(&self) -> usize {
+        self.lines
+            .iter()
+            .map(|line| {
+                segments(line)
+                    .iter()
+                    .map(|(_, text)| text.width())
+                    .sum::<usize>()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Colored lines, each padded to [`Logo::width`].
+    pub fn render(&self, theme: &Theme) -> Vec<String> {
+        let width = self.width();
+        let rows = self.height();
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(row, line)| {
+                let mut out = String::new();
+                let mut line_width = 0;
+                for (slot, text) in segments(line) {
+                    line_width += text.width();
+                    out.push_str(&color::paint(&text, self.color(slot, row, rows, theme)));
+                }
+                out.push_str(&" ".repeat(width - line_width));
+                out
+            })
+            .collect()
+    }
+
+    /// Unset slots follow the theme: `$2` is its accent (or gradient end),
+    /// every other slot the logo color.
+    fn color(&self, slot: usize, row: usize, rows: usize, theme: &Theme) -> Rgb {
+        if let Some(color) = self.colors.get(slot - 1) {
+            return *color;
+        }
+        match slot {
+            2 => theme
+                .accent
+                .or(theme.logo_gradient)
+                .unwrap_or_else(|| theme.logo_color(row, rows)),
+            _ => theme.logo_color(row, rows),
+        }
+    }
+}
+
+/// Splits a line at its `$1`..`$9` placeholders into (slot, text) runs.
+/// Lines start in slot 1 and the slot carries over within the line only.
+fn segments(line: &str) -> Vec<(usize, String)> {
+    let mut out: Vec<(usize, String)> = Vec::new();
+    let mut slot = 1;
+    let mut text = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            text.push(c);
+            continue;
+        }
+        match chars.peek().copied() {
+            Some('$') => {
+                chars.next();
+                text.push('$');
+            }
+            Some(d @ '1'..='9') => {
+                chars.next();
+                if !text.is_empty() {
+                    out.push((slot, std::mem::take(&mut text)));
+                }
+                slot = d as usize - '0' as usize;
+            }
+            _ => text.push('$'),
+        }
+    }
+    if !text.is_empty() {
+        out.push((slot, text));
+    }
+    out
+}
+
+/// The `[logo]` table: a built-in `name` or a logo `file`, and optional
+/// colors for its `$1`..`$9` placeholders.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LogoConfig {
+    pub name: Option<String>,
+    pub file: Option<PathBuf>,
+    pub colors: Option<Vec<Rgb>>,
+}
+
+impl LogoConfig {
+    /// `file` wins over `name`; a relative `file` is resolved against
+    /// `base_dir`, the directory of the config file.
+    pub fn resolve(self, base_dir: Option<&Path>) -> Result<Logo> {
+        let logo = match (&self.file, self.name.as_deref()) {
+            (Some(file), _) => {
+                let path = match base_dir {
+                    Some(dir) if file.is_relative() => dir.join(file),
+                    _ => file.clone(),
+                };
+                Logo::from_file(&path)?
+            }
+            (None, Some(name)) => Logo::builtin(name).with_context(|| {
+                format!(
+                    "Unknown logo '{}' (built-in logos: {})",
+                    name,
+                    BUILTIN_LOGOS.join(", ")
+                )
+            })?,
+            (None, None) => Logo::default(),
+        };
+        if let Some(colors) = &self.colors {
+            if colors.len() > 9 {
+                anyhow::bail!("colors takes at most 9 entries ($1..$9)");
+            }
+        }
+        Ok(logo.with_colors(self.colors.unwrap_or_default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+
+    #[test]
+    fn test_builtin_logos_measure_their_own_size() {
+        let steam = Logo::default();
+        assert_eq!((steam.width(), steam.height()), (34, 18));
+        for name in BUILTIN_LOGOS {
+            let logo = Logo::builtin(name).unwrap_or_else(|| panic!("{name}"));
+            assert!(logo.height() <= steam.height(), "{name}");
+        }
+        let small = Logo::builtin("small").unwrap();
+        assert!(small.width() < steam.width() && small.height() < steam.height());
+        let none = Logo::builtin("none").unwrap();
+        assert_eq!((none.width(), none.height()), (0, 0));
+        assert!(Logo::builtin("arch").is_none());
+        assert!(Logo::load("arch").unwrap_err().to_string().contains("deck"));
+    }
+
+    #[test]
+    fn test_segments_split_at_color_placeholders() {
+        assert_eq!(segments("ab"), vec![(1, "ab".to_string())]);
+        assert_eq!(
+            segments("$2ab$1c"),
+            vec![(2, "ab".to_string()), (1, "c".to_string())]
+        );
+        assert_eq!(segments("$$5 $x"), vec![(1, "$5 $x".to_string())]);
+        assert_eq!(segments("$3"), vec![]);
+    }
+
+    #[test]
+    fn test_render_pads_lines_and_applies_slot_colors() {
+        let logo = Logo::parse("$1#$2##\n#\n\n").with_colors(vec![Rgb(1, 2, 3)]);
+        assert_eq!((logo.width(), logo.height()), (3, 2));
+
+        let theme = Theme {
+            accent: Some(Rgb(9, 9, 9)),
+            ..Theme::default()
+        };
+        let lines = logo.render(&theme);
+        assert_eq!(
+            lines[0],
+            format!(
+                "{}{}",
+                color::paint("#", Rgb(1, 2, 3)),
+                color::paint("##", Rgb(9, 9, 9))
+            )
+        );
+        assert_eq!(lines[1], format!("{}  ", color::paint("#", Rgb(1, 2, 3))));
+    }
+
+    #[test]
+    fn test_logo_config_resolves_file_relative_to_base_dir() {
+        let dir = unique_temp_dir("resolve");
+        fs::write(dir.join("mine.txt"), "$1/\\\n\\/\n").unwrap();
+        let config: LogoConfig =
+            toml::from_str("name = \"small\"\nfile = \"mine.txt\"\ncolors = [\"#ff0000\"]")
+                .unwrap();
+        let logo = config.resolve(Some(&dir)).unwrap();
+        assert_eq!(logo.lines, vec!["$1/\\", "\\/"]);
+        assert_eq!(logo.colors, vec![Rgb(255, 0, 0)]);
+
+        let loaded = Logo::load(dir.join("mine.txt").to_str().unwrap()).unwrap();
+        assert_eq!(loaded.width(), 2);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_logo_config_rejects_bad_values() {
+        let bad = |toml: &str| {
+            toml::from_str::<LogoConfig>(toml)
+                .map_err(anyhow::Error::from)
+                .and_then(|c| c.resolve(None))
+                .is_err()
+        };
+        assert!(bad("name = \"arch\""));
+        assert!(bad("file = \"/nonexistent/steamfetch-logo.txt\""));
+        assert!(bad("colors = [\"red\"]"));
+        assert!(bad(&format!(
+            "colors = [{}]",
+            ["\"#000000\""; 10].join(",")
+        )));
+        assert!(bad("size = 3"));
+        assert_eq!(
+            toml::from_str::<LogoConfig>("")
+                .unwrap()
+                .resolve(None)
+                .unwrap(),
+            Logo::default()
+        );
+    }
+}
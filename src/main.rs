@@ -1,24 +1,31 @@
-mod cache;
-mod config;
-mod display;
-mod image_display;
-mod steam;
 #[cfg(test)]
 mod test_support;
 
-use anyhow::Result;
-use clap::{Parser, ValueEnum};
-use std::path::PathBuf;
-
-use config::Config;
-use steam::{NativeSteamClient, SteamClient};
-
-#[derive(Debug, Clone, Copy, ValueEnum)]
-pub enum ImageProtocol {
-    Auto,
-    Kitty,
-    Iterm,
-    Sixel,
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
+
+use steamfetch::card::{self, CardSize};
+use steamfetch::color::{self, ColorChoice};
+use steamfetch::config::{self, Config, DisplayConfig, Endpoints};
+use steamfetch::games::{GameEntry, GamesQuery, PlayedFilter, SortKey};
+use steamfetch::history::{self, GroupBy, HistoryStore, TimeArg};
+use steamfetch::image_display::{self, ImageProtocol};
+use steamfetch::logo::Logo;
+#[cfg(feature = "native")]
+use steamfetch::steam::NativeSteamClient;
+use steamfetch::steam::{self, Recording, SteamClient};
+use steamfetch::template::Template;
+use steamfetch::theme::Theme;
+use steamfetch::{achievements, demo_stats, display, games, html, json, markdown};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Ansi,
+    Json,
+    #[value(alias = "md")]
+    Markdown,
+    Html,
 }
 
 #[derive(Parser)]
@@ -26,16 +33,19 @@ pub enum ImageProtocol {
 #[command(about = "neofetch for Steam - Display your Steam stats in terminal")]
 #[command(version)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Show demo output with sample data
     #[arg(long)]
     demo: bool,
 
     /// Show verbose output for debugging
-    #[arg(long, short)]
+    #[arg(long, global = true, short)]
     verbose: bool,
 
     /// Path to config file
-    #[arg(long, value_name = "PATH")]
+    #[arg(long, global = true, value_name = "PATH")]
     config: Option<PathBuf>,
 
     /// Show config file path and exit
@@ -43,9 +53,33 @@ struct Cli {
     config_path: bool,
 
     /// Request timeout in seconds (default: 30)
-    #[arg(long, value_name = "SECONDS", default_value = "30", value_parser = clap::value_parser!(u64).range(1..))]
+    #[arg(long, global = true, value_name = "SECONDS", default_value = "30", value_parser = clap::value_parser!(u64).range(1..))]
     timeout: u64,
 
+    /// Maximum number of concurrent achievement requests (default: 8)
+    #[arg(long, global = true, value_name = "N", default_value = "8", value_parser = clap::value_parser!(u64).range(1..=64))]
+    concurrency: u64,
+
+    /// Steam Web API base URL, e.g. a caching proxy or mock server
+    #[arg(long, global = true, value_name = "URL", value_parser = parse_url_arg)]
+    api_base_url: Option<String>,
+
+    /// URL of the games.xml list used for Native SDK ownership checks
+    #[arg(long, value_name = "URL", value_parser = parse_url_arg)]
+    games_list_url: Option<String>,
+
+    /// Save every Steam API response to DIR (the API key is never written)
+    #[arg(long, global = true, value_name = "DIR", conflicts_with = "replay")]
+    record: Option<PathBuf>,
+
+    /// Serve Steam API responses from a --record directory instead of the network
+    #[arg(long, global = true, value_name = "DIR")]
+    replay: Option<PathBuf>,
+
+    /// Mask the Steam ID (not just the API key) in verbose output, errors and recordings
+    #[arg(long, global = true)]
+    redact_steam_id: bool,
+
     /// Show profile avatar as image instead of ASCII logo
     #[arg(long)]
     image: bool,
@@ -53,11 +87,132 @@ struct Cli {
     /// Image display protocol (auto, kitty, iterm, sixel)
     #[arg(long, value_enum, default_value = "auto")]
     image_protocol: ImageProtocol,
+
+    /// List the N unfinished games closest to 100% achievements (0 hides the section)
+    #[arg(long, value_name = "N")]
+    near_complete: Option<usize>,
+
+    /// Color theme: default, monochrome, steam-classic, deck, or a theme file
+    #[arg(long, global = true, value_name = "NAME|PATH")]
+    theme: Option<String>,
+
+    /// ASCII logo: steam, small, deck, steamos, none, or a logo text file
+    #[arg(long, value_name = "NAME|PATH")]
+    logo: Option<String>,
+
+    /// Print a template filled with the stats instead, e.g. '{games} games, {playtime_hours}h'
+    #[arg(long, value_name = "FILE|TEXT", conflicts_with = "format")]
+    template: Option<String>,
+
+    /// Output format (ansi, json; markdown and html for the stats card only)
+    #[arg(long, global = true, value_enum, default_value = "ansi")]
+    format: OutputFormat,
+
+    /// Use colors: auto (detect from the terminal), always or never
+    #[arg(long, global = true, value_enum, default_value = "auto")]
+    color: ColorChoice,
+}
+
+impl Cli {
+    /// CLI flags take precedence over the `[display]` config section.
+    fn display_overrides(&self, mut display: DisplayConfig) -> Result<DisplayConfig> {
+        if let Some(n) = self.near_complete {
+            display.show_near_complete = n;
+        }
+        if let Some(theme) = &self.theme {
+            display.theme = Theme::load(theme)?;
+        }
+        if let Some(logo) = &self.logo {
+            display.logo = Logo::load(logo)?;
+        }
+        Ok(display)
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List every owned game as a table, with sorting and filtering
+    Games(GamesArgs),
+    /// Show every achievement of one game, rarest first
+    Achievements(AchievementsArgs),
+    /// Report playtime, achievements and new games between saved runs
+    History(HistoryArgs),
+    /// Write the stats as an SVG card, e.g. for a GitHub profile README
+    Card(CardArgs),
+}
+
+#[derive(Args)]
+struct GamesArgs {
+    /// Sort order
+    #[arg(long, value_enum, default_value = "playtime")]
+    sort: SortKey,
+
+    /// Only show unplayed or played games
+    #[arg(long, value_enum)]
+    filter: Option<PlayedFilter>,
+
+    /// Only show games with at least this many hours played
+    #[arg(long, value_name = "HOURS")]
+    min_hours: Option<u32>,
+
+    /// Only show games whose name contains TEXT (case-insensitive)
+    #[arg(long, value_name = "TEXT")]
+    search: Option<String>,
+
+    /// Show at most N games
+    #[arg(long, value_name = "N")]
+    limit: Option<usize>,
+}
+
+#[derive(Args)]
+struct AchievementsArgs {
+    /// App ID, or a (partial) name of an owned game
+    #[arg(value_name = "APPID|NAME")]
+    game: String,
+}
+
+#[derive(Args)]
+struct HistoryArgs {
+    /// Start of the range: a date (YYYY-MM-DD) or an age like 7d or 4w
+    #[arg(long, value_name = "WHEN")]
+    since: Option<TimeArg>,
+
+    /// End of the range, inclusive; same format as --since
+    #[arg(long, value_name = "WHEN")]
+    until: Option<TimeArg>,
+
+    /// Split the range into periods
+    #[arg(long, value_enum)]
+    group_by: Option<GroupBy>,
+}
+
+#[derive(Args)]
+struct CardArgs {
+    /// Write the SVG to PATH instead of stdout
+    #[arg(long, short, value_name = "PATH")]
+    out: Option<PathBuf>,
+
+    /// Card size
+    #[arg(long, value_enum, default_value = "medium")]
+    size: CardSize,
+}
+
+impl GamesArgs {
+    fn query(&self) -> GamesQuery {
+        GamesQuery {
+            sort: self.sort,
+            filter: self.filter,
+            min_hours: self.min_hours,
+            search: self.search.clone(),
+            limit: self.limit,
+        }
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    color::init(cli.color);
 
     if cli.config_path {
         match config::config_path() {
@@ -67,46 +222,282 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    if let Some(command) = &cli.command {
+        let is_card = matches!(command, Command::Card(_));
+        if cli.demo && !is_card {
+            anyhow::bail!("--demo cannot be combined with a subcommand other than card");
+        }
+        if cli.template.is_some() {
+            anyhow::bail!("--template cannot be combined with a subcommand");
+        }
+        if is_card && cli.format != OutputFormat::Ansi {
+            anyhow::bail!("card always writes SVG and takes no --format");
+        }
+        if matches!(cli.format, OutputFormat::Markdown | OutputFormat::Html) {
+            anyhow::bail!("--format markdown/html is only supported for the summary");
+        }
+        return match command {
+            Command::Games(args) => run_games(&cli, args).await,
+            Command::Achievements(args) => run_achievements(&cli, args).await,
+            Command::History(args) => run_history(&cli, args),
+            Command::Card(args) => run_card(&cli, args).await,
+        };
+    }
+
     let image_config = display::ImageConfig {
         enabled: cli.image,
         protocol: cli.image_protocol,
     };
 
-    let stats = if cli.demo {
-        demo_stats()
-    } else {
-        fetch_stats(&cli).await?
+    // Template mistakes are reported before any request is made; the demo
+    // stats fill every optional field, so unknown fields show up too.
+    let template = cli.template.as_deref().map(Template::load).transpose()?;
+    if let Some(template) = &template {
+        template.render(&demo_stats())?;
+    }
+
+    let (stats, display_config) = load_stats(&cli).await?;
+
+    if let Some(template) = template {
+        let text = template.render(&stats)?;
+        // Command substitution drops the newline; a terminal needs it.
+        if text.ends_with('\n') {
+            print!("{}", text);
+        } else {
+            println!("{}", text);
+        }
+        return Ok(());
+    }
+
+    match cli.format {
+        OutputFormat::Ansi => display::render(&stats, &display_config, &image_config).await,
+        OutputFormat::Json => println!("{}", json::render(&stats)?),
+        OutputFormat::Markdown => print!("{}", markdown::render(&stats, &display_config)),
+        OutputFormat::Html => print!("{}", html::render(&stats, &display_config)),
+    }
+    Ok(())
+}
+
+async fn load_stats(cli: &Cli) -> Result<(steam::SteamStats, DisplayConfig)> {
+    if cli.demo {
+        // Demo mode needs no config, but an explicit one previews its layout.
+        let display_config = match &cli.config {
+            Some(path) => Config::load_display(Some(path.clone()))?,
+            None => DisplayConfig::default(),
+        };
+        return Ok((demo_stats(), cli.display_overrides(display_config)?));
+    }
+    let display_config = cli.display_overrides(Config::load_display(cli.config.clone())?)?;
+    Ok((fetch_stats(cli, &display_config).await?, display_config))
+}
+
+/// Fetches like the default command, so `--demo` and `--replay` work too,
+/// and colors the card with the same theme as the terminal.
+async fn run_card(cli: &Cli, args: &CardArgs) -> Result<()> {
+    let (stats, display_config) = load_stats(cli).await?;
+    let avatar = image_display::load_avatar(&stats).await;
+    let svg = card::render(&stats, &display_config.theme, args.size, avatar.as_ref());
+    match &args.out {
+        Some(path) => std::fs::write(path, svg)
+            .with_context(|| format!("Failed to write card: {}", path.display())),
+        None => {
+            print!("{}", svg);
+            Ok(())
+        }
+    }
+}
+
+async fn run_games(cli: &Cli, args: &GamesArgs) -> Result<()> {
+    let client = subcommand_client(cli)?;
+    let owned = client.fetch_owned_games().await?;
+    let games = args
+        .query()
+        .apply(owned.games.into_iter().map(GameEntry::from).collect());
+
+    match cli.format {
+        OutputFormat::Ansi => print!("{}", games::render_table(&games, owned.game_count)),
+        OutputFormat::Json => println!("{}", json::render_games(&games, owned.game_count)?),
+        OutputFormat::Markdown | OutputFormat::Html => {
+            anyhow::bail!("--format markdown/html is only supported for the summary")
+        }
+    }
+    Ok(())
+}
+
+/// A numeric argument is taken as the app ID; anything else is looked up
+/// among the owned games by name.
+async fn run_achievements(cli: &Cli, args: &AchievementsArgs) -> Result<()> {
+    let client = subcommand_client(cli)?;
+    let details = match args.game.trim().parse::<u32>() {
+        Ok(appid) => client.fetch_achievement_details(appid).await?,
+        Err(_) => {
+            let owned = client.fetch_owned_games().await?;
+            let (appid, name) = achievements::find_game(&owned.games, args.game.trim())?;
+            let mut details = client.fetch_achievement_details(appid).await?;
+            details.game = name;
+            details
+        }
     };
 
-    display::render(&stats, &image_config).await;
+    match cli.format {
+        OutputFormat::Ansi => print!("{}", achievements::render(&details)),
+        OutputFormat::Json => println!("{}", json::render_achievements(&details)?),
+        OutputFormat::Markdown | OutputFormat::Html => {
+            anyhow::bail!("--format markdown/html is only supported for the summary")
+        }
+    }
+    Ok(())
+}
+
+/// Works offline from the snapshots saved by previous runs.
+fn run_history(cli: &Cli, args: &HistoryArgs) -> Result<()> {
+    let now = history::now();
+    let since = args.since.map(|t| t.resolve(now, false));
+    let until = args.until.map(|t| t.resolve(now, true));
+    if let (Some(since), Some(until)) = (since, until) {
+        if since > until {
+            anyhow::bail!("--since must not be later than --until");
+        }
+    }
+    let path = HistoryStore::default_path().context("Could not determine data directory")?;
+    let report = history::report(
+        &HistoryStore::new(path).load()?,
+        since,
+        until,
+        args.group_by,
+    );
+
+    match cli.format {
+        OutputFormat::Ansi => print!("{}", history::render_report(&report)),
+        OutputFormat::Json => println!("{}", json::render_history(&report)?),
+        OutputFormat::Markdown | OutputFormat::Html => {
+            anyhow::bail!("--format markdown/html is only supported for the summary")
+        }
+    }
     Ok(())
 }
 
-async fn fetch_stats(cli: &Cli) -> Result<steam::SteamStats> {
-    match NativeSteamClient::try_new(cli.verbose) {
-        Some(native) => fetch_native_stats(native, cli).await,
-        None => fetch_web_stats(cli).await,
+fn subcommand_client(cli: &Cli) -> Result<SteamClient> {
+    match &cli.replay {
+        Some(dir) => replay_client(dir, cli),
+        None => web_client(cli),
+    }
+}
+
+fn parse_url_arg(value: &str) -> Result<String, String> {
+    config::parse_url(value).map_err(|e| e.to_string())
+}
+
+/// CLI flags take precedence over env vars and the config file.
+fn load_endpoints(cli: &Cli) -> Result<Endpoints> {
+    let mut endpoints = Config::load_endpoints(cli.config.clone())?;
+    if let Some(url) = &cli.api_base_url {
+        endpoints.api_base_url = url.clone();
+    }
+    if let Some(url) = &cli.games_list_url {
+        endpoints.games_list_url = url.clone();
+    }
+    Ok(endpoints)
+}
+
+async fn fetch_stats(cli: &Cli, display: &DisplayConfig) -> Result<steam::SteamStats> {
+    if let Some(dir) = &cli.replay {
+        return fetch_replay_stats(dir, cli, display).await;
+    }
+    let mut stats = fetch_account_stats(cli, display).await?;
+    if display.show_installed {
+        stats.installed = steam::library::scan_installed();
+    }
+    // Replays are old data, so only live runs are recorded.
+    if display.show_history {
+        stats.history = record_history(&stats, cli.verbose);
+    }
+    Ok(stats)
+}
+
+/// History is best effort: a failure never prevents the stats from showing.
+fn record_history(stats: &steam::SteamStats, verbose: bool) -> Option<steam::HistoryDelta> {
+    let store = HistoryStore::new(HistoryStore::default_path()?);
+    store
+        .record(stats, history::now())
+        .map_err(|e| {
+            if verbose {
+                eprintln!("[verbose] History not updated: {:#}", e);
+            }
+        })
+        .ok()
+        .flatten()
+}
+
+async fn fetch_account_stats(cli: &Cli, display: &DisplayConfig) -> Result<steam::SteamStats> {
+    // Replays go through the Web API path, so recordings must too.
+    #[cfg(feature = "native")]
+    if cli.record.is_none() {
+        if let Some(native) = NativeSteamClient::try_new(cli.verbose) {
+            return fetch_native_stats(native, cli, display).await;
+        }
     }
+    fetch_web_stats(cli, display).await
+}
+
+async fn fetch_web_stats(cli: &Cli, display: &DisplayConfig) -> Result<steam::SteamStats> {
+    let client = web_client(cli)?.with_options(display.fetch_options());
+    client.fetch_stats().await
+}
+
+/// Replays a recording offline; credentials come from the recording itself.
+async fn fetch_replay_stats(
+    dir: &Path,
+    cli: &Cli,
+    display: &DisplayConfig,
+) -> Result<steam::SteamStats> {
+    let client = replay_client(dir, cli)?.with_options(display.fetch_options());
+    client.fetch_stats().await
 }
 
-async fn fetch_web_stats(cli: &Cli) -> Result<steam::SteamStats> {
+fn web_client(cli: &Cli) -> Result<SteamClient> {
     let config = Config::load(cli.config.clone())?;
-    let client = SteamClient::new(config.api_key, config.steam_id)
+    let endpoints = load_endpoints(cli)?;
+    Ok(SteamClient::new(config.api_key, config.steam_id)
+        .with_base_url(endpoints.api_base_url)
+        .with_recording(cli.record.clone().map(Recording::Record))
+        .with_redacted_steam_id(cli.redact_steam_id)
         .with_verbose(cli.verbose)
-        .with_timeout(cli.timeout);
-    client.fetch_stats().await
+        .with_timeout(cli.timeout)
+        .with_concurrency(cli.concurrency as usize))
 }
 
-async fn fetch_native_stats(native: NativeSteamClient, cli: &Cli) -> Result<steam::SteamStats> {
+fn replay_client(dir: &Path, cli: &Cli) -> Result<SteamClient> {
+    let steam_id = steam::recording::recorded_steam_id(dir)
+        .with_context(|| format!("No single recorded player found in {}", dir.display()))?;
+    Ok(SteamClient::new(String::new(), steam_id)
+        .with_recording(Some(Recording::Replay(dir.to_path_buf())))
+        .with_redacted_steam_id(cli.redact_steam_id)
+        .with_verbose(cli.verbose)
+        .with_concurrency(cli.concurrency as usize))
+}
+
+#[cfg(feature = "native")]
+async fn fetch_native_stats(
+    native: NativeSteamClient,
+    cli: &Cli,
+    display: &DisplayConfig,
+) -> Result<steam::SteamStats> {
     let username = native.username();
     let steam_id = native.steam_id().to_string();
 
     if cli.verbose {
         eprintln!("[verbose] Native SDK username: {}", username);
-        eprintln!("[verbose] Native SDK steam_id: {}", steam_id);
+        let shown_id = if cli.redact_steam_id {
+            steam::redact::MASK
+        } else {
+            steam_id.as_str()
+        };
+        eprintln!("[verbose] Native SDK steam_id: {}", shown_id);
     }
 
-    let all_appids = steam::native::fetch_all_game_appids().await?;
+    let endpoints = load_endpoints(cli)?;
+    let all_appids = steam::games_list::fetch_all_game_appids(&endpoints.games_list_url).await?;
     let owned_appids = native.get_owned_appids(&all_appids);
 
     if cli.verbose {
@@ -118,167 +509,51 @@ async fn fetch_native_stats(native: NativeSteamClient, cli: &Cli) -> Result<stea
 
     let api_key = Config::load_api_key_only(cli.config.clone())?;
     let client = SteamClient::new(api_key, steam_id)
+        .with_base_url(endpoints.api_base_url)
+        .with_recording(cli.record.clone().map(Recording::Record))
+        .with_redacted_steam_id(cli.redact_steam_id)
         .with_verbose(cli.verbose)
-        .with_timeout(cli.timeout);
+        .with_timeout(cli.timeout)
+        .with_concurrency(cli.concurrency as usize)
+        .with_options(display.fetch_options());
     client
         .fetch_stats_for_appids(&owned_appids, &username)
         .await
 }
 
-pub(crate) fn demo_stats() -> steam::SteamStats {
-    use steam::SteamStats;
-
-    SteamStats {
-        username: "unhappychoice".to_string(),
-        game_count: 486,
-        unplayed_count: 123,
-        total_playtime_minutes: 170820,
-        top_games: vec![
-            steam::GameStat {
-                name: "Borderlands 3".to_string(),
-                playtime_minutes: 28680,
-            },
-            steam::GameStat {
-                name: "Coin Push RPG".to_string(),
-                playtime_minutes: 22620,
-            },
-            steam::GameStat {
-                name: "DRG Survivor".to_string(),
-                playtime_minutes: 15120,
-            },
-        ],
-        achievement_stats: Some(steam::AchievementStats {
-            total_achieved: 3241,
-            total_possible: 5892,
-            perfect_games: 24,
-            rarest: Some(steam::RarestAchievement {
-                name: "Impossible Task".to_string(),
-                game: "Dark Souls III".to_string(),
-                percent: 0.1,
-            }),
-        }),
-        account_created: Some(1234567890),
-        steam_level: Some(42),
-        recently_played: vec![
-            steam::GameStat {
-                name: "Elden Ring".to_string(),
-                playtime_minutes: 1200,
-            },
-            steam::GameStat {
-                name: "Hades II".to_string(),
-                playtime_minutes: 480,
-            },
-        ],
-        avatar_url: None,
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use clap::Parser;
 
-    #[test]
-    fn test_demo_stats_returns_expected_username() {
-        let stats = demo_stats();
-        assert_eq!(stats.username, "unhappychoice");
-    }
-
-    #[test]
-    fn test_demo_stats_top_games_in_descending_playtime() {
-        let stats = demo_stats();
-        assert!(stats.top_games.len() >= 2);
-        for window in stats.top_games.windows(2) {
-            assert!(window[0].playtime_minutes >= window[1].playtime_minutes);
-        }
-    }
-
-    #[test]
-    fn test_demo_stats_unplayed_does_not_exceed_total() {
-        let stats = demo_stats();
-        assert!(stats.unplayed_count <= stats.game_count);
-    }
-
-    #[test]
-    fn test_demo_stats_achievements_consistent() {
-        let stats = demo_stats();
-        let ach = stats.achievement_stats.expect("demo has achievements");
-        assert!(ach.total_achieved <= ach.total_possible);
-        assert!(ach.perfect_games <= stats.game_count);
-        let rarest = ach.rarest.expect("demo has rarest achievement");
-        assert!(rarest.percent >= 0.0 && rarest.percent <= 100.0);
-        assert!(!rarest.name.is_empty());
-        assert!(!rarest.game.is_empty());
-    }
-
-    #[test]
-    fn test_demo_stats_recently_played_has_entries() {
-        let stats = demo_stats();
-        assert!(!stats.recently_played.is_empty());
-        for game in &stats.recently_played {
-            assert!(!game.name.is_empty());
-        }
-    }
-
-    #[test]
-    fn test_demo_stats_avatar_url_is_none() {
-        assert!(demo_stats().avatar_url.is_none());
-    }
-
-    #[test]
-    fn test_demo_stats_profile_details_are_present() {
-        let stats = demo_stats();
-        let achievements = stats.achievement_stats.as_ref().expect("demo achievements");
-        let rarest = achievements.rarest.as_ref().expect("demo rarest");
-
-        assert_eq!(stats.account_created, Some(1234567890));
-        assert_eq!(stats.steam_level, Some(42));
-        assert_eq!(rarest.name, "Impossible Task");
-        assert_eq!(rarest.game, "Dark Souls III");
-        assert_eq!(stats.recently_played[0].playtime_minutes, 1200);
-    }
-
-    #[test]
-    fn test_demo_stats_fixture_values_are_stable() {
-        let stats = demo_stats();
-        let top_games = stats
-            .top_games
-            .iter()
-            .map(|game| (game.name.as_str(), game.playtime_minutes))
-            .collect::<Vec<_>>();
-        let recently_played = stats
-            .recently_played
-            .iter()
-            .map(|game| (game.name.as_str(), game.playtime_minutes))
-            .collect::<Vec<_>>();
-
-        assert_eq!(stats.game_count, 486);
-        assert_eq!(stats.unplayed_count, 123);
-        assert_eq!(stats.total_playtime_minutes, 170820);
-        assert_eq!(
-            top_games,
-            vec![
-                ("Borderlands 3", 28680),
-                ("Coin Push RPG", 22620),
-                ("DRG Survivor", 15120),
-            ],
-        );
-        assert_eq!(
-            recently_played,
-            vec![("Elden Ring", 1200), ("Hades II", 480)]
-        );
-    }
-
     #[test]
     fn test_cli_parses_minimum_args() {
         let cli = Cli::try_parse_from(["steamfetch"]).expect("default args should parse");
         assert!(!cli.demo);
         assert!(!cli.verbose);
         assert!(!cli.config_path);
-        assert!(!cli.image);
         assert_eq!(cli.timeout, 30);
+        assert_eq!(cli.concurrency, 8);
+        assert!(cli.api_base_url.is_none());
+        assert!(cli.games_list_url.is_none());
+        assert!(cli.record.is_none());
+        assert!(cli.replay.is_none());
+        assert!(!cli.redact_steam_id);
         assert!(cli.config.is_none());
         assert!(matches!(cli.image_protocol, ImageProtocol::Auto));
+        assert_eq!(cli.format, OutputFormat::Ansi);
+    }
+
+    #[test]
+    fn test_cli_parses_json_format() {
+        let cli = Cli::try_parse_from(["steamfetch", "--format", "json"])
+            .expect("--format json should parse");
+        assert_eq!(cli.format, OutputFormat::Json);
+    }
+
+    #[test]
+    fn test_cli_rejects_unknown_format() {
+        assert!(Cli::try_parse_from(["steamfetch", "--format", "yaml"]).is_err());
     }
 
     #[test]
@@ -357,6 +632,49 @@ mod tests {
         assert_eq!(cli.timeout, 5);
     }
 
+    #[test]
+    fn test_cli_parses_custom_concurrency() {
+        let cli = Cli::try_parse_from(["steamfetch", "--concurrency", "16"])
+            .expect("concurrency should parse");
+        assert_eq!(cli.concurrency, 16);
+    }
+
+    #[test]
+    fn test_cli_rejects_out_of_range_concurrency() {
+        assert!(Cli::try_parse_from(["steamfetch", "--concurrency", "0"]).is_err());
+        assert!(Cli::try_parse_from(["steamfetch", "--concurrency", "65"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_endpoint_urls() {
+        let cli = Cli::try_parse_from([
+            "steamfetch",
+            "--api-base-url",
+            "http://127.0.0.1:8080/",
+            "--games-list-url",
+            "http://127.0.0.1:8080/games.xml",
+        ])
+        .expect("endpoint URLs should parse");
+        assert_eq!(cli.api_base_url.as_deref(), Some("http://127.0.0.1:8080"));
+        assert_eq!(
+            cli.games_list_url.as_deref(),
+            Some("http://127.0.0.1:8080/games.xml")
+        );
+    }
+
+    #[test]
+    fn test_cli_rejects_invalid_api_base_url() {
+        assert!(Cli::try_parse_from(["steamfetch", "--api-base-url", "not a url"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_rejects_record_with_replay() {
+        assert!(Cli::try_parse_from(["steamfetch", "--record", "a", "--replay", "b"]).is_err());
+        let cli = Cli::try_parse_from(["steamfetch", "--replay", "fixtures"])
+            .expect("--replay should parse");
+        assert_eq!(cli.replay, Some(PathBuf::from("fixtures")));
+    }
+
     #[test]
     fn test_cli_rejects_zero_timeout() {
         // Range is 1.. — zero must be rejected by clap's value_parser.
@@ -422,6 +740,177 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cli_near_complete_overrides_display_config() {
+        let cli = Cli::try_parse_from(["steamfetch", "--near-complete", "10"]).unwrap();
+        assert_eq!(cli.near_complete, Some(10));
+        let display = cli.display_overrides(DisplayConfig::default()).unwrap();
+        assert_eq!(display.show_near_complete, 10);
+        assert_eq!(display.fetch_options().near_complete, 10);
+
+        let cli = Cli::try_parse_from(["steamfetch"]).unwrap();
+        let display = cli
+            .display_overrides(DisplayConfig {
+                show_near_complete: 7,
+                ..DisplayConfig::default()
+            })
+            .unwrap();
+        assert_eq!(display.show_near_complete, 7);
+        assert!(Cli::try_parse_from(["steamfetch", "--near-complete", "-1"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_theme_overrides_config_theme() {
+        let cli = Cli::try_parse_from(["steamfetch", "--theme", "monochrome"]).unwrap();
+        let display = cli.display_overrides(DisplayConfig::default()).unwrap();
+        assert_eq!(Some(display.theme), Theme::builtin("monochrome"));
+
+        let cli = Cli::try_parse_from(["steamfetch", "--theme", "neon"]).unwrap();
+        let err = cli
+            .display_overrides(DisplayConfig::default())
+            .expect_err("unknown theme");
+        assert!(err.to_string().contains("steam-classic"));
+    }
+
+    #[test]
+    fn test_cli_template_conflicts_with_format() {
+        let cli = Cli::try_parse_from(["steamfetch", "--template", "{games}"]).unwrap();
+        assert_eq!(cli.template.as_deref(), Some("{games}"));
+        assert!(
+            Cli::try_parse_from(["steamfetch", "--template", "{games}", "--format", "json"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_logo_overrides_config_logo() {
+        let cli = Cli::try_parse_from(["steamfetch", "--logo", "small"]).unwrap();
+        let display = cli.display_overrides(DisplayConfig::default()).unwrap();
+        assert_eq!(Some(display.logo), Logo::builtin("small"));
+
+        let cli = Cli::try_parse_from(["steamfetch", "--logo", "arch"]).unwrap();
+        let err = cli
+            .display_overrides(DisplayConfig::default())
+            .expect_err("unknown logo");
+        assert!(err.to_string().contains("steamos"));
+    }
+
+    #[test]
+    fn test_cli_color_flag_is_global() {
+        let cli = Cli::try_parse_from(["steamfetch"]).unwrap();
+        assert_eq!(cli.color, ColorChoice::Auto);
+        let cli = Cli::try_parse_from(["steamfetch", "games", "--color", "never"]).unwrap();
+        assert_eq!(cli.color, ColorChoice::Never);
+        assert!(Cli::try_parse_from(["steamfetch", "--color", "sometimes"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_games_subcommand_with_global_flags() {
+        let cli = Cli::try_parse_from([
+            "steamfetch",
+            "--verbose",
+            "games",
+            "--sort",
+            "last-played",
+            "--filter",
+            "unplayed",
+            "--min-hours",
+            "2",
+            "--search",
+            "portal",
+            "--limit",
+            "10",
+            "--format",
+            "json",
+        ])
+        .expect("games subcommand should parse");
+
+        assert!(cli.verbose);
+        assert_eq!(cli.format, OutputFormat::Json);
+        let Some(Command::Games(args)) = &cli.command else {
+            panic!("expected games subcommand");
+        };
+        let query = args.query();
+        assert_eq!(query.sort, SortKey::LastPlayed);
+        assert_eq!(query.filter, Some(PlayedFilter::Unplayed));
+        assert_eq!(query.min_hours, Some(2));
+        assert_eq!(query.search.as_deref(), Some("portal"));
+        assert_eq!(query.limit, Some(10));
+    }
+
+    #[test]
+    fn test_cli_games_defaults_and_invalid_sort() {
+        let cli = Cli::try_parse_from(["steamfetch", "games"]).expect("bare games should parse");
+        let Some(Command::Games(args)) = &cli.command else {
+            panic!("expected games subcommand");
+        };
+        assert_eq!(args.query().sort, SortKey::Playtime);
+        assert!(args.query().filter.is_none());
+
+        assert!(Cli::try_parse_from(["steamfetch", "games", "--sort", "size"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_achievements_subcommand() {
+        let cli =
+            Cli::try_parse_from(["steamfetch", "achievements", "Portal 2", "--format", "json"])
+                .expect("achievements subcommand should parse");
+        assert_eq!(cli.format, OutputFormat::Json);
+        let Some(Command::Achievements(args)) = &cli.command else {
+            panic!("expected achievements subcommand");
+        };
+        assert_eq!(args.game, "Portal 2");
+
+        assert!(Cli::try_parse_from(["steamfetch", "achievements"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_history_subcommand() {
+        let cli = Cli::try_parse_from([
+            "steamfetch",
+            "history",
+            "--since",
+            "4w",
+            "--until",
+            "2024-01-31",
+            "--group-by",
+            "week",
+        ])
+        .expect("history subcommand should parse");
+        let Some(Command::History(args)) = &cli.command else {
+            panic!("expected history subcommand");
+        };
+        assert_eq!(args.since, Some(TimeArg::Ago(28 * 86_400)));
+        assert_eq!(args.until, Some(TimeArg::Date(1_706_659_200)));
+        assert_eq!(args.group_by, Some(GroupBy::Week));
+
+        assert!(Cli::try_parse_from(["steamfetch", "history", "--since", "soon"]).is_err());
+        assert!(Cli::try_parse_from(["steamfetch", "history", "--group-by", "year"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_card_subcommand() {
+        let cli = Cli::try_parse_from(["steamfetch", "--demo", "card", "-o", "card.svg"])
+            .expect("card subcommand should parse");
+        assert!(cli.demo);
+        let Some(Command::Card(args)) = &cli.command else {
+            panic!("expected card subcommand");
+        };
+        assert_eq!(args.out.as_deref(), Some(Path::new("card.svg")));
+        assert_eq!(args.size, CardSize::Medium);
+        assert_eq!(cli.theme, None);
+
+        let cli = Cli::try_parse_from(["steamfetch", "card", "--theme", "deck", "--size", "large"])
+            .expect("card options should parse");
+        let Some(Command::Card(args)) = &cli.command else {
+            panic!("expected card subcommand");
+        };
+        assert!(args.out.is_none());
+        assert_eq!(args.size, CardSize::Large);
+        assert_eq!(cli.theme.as_deref(), Some("deck"));
+        assert!(Cli::try_parse_from(["steamfetch", "card", "--size", "huge"]).is_err());
+    }
+
     #[cfg(target_os = "linux")]
     #[test]
     fn test_fetch_stats_routes_to_web_stats_when_native_unavailable() {
@@ -429,8 +918,8 @@ mod tests {
         // Steam install at ~/.steam/sdk64/steamclient.so, so calling
         // `fetch_stats` without scoping $HOME could load real steamclient.so
         // and reach Steam SDK code. Point $HOME at an empty temp directory:
-        // `NativeSteamClient::try_new` then matches the
-        // `None => fetch_web_stats(cli).await` arm of `fetch_stats`. Pair it
+        // `NativeSteamClient::try_new` then returns None and `fetch_stats`
+        // falls through to `fetch_web_stats`. Pair it
         // with a malformed config path so `fetch_web_stats` propagates a
         // `Config::load` error before any HTTP request — proving the routing
         // hit the web-stats path.
@@ -467,15 +956,28 @@ mod tests {
             config: Some(cfg_path.clone()),
             config_path: false,
             timeout: 30,
+            concurrency: 8,
+            api_base_url: None,
+            games_list_url: None,
+            record: None,
+            replay: None,
+            command: None,
+            redact_steam_id: false,
             image: false,
             image_protocol: ImageProtocol::Auto,
+            near_complete: None,
+            theme: None,
+            logo: None,
+            template: None,
+            format: OutputFormat::Ansi,
+            color: ColorChoice::Auto,
         };
 
         let err = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
             .expect("rt")
-            .block_on(fetch_stats(&cli))
+            .block_on(fetch_stats(&cli, &DisplayConfig::default()))
             .expect_err("malformed config should propagate from fetch_web_stats");
         let msg = format!("{:#}", err);
 
@@ -515,11 +1017,24 @@ mod tests {
             config: Some(path.clone()),
             config_path: false,
             timeout: 30,
+            concurrency: 8,
+            api_base_url: None,
+            games_list_url: None,
+            record: None,
+            replay: None,
+            command: None,
+            redact_steam_id: false,
             image: false,
             image_protocol: ImageProtocol::Auto,
+            near_complete: None,
+            theme: None,
+            logo: None,
+            template: None,
+            format: OutputFormat::Ansi,
+            color: ColorChoice::Auto,
         };
 
-        let err = fetch_web_stats(&cli)
+        let err = fetch_web_stats(&cli, &DisplayConfig::default())
             .await
             .expect_err("invalid TOML should make Config::load propagate an error");
         let msg = format!("{:#}", err);
@@ -616,15 +1131,28 @@ steam_id = "76561197960265728"
                 config: Some(path.clone()),
                 config_path: false,
                 timeout: 1,
+                concurrency: 8,
+                api_base_url: None,
+                games_list_url: None,
+                record: None,
+                replay: None,
+                command: None,
+                redact_steam_id: false,
                 image: false,
                 image_protocol: ImageProtocol::Auto,
+                near_complete: None,
+                theme: None,
+                logo: None,
+                template: None,
+                format: OutputFormat::Ansi,
+                color: ColorChoice::Auto,
             };
 
             let err = tokio::runtime::Builder::new_current_thread()
                 .enable_all()
                 .build()
                 .expect("rt")
-                .block_on(fetch_web_stats(&cli))
+                .block_on(fetch_web_stats(&cli, &DisplayConfig::default()))
                 .expect_err("unreachable proxy should make fetch_stats fail");
 
             let _ = std::fs::remove_file(&path);
@@ -730,15 +1258,28 @@ steam_id = "76561197960265728"
             config: Some(path.clone()),
             config_path: false,
             timeout: 1,
+            concurrency: 8,
+            api_base_url: None,
+            games_list_url: None,
+            record: None,
+            replay: None,
+            command: None,
+            redact_steam_id: false,
             image: false,
             image_protocol: ImageProtocol::Auto,
+            near_complete: None,
+            theme: None,
+            logo: None,
+            template: None,
+            format: OutputFormat::Ansi,
+            color: ColorChoice::Auto,
         };
 
         let err = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
             .expect("rt")
-            .block_on(fetch_web_stats(&cli))
+            .block_on(fetch_web_stats(&cli, &DisplayConfig::default()))
             .expect_err("unreachable proxy should make env-backed fetch fail");
 
         let _ = std::fs::remove_file(&path);
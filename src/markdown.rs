@@ -0,0 +1,168 @@
+//! `--format markdown`: the stats as a table and lists, for GitHub profile
+//! READMEs and other Markdown pages.
+
+use crate::config::DisplayConfig;
+use crate::display::{
+    format_number, format_playtime, rarest_label, rarest_shown, recently_played, stat_rows,
+    top_games,
+};
+use crate::modules::{Module, ModuleKind};
+use crate::steam::SteamStats;
+
+/// The stats table, then the list sections in `display.modules` order.
+pub fn render(stats: &SteamStats, display: &DisplayConfig) -> String {
+    let mut out = format!("## {}@Steam\n", escape(&stats.username));
+    let rows = stat_rows(stats, display);
+    if !rows.is_empty() {
+        out.push_str("\n| Stat | Value | Title |\n| --- | --- | --- |\n");
+        for row in rows {
+            out.push_str(&format!(
+                "| {} | {} | {} |\n",
+                escape(&row.label),
+                escape(&row.value),
+                row.title.unwrap_or("")
+            ));
+        }
+    }
+
+    for module in &display.modules {
+        match module.kind {
+            ModuleKind::TopPlayed => {
+                let games = top_games(stats, display);
+                if games.is_empty() {
+                    continue;
+                }
+                heading(&mut out, module, module.label());
+                for (i, game) in games.iter().enumerate() {
+                    out.push_str(&format!(
+                        "{}. {} — {}h\n",
+                        i + 1,
+                        escape(&game.name),
+                        format_number(game.playtime_hours())
+                    ));
+                }
+            }
+            ModuleKind::RecentlyPlayed => {
+                let games = recently_played(stats, display);
+                if games.is_empty() {
+                    continue;
+                }
+                heading(&mut out, module, module.label());
+                for game in games {
+                    out.push_str(&format!(
+                        "- {} — {}\n",
+                        escape(&game.name),
+                        format_playtime(game.playtime_minutes)
+                    ));
+                }
+            }
+            ModuleKind::Rarest => match rarest_shown(stats, display) {
+                [] => {}
+                [rarest] => {
+                    heading(&mut out, module, rarest_label(module, 1));
+                    out.push_str(&format!(
+                        "**{}** in {} ({:.1}% of players)\n",
+                        escape(&rarest.name),
+                        escape(&rarest.game),
+                        rarest.percent
+                    ));
+                }
+                unlocks => {
+                    heading(&mut out, module, module.label());
+                    for (i, unlock) in unlocks.iter().enumerate() {
+                        out.push_str(&format!(
+                            "{}. **{}** — {} ({:.1}%)\n",
+                            i + 1,
+                            escape(&unlock.name),
+                            escape(&unlock.game),
+                            unlock.percent
+                        ));
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Opens a section; modules with `title = false` get no heading.
+fn heading(out: &mut String, module: &Module, label: &str) {
+    out.push('\n');
+    if module.title {
+        out.push_str(&format!("### {}\n\n", escape(label)));
+    }
+}
+
+/// Backslash-escapes characters that Markdown would read as formatting,
+/// including `|` so names cannot break the table.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '#'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_demo_has_table_lists_and_rarest() {
+        let display = DisplayConfig {
+            rarest_count: 5,
+            ..DisplayConfig::default()
+        };
+        let text = render(&crate::demo_stats(), &display);
+        assert!(text.starts_with("## unhappychoice@Steam\n\n| Stat | Value | Title |\n"));
+        assert!(text.contains("| Games | 486 | Abyss Keeper |\n"));
+        assert!(text.contains("| Achievements | 3,241 (55%) | Power Unleashed |\n"));
+        assert!(text.contains("### Top Played\n\n1. Borderlands 3 — 478h\n"));
+        assert!(text.contains("- Hades II — 8h 0m\n"));
+        assert!(
+            text.contains("### Rarest Unlocks\n\n1. **Impossible Task** — Dark Souls III (0.1%)\n")
+        );
+    }
+
+    #[test]
+    fn test_render_follows_display_config() {
+        let display = DisplayConfig {
+            show_achievements: false,
+            show_recently_played: false,
+            rarest_count: 1,
+            ..DisplayConfig::default()
+        };
+        let text = render(&crate::demo_stats(), &display);
+        assert!(!text.contains("| Achievements |"));
+        assert!(!text.contains("| Perfect |"));
+        assert!(!text.contains("Recently Played"));
+        assert!(text
+            .contains("### Rarest\n\n**Impossible Task** in Dark Souls III (0.1% of players)\n"));
+
+        let display: DisplayConfig = toml::from_str(
+            r#"modules = [{ type = "games", label = "Library", title = false }, "rarest"]
+show_rarest = false"#,
+        )
+        .unwrap();
+        let text = render(&crate::demo_stats(), &display);
+        assert!(text.contains("| Library | 486 |  |\n"));
+        assert!(!text.contains("Playtime"));
+        assert!(!text.contains("Top Played"));
+        assert!(!text.contains("Rarest"));
+    }
+
+    #[test]
+    fn test_render_escapes_names() {
+        let mut stats = crate::demo_stats();
+        stats.username = "a|b_c".to_string();
+        let text = render(&stats, &DisplayConfig::default());
+        assert!(text.starts_with("## a\\|b\\_c@Steam"));
+    }
+}
@@ -0,0 +1,215 @@
+//! The stat lines and sections shown next to the logo, in the order of
+//! `[display] modules`.
+
+use serde::de::{self, IntoDeserializer, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+
+/// Names accepted in `modules`, in the default order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModuleKind {
+    /// `user@Steam`
+    Header,
+    /// The line under the header
+    Separator,
+    Member,
+    Level,
+    Games,
+    Unplayed,
+    Playtime,
+    Perfect,
+    /// Unlocked achievements and the completion meter
+    Achievements,
+    /// Changes since the last run
+    History,
+    TopPlayed,
+    PlaytimeShare,
+    PlaytimeDistribution,
+    RecentlyPlayed,
+    Installed,
+    NearComplete,
+    Rarest,
+    /// An empty line
+    Break,
+}
+
+impl ModuleKind {
+    pub const DEFAULT_LAYOUT: [ModuleKind; 17] = [
+        ModuleKind::Header,
+        ModuleKind::Separator,
+        ModuleKind::Member,
+        ModuleKind::Level,
+        ModuleKind::Games,
+        ModuleKind::Unplayed,
+        ModuleKind::Playtime,
+        ModuleKind::Perfect,
+        ModuleKind::Achievements,
+        ModuleKind::History,
+        ModuleKind::TopPlayed,
+        ModuleKind::PlaytimeShare,
+        ModuleKind::PlaytimeDistribution,
+        ModuleKind::RecentlyPlayed,
+        ModuleKind::Installed,
+        ModuleKind::NearComplete,
+        ModuleKind::Rarest,
+    ];
+
+    /// The label of a stat line, or the heading of a section.
+    pub fn default_label(self) -> &'static str {
+        match self {
+            ModuleKind::Header => "Steam",
+            ModuleKind::Separator | ModuleKind::Break => "",
+            ModuleKind::Member => "Member",
+            ModuleKind::Level => "Level",
+            ModuleKind::Games => "Games",
+            ModuleKind::Unplayed => "Unplayed",
+            ModuleKind::Playtime => "Playtime",
+            ModuleKind::Perfect => "Perfect",
+            ModuleKind::Achievements => "Achievements",
+            ModuleKind::History => "Since last run",
+            ModuleKind::TopPlayed => "Top Played",
+            ModuleKind::PlaytimeShare => "Playtime Share",
+            ModuleKind::PlaytimeDistribution => "Playtime Distribution",
+            ModuleKind::RecentlyPlayed => "Recently Played (2 weeks)",
+            ModuleKind::Installed => "Installed",
+            ModuleKind::NearComplete => "Almost Perfect",
+            ModuleKind::Rarest => "Rarest Unlocks",
+        }
+    }
+}
+
+/// One entry of `modules`: a bare name like `"games"`, or a table such as
+/// `{ type = "games", label = "Library", title = false }`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Module {
+    pub kind: ModuleKind,
+    /// Replaces the label or heading; for `header`, the text after `@`.
+    pub label: Option<String>,
+    /// Whether stat lines show their rank title and sections their heading.
+    pub title: bool,
+    /// Text after a stat label (`:` by default); for `separator`, the
+    /// repeated glyph.
+    pub separator: Option<String>,
+}
+
+impl Module {
+    pub fn new(kind: ModuleKind) -> Self {
+        Self {
+            kind,
+            label: None,
+            title: true,
+            separator: None,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        self.label
+            .as_deref()
+            .unwrap_or_else(|| self.kind.default_label())
+    }
+}
+
+pub fn default_modules() -> Vec<Module> {
+    ModuleKind::DEFAULT_LAYOUT
+        .into_iter()
+        .map(Module::new)
+        .collect()
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ModuleTable {
+    #[serde(rename = "type")]
+    kind: ModuleKind,
+    label: Option<String>,
+    #[serde(default = "default_title")]
+    title: bool,
+    separator: Option<String>,
+}
+
+fn default_title() -> bool {
+    true
+}
+
+impl<'de> Deserialize<'de> for Module {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ModuleVisitor;
+
+        impl<'de> Visitor<'de> for ModuleVisitor {
+            type Value = Module;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a module name or a table with a `type`")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<Module, E> {
+                ModuleKind::deserialize(name.into_deserializer()).map(Module::new)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Module, A::Error> {
+                let table = ModuleTable::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                Ok(Module {
+                    kind: table.kind,
+                    label: table.label,
+                    title: table.title,
+                    separator: table.separator,
+                })
+            }
+        }
+
+        deserializer.deserialize_any(ModuleVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Layout {
+        modules: Vec<Module>,
+    }
+
+    fn parse(toml: &str) -> Result<Vec<Module>, toml::de::Error> {
+        toml::from_str::<Layout>(toml).map(|l| l.modules)
+    }
+
+    #[test]
+    fn test_modules_parse_from_names_and_tables() {
+        let modules = parse(
+            r#"modules = [
+                "games",
+                { type = "top_played", label = "Most Played" },
+                { type = "playtime", title = false, separator = " =" },
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(modules[0], Module::new(ModuleKind::Games));
+        assert_eq!(modules[1].kind, ModuleKind::TopPlayed);
+        assert_eq!(modules[1].label(), "Most Played");
+        assert!(modules[1].title);
+        assert_eq!(modules[2].label(), "Playtime");
+        assert!(!modules[2].title);
+        assert_eq!(modules[2].separator.as_deref(), Some(" ="));
+    }
+
+    #[test]
+    fn test_modules_reject_unknown_names_and_keys() {
+        let err = parse(r#"modules = ["gmaes"]"#).unwrap_err().to_string();
+        assert!(err.contains("gmaes"), "{err}");
+        assert!(err.contains("recently_played"), "{err}");
+        assert!(parse(r#"modules = [{ label = "Games" }]"#).is_err());
+        assert!(parse(r#"modules = [{ type = "games", colour = "red" }]"#).is_err());
+        assert!(parse("modules = [1]").is_err());
+    }
+
+    #[test]
+    fn test_default_modules_follow_default_layout() {
+        let modules = default_modules();
+        assert_eq!(modules.len(), ModuleKind::DEFAULT_LAYOUT.len());
+        assert_eq!(modules[0].kind, ModuleKind::Header);
+        assert!(!modules.iter().any(|m| m.kind == ModuleKind::Break));
+        assert_eq!(modules[2].label(), "Member");
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use futures::stream::{self, StreamExt};
 use indicatif::{ProgressBar, ProgressStyle};
 use reqwest::Client;
 use std::io::{self, Write};
@@ -6,13 +7,19 @@ use std::time::Duration;
 
 use super::error::SteamApiError;
 use super::models::{
-    AchievementStats, AchievementsResponse, GameStat, GlobalAchievementsResponse,
-    OwnedGamesResponse, PlayerSummaryResponse, RarestAchievement, SteamStats,
+    AchievementDetail, AchievementStats, AchievementsResponse, GameAchievements, GameCompletion,
+    GameSchema, GameSchemaResponse, GameStat, GlobalAchievementsResponse, OwnedGamesResponse,
+    PlayerSummaryResponse, PlaytimeBucket, RarestAchievement, SteamStats,
 };
+use super::recording::{self, Recording};
+use super::redact::Redactor;
 use crate::cache::AchievementCache;
 
-const BASE_URL: &str = "https://api.steampowered.com";
+pub const DEFAULT_BASE_URL: &str = "https://api.steampowered.com";
 const DEFAULT_TIMEOUT_SECS: u64 = 30;
+pub const DEFAULT_CONCURRENCY: usize = 8;
+/// Rarest unlocks kept per game, at minimum.
+const RAREST_PER_GAME: usize = 10;
 const MAX_RETRIES: u32 = 3;
 const INITIAL_BACKOFF_MS: u64 = 500;
 
@@ -26,18 +33,51 @@ fn clear_status() {
     let _ = io::stderr().flush();
 }
 
+/// Controls which optional sections are fetched by `fetch_stats`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchOptions {
+    pub top_games: usize,
+    pub recently_played: bool,
+    pub achievements: bool,
+    /// How many unlocks to keep in `AchievementStats::rarest_unlocks`.
+    pub rarest: usize,
+    /// How many unfinished games to keep in `AchievementStats::near_complete`.
+    pub near_complete: usize,
+}
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        Self {
+            top_games: 5,
+            recently_played: true,
+            achievements: true,
+            rarest: 1,
+            near_complete: 0,
+        }
+    }
+}
+
+/// Steam Web API client. Configure it with the `with_*` builder methods,
+/// then call [`SteamClient::fetch_stats`].
 pub struct SteamClient {
     client: Client,
     api_key: String,
     steam_id: String,
     verbose: bool,
     timeout: Duration,
+    options: FetchOptions,
+    concurrency: usize,
+    base_url: String,
+    recording: Option<Recording>,
+    redactor: Redactor,
 }
 
 impl SteamClient {
+    /// Creates a client for `steam_id` (a SteamID64) authenticated with `api_key`.
     pub fn new(api_key: String, steam_id: String) -> Self {
         let timeout = Duration::from_secs(DEFAULT_TIMEOUT_SECS);
         let client = build_http_client(timeout);
+        let redactor = Redactor::new(&api_key);
 
         Self {
             client,
@@ -45,6 +85,11 @@ impl SteamClient {
             steam_id,
             verbose: false,
             timeout,
+            options: FetchOptions::default(),
+            concurrency: DEFAULT_CONCURRENCY,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            recording: None,
+            redactor,
         }
     }
 
@@ -59,6 +104,39 @@ impl SteamClient {
         self
     }
 
+    pub fn with_options(mut self, options: FetchOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sends API requests to `base_url` instead of api.steampowered.com,
+    /// e.g. a caching proxy or a local mock server.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Records every API response to a directory, or replays them offline.
+    pub fn with_recording(mut self, recording: Option<Recording>) -> Self {
+        self.recording = recording;
+        self
+    }
+
+    /// Masks the Steam ID as well as the API key in logs, errors and recordings.
+    pub fn with_redacted_steam_id(mut self, redact: bool) -> Self {
+        if redact {
+            self.redactor = self.redactor.with_secret(&self.steam_id);
+        }
+        self
+    }
+
+    /// Maximum number of games whose achievements are fetched at once.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Fetches the profile, owned games and achievements and aggregates them.
     pub async fn fetch_stats(&self) -> Result<SteamStats> {
         print_status("Fetching player info...");
         let player = self.fetch_player().await?;
@@ -75,8 +153,12 @@ impl SteamClient {
             .filter(|g| g.playtime_forever == 0)
             .count() as u32;
         let total_playtime = games.games.iter().map(|g| g.playtime_forever).sum();
-        let top_games = extract_top_games(&games);
-        let achievement_stats = self.fetch_achievement_stats(&games).await;
+        let top_games = extract_top_games(&games, self.options.top_games);
+        let achievement_stats = if self.options.achievements {
+            self.fetch_achievement_stats(&games).await
+        } else {
+            None
+        };
 
         Ok(SteamStats {
             username: player.personaname,
@@ -89,9 +171,15 @@ impl SteamClient {
             steam_level,
             recently_played,
             avatar_url: player.avatarfull,
+            installed: None,
+            history: None,
+            playtime_distribution: PlaytimeBucket::distribution(&games.games),
+            games: games.games,
         })
     }
 
+    /// Like [`SteamClient::fetch_stats`], but for an explicit list of owned
+    /// app IDs (as found via the Native SDK) and a known username.
     pub async fn fetch_stats_for_appids(
         &self,
         appids: &[u32],
@@ -113,7 +201,7 @@ impl SteamClient {
             .count() as u32;
 
         let total_playtime = games.games.iter().map(|g| g.playtime_forever).sum();
-        let top_games = extract_top_games(&games);
+        let top_games = extract_top_games(&games, self.options.top_games);
 
         let games_with_playtime: std::collections::HashMap<u32, _> =
             games.games.iter().map(|g| (g.appid, g)).collect();
@@ -136,7 +224,11 @@ impl SteamClient {
                 .collect(),
         };
 
-        let achievement_stats = self.fetch_achievement_stats(&native_games).await;
+        let achievement_stats = if self.options.achievements {
+            self.fetch_achievement_stats(&native_games).await
+        } else {
+            None
+        };
 
         Ok(SteamStats {
             username: username.to_string(),
@@ -149,6 +241,10 @@ impl SteamClient {
             steam_level,
             recently_played,
             avatar_url: player.avatarfull,
+            installed: None,
+            history: None,
+            playtime_distribution: PlaytimeBucket::distribution(&native_games.games),
+            games: native_games.games,
         })
     }
 
@@ -156,19 +252,20 @@ impl SteamClient {
         let steam_level = match self.fetch_steam_level().await {
             Ok(level) => level,
             Err(e) => {
-                if self.verbose {
-                    eprintln!("[verbose] Failed to fetch steam level: {}", e);
-                }
+                self.log(&format!("Failed to fetch steam level: {}", e));
                 None
             }
         };
 
-        let recently_played = match self.fetch_recently_played().await {
+        let recently_played = if self.options.recently_played {
+            self.fetch_recently_played().await
+        } else {
+            Ok(Vec::new())
+        };
+        let recently_played = match recently_played {
             Ok(games) => games,
             Err(e) => {
-                if self.verbose {
-                    eprintln!("[verbose] Failed to fetch recently played: {}", e);
-                }
+                self.log(&format!("Failed to fetch recently played: {}", e));
                 Vec::new()
             }
         };
@@ -179,14 +276,12 @@ impl SteamClient {
     async fn fetch_player(&self) -> Result<super::models::Player> {
         let url = format!(
             "{}/ISteamUser/GetPlayerSummaries/v2/?key={}&steamids={}",
-            BASE_URL, self.api_key, self.steam_id
+            self.base_url, self.api_key, self.steam_id
         );
-        if self.verbose {
-            eprintln!(
-                "[verbose] Fetching player summary for Steam ID: {}",
-                self.steam_id
-            );
-        }
+        self.log(&format!(
+            "Fetching player summary for Steam ID: {}",
+            self.steam_id
+        ));
 
         let body = self.request_with_retry(&url, "player summary").await?;
         detect_api_error(&body, self.verbose)?;
@@ -202,10 +297,79 @@ impl SteamClient {
             .ok_or_else(|| SteamApiError::PlayerNotFound.into())
     }
 
-    async fn fetch_owned_games(&self) -> Result<super::models::OwnedGamesData> {
+    /// Fetches the full `GetOwnedGames` list, including free games played.
+    pub async fn fetch_owned_games(&self) -> Result<super::models::OwnedGamesData> {
         self.fetch_owned_games_filtered(None).await
     }
 
+    /// Fetches every achievement of one game with unlock state, unlock time
+    /// and global rarity, rarest first. Names and descriptions of hidden
+    /// achievements come from `GetSchemaForGame`.
+    pub async fn fetch_achievement_details(&self, appid: u32) -> Result<GameAchievements> {
+        let (player_achievements, global_percentages, schema) = tokio::join!(
+            self.fetch_player_achievements(appid),
+            self.fetch_global_percentages(appid),
+            self.fetch_game_schema(appid)
+        );
+
+        let Some(achievements) = player_achievements? else {
+            return Err(SteamApiError::NoAchievements(appid).into());
+        };
+        let percentages = global_percentages.unwrap_or_else(|e| {
+            self.log(&format!(
+                "Global percentages for app {} unavailable: {}",
+                appid, e
+            ));
+            Default::default()
+        });
+        let schema = schema.unwrap_or_else(|e| {
+            self.log(&format!("Schema for app {} unavailable: {}", appid, e));
+            GameSchema::default()
+        });
+
+        let mut details: Vec<AchievementDetail> = achievements
+            .into_iter()
+            .map(|a| {
+                let entry = schema
+                    .available_game_stats
+                    .achievements
+                    .iter()
+                    .find(|s| s.name == a.apiname);
+                let percent = percentages
+                    .get(&a.apiname)
+                    .or_else(|| percentages.get(&a.apiname.to_uppercase()))
+                    .copied();
+                AchievementDetail {
+                    name: non_empty(a.name)
+                        .or_else(|| entry.and_then(|s| non_empty(s.display_name.clone())))
+                        .unwrap_or_else(|| a.apiname.clone()),
+                    description: non_empty(a.description)
+                        .or_else(|| entry.and_then(|s| non_empty(s.description.clone()))),
+                    hidden: entry.is_some_and(|s| s.hidden == 1),
+                    achieved: a.achieved == 1,
+                    unlock_time: (a.achieved == 1 && a.unlocktime > 0).then_some(a.unlocktime),
+                    global_percent: percent,
+                    api_name: a.apiname,
+                }
+            })
+            .collect();
+        details.sort_by(|a, b| match (a.global_percent, b.global_percent) {
+            (Some(x), Some(y)) => x.total_cmp(&y).then_with(|| a.name.cmp(&b.name)),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => a.name.cmp(&b.name),
+        });
+
+        Ok(GameAchievements {
+            appid,
+            game: schema
+                .game_name
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| format!("App {}", appid)),
+            achievements: details,
+        })
+    }
+
     async fn fetch_owned_games_for_appids(
         &self,
         appids: &[u32],
@@ -230,7 +394,7 @@ impl SteamClient {
     ) -> Result<super::models::OwnedGamesData> {
         let mut url = format!(
             "{}/IPlayerService/GetOwnedGames/v1/?key={}&steamid={}&include_appinfo=1&include_played_free_games=1",
-            BASE_URL, self.api_key, self.steam_id
+            self.base_url, self.api_key, self.steam_id
         );
 
         if let Some(appids) = appids_filter {
@@ -239,9 +403,7 @@ impl SteamClient {
             }
         }
 
-        if self.verbose {
-            eprintln!("[verbose] Fetching owned games...");
-        }
+        self.log("Fetching owned games...");
 
         let body = self.request_with_retry(&url, "owned games").await?;
         detect_private_profile(&body)?;
@@ -255,11 +417,9 @@ impl SteamClient {
     async fn fetch_steam_level(&self) -> Result<Option<u32>> {
         let url = format!(
             "{}/IPlayerService/GetSteamLevel/v1/?key={}&steamid={}",
-            BASE_URL, self.api_key, self.steam_id
+            self.base_url, self.api_key, self.steam_id
         );
-        if self.verbose {
-            eprintln!("[verbose] Fetching steam level...");
-        }
+        self.log("Fetching steam level...");
 
         let body = self.request_with_retry(&url, "steam level").await?;
 
@@ -272,11 +432,9 @@ impl SteamClient {
     async fn fetch_recently_played(&self) -> Result<Vec<GameStat>> {
         let url = format!(
             "{}/IPlayerService/GetRecentlyPlayedGames/v1/?key={}&steamid={}&count=5",
-            BASE_URL, self.api_key, self.steam_id
+            self.base_url, self.api_key, self.steam_id
         );
-        if self.verbose {
-            eprintln!("[verbose] Fetching recently played...");
-        }
+        self.log("Fetching recently played...");
 
         let body = self.request_with_retry(&url, "recently played").await?;
 
@@ -298,9 +456,15 @@ impl SteamClient {
         &self,
         games: &super::models::OwnedGamesData,
     ) -> Option<AchievementStats> {
-        let mut cache = AchievementCache::load();
-        let all_games: Vec<_> = games.games.iter().collect();
-        let total_games = all_games.len();
+        // Recording and replaying must see every game's responses, so the
+        // user's cache is neither read nor updated.
+        let use_cache = self.recording.is_none();
+        let mut cache = if use_cache {
+            AchievementCache::load()
+        } else {
+            AchievementCache::default()
+        };
+        let total_games = games.games.len();
 
         clear_status();
         let pb = ProgressBar::new(total_games as u64);
@@ -310,43 +474,60 @@ impl SteamClient {
                 .unwrap()
                 .progress_chars("#>-"),
         );
-        pb.set_message("Achievements (0 cached, 0 fetched)");
 
-        let mut total_achieved = 0u32;
-        let mut total_possible = 0u32;
-        let mut perfect_games = 0u32;
-        let mut rarest_candidates: Vec<RarestAchievement> = Vec::new();
+        let mut totals = AchievementTotals {
+            rarest_limit: self.options.rarest,
+            near_complete_limit: self.options.near_complete,
+            ..AchievementTotals::default()
+        };
         let mut cached_count = 0u32;
         let mut fetched_count = 0u32;
+        let mut misses = Vec::new();
 
-        for game in &all_games {
+        for game in &games.games {
             let game_name = game
                 .name
                 .clone()
                 .unwrap_or_else(|| format!("App {}", game.appid));
 
-            if let Some(cached) = cache.get(game.appid, game.rtime_last_played) {
-                cached_count += 1;
-                total_achieved += cached.achieved;
-                total_possible += cached.total;
-                if cached.achieved == cached.total && cached.total > 0 {
-                    perfect_games += 1;
-                }
-                if let (Some(name), Some(percent)) = (&cached.rarest_name, cached.rarest_percent) {
-                    rarest_candidates.push(RarestAchievement {
-                        name: name.clone(),
-                        game: game_name,
-                        percent,
+            let cached = cache
+                .get(game.appid, game.rtime_last_played)
+                .filter(|c| c.covers_rarest(self.options.rarest));
+            match cached {
+                Some(cached) => {
+                    cached_count += 1;
+                    totals.add_completion(game.appid, &game_name, cached.achieved, cached.total);
+                    let rarest = cached.rarest.iter().filter_map(|r| {
+                        Some(RarestAchievement {
+                            name: r.name.clone(),
+                            game: game_name.clone(),
+                            percent: r.percent?,
+                        })
                     });
+                    totals.add(cached.achieved, cached.total, rarest);
+                    pb.inc(1);
                 }
-                pb.inc(1);
-                pb.set_message(format!(
-                    "Achievements ({} cached, {} fetched)",
-                    cached_count, fetched_count
-                ));
-                continue;
+                None => misses.push((game, game_name)),
             }
+        }
+        pb.set_message(format!(
+            "Achievements ({} cached, {} fetched)",
+            cached_count, fetched_count
+        ));
+
+        // Cache misses are fetched by a bounded pool of in-flight requests.
+        // Results arrive in completion order, so aggregation must not depend
+        // on ordering (see `AchievementTotals`).
+        let mut fetches = stream::iter(misses)
+            .map(|(game, game_name)| async move {
+                let result = self
+                    .fetch_game_achievements(game.appid, game_name.clone())
+                    .await;
+                (game, game_name, result)
+            })
+            .buffer_unordered(self.concurrency);
 
+        while let Some((game, game_name, result)) = fetches.next().await {
             fetched_count += 1;
             pb.inc(1);
             pb.set_message(format!(
@@ -354,68 +535,69 @@ impl SteamClient {
                 cached_count, fetched_count
             ));
 
-            if let Some(result) = self
-                .fetch_game_achievements(game.appid, game_name.clone())
-                .await
-            {
-                total_achieved += result.achieved;
-                total_possible += result.total;
-                if result.achieved == result.total && result.total > 0 {
-                    perfect_games += 1;
+            match result {
+                Ok(Some(result)) => {
+                    cache.set_with_rarest_limit(
+                        game.appid,
+                        game.rtime_last_played,
+                        result.achieved,
+                        result.total,
+                        result.rarest.iter().map(|r| (r.name.as_str(), r.percent)),
+                        Some(self.rarest_per_game()),
+                    );
+                    totals.add_completion(game.appid, &game_name, result.achieved, result.total);
+                    totals.add(result.achieved, result.total, result.rarest);
                 }
-
-                let rarest_for_cache = result.rarest.as_ref().map(|r| (r.name.as_str(), r.percent));
-                cache.set(
-                    game.appid,
-                    game.rtime_last_played,
-                    result.achieved,
-                    result.total,
-                    rarest_for_cache,
-                );
-
-                if let Some(r) = result.rarest {
-                    rarest_candidates.push(r);
+                // Cached as 0/0 so games without achievements are not
+                // re-requested on every run.
+                Ok(None) => cache.set(game.appid, game.rtime_last_played, 0, 0, None),
+                Err(e) => {
+                    self.log(&format!(
+                        "Achievements for app {} could not be fetched: {:#}",
+                        game.appid, e
+                    ));
+                    totals.failed_games += 1;
                 }
             }
         }
 
         pb.finish_and_clear();
         clear_status();
-        cache.save();
-
-        let rarest = rarest_candidates.into_iter().min_by(|a, b| {
-            a.percent
-                .partial_cmp(&b.percent)
-                .unwrap()
-                .then_with(|| a.game.cmp(&b.game))
-                .then_with(|| a.name.cmp(&b.name))
-        });
+        if use_cache {
+            cache.save();
+        }
 
-        (total_possible > 0).then_some(AchievementStats {
-            total_achieved,
-            total_possible,
-            perfect_games,
-            rarest,
-        })
+        totals.into_stats()
     }
 
+    /// Returns `Ok(None)` when the game has no achievements (Steam reports
+    /// "no stats"), and an error when the player's achievements could not be
+    /// fetched at all.
     async fn fetch_game_achievements(
         &self,
         appid: u32,
         game_name: String,
-    ) -> Option<GameAchievementResult> {
+    ) -> Result<Option<GameAchievementResult>> {
         let (player_achievements, global_percentages) = tokio::join!(
             self.fetch_player_achievements(appid),
             self.fetch_global_percentages(appid)
         );
 
-        let achievements = player_achievements.ok()?;
-        let percentages = global_percentages.ok().unwrap_or_default();
+        let Some(achievements) = player_achievements? else {
+            return Ok(None);
+        };
+        let percentages = global_percentages.unwrap_or_else(|e| {
+            self.log(&format!(
+                "Global percentages for app {} unavailable: {}",
+                appid, e
+            ));
+            Default::default()
+        });
 
         let achieved = achievements.iter().filter(|a| a.achieved == 1).count() as u32;
         let total = achievements.len() as u32;
 
-        let rarest = achievements
+        let mut rarest: Vec<RarestAchievement> = achievements
             .iter()
             .filter(|a| a.achieved == 1)
             .filter_map(|a| {
@@ -428,32 +610,47 @@ impl SteamClient {
                     percent: *percent,
                 })
             })
-            .min_by(|a, b| a.percent.partial_cmp(&b.percent).unwrap());
+            .collect();
+        rarest.sort_by(rarity_order);
+        rarest.truncate(self.rarest_per_game());
 
-        Some(GameAchievementResult {
+        Ok(Some(GameAchievementResult {
             achieved,
             total,
             rarest,
-        })
+        }))
+    }
+
+    /// How many rarest unlocks to keep per game. Cached entries record it,
+    /// and a later run that wants more treats them as misses.
+    fn rarest_per_game(&self) -> usize {
+        self.options.rarest.max(RAREST_PER_GAME)
     }
 
     async fn fetch_player_achievements(
         &self,
         appid: u32,
-    ) -> Result<Vec<super::models::Achievement>> {
+    ) -> Result<Option<Vec<super::models::Achievement>>> {
         let url = format!(
             "{}/ISteamUserStats/GetPlayerAchievements/v1/?key={}&steamid={}&appid={}&l=english",
-            BASE_URL, self.api_key, self.steam_id, appid
+            self.base_url, self.api_key, self.steam_id, appid
         );
-        Ok(self
-            .client
-            .get(&url)
-            .send()
-            .await?
-            .json::<AchievementsResponse>()
-            .await?
-            .playerstats
-            .achievements)
+        let context = format!("GetPlayerAchievements (app {})", appid);
+        let body = match self.request_with_retry(&url, &context).await {
+            Ok(body) => body,
+            // Steam answers games without achievements with HTTP 400 and an
+            // error body rather than an empty list.
+            Err(e) if is_no_stats_error(&e) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        let response: AchievementsResponse = serde_json::from_str(&body)
+            .with_context(|| format!("Failed to parse {} response", context))?;
+        match response.playerstats.error {
+            Some(message) if has_no_stats(&message) => Ok(None),
+            Some(message) => Err(anyhow::anyhow!("{} failed: {}", context, message)),
+            None => Ok(Some(response.playerstats.achievements)),
+        }
     }
 
     async fn fetch_global_percentages(
@@ -462,10 +659,12 @@ impl SteamClient {
     ) -> Result<std::collections::HashMap<String, f64>> {
         let url = format!(
             "{}/ISteamUserStats/GetGlobalAchievementPercentagesForApp/v2/?gameid={}",
-            BASE_URL, appid
+            self.base_url, appid
         );
-        let response: GlobalAchievementsResponse =
-            self.client.get(&url).send().await?.json().await?;
+        let context = format!("GetGlobalAchievementPercentagesForApp (app {})", appid);
+        let body = self.request_with_retry(&url, &context).await?;
+        let response: GlobalAchievementsResponse = serde_json::from_str(&body)
+            .with_context(|| format!("Failed to parse {} response", context))?;
 
         Ok(response
             .achievementpercentages
@@ -475,55 +674,87 @@ impl SteamClient {
             .collect())
     }
 
+    async fn fetch_game_schema(&self, appid: u32) -> Result<GameSchema> {
+        let url = format!(
+            "{}/ISteamUserStats/GetSchemaForGame/v2/?key={}&appid={}&l=english",
+            self.base_url, self.api_key, appid
+        );
+        let context = format!("GetSchemaForGame (app {})", appid);
+        let body = self.request_with_retry(&url, &context).await?;
+        let response: GameSchemaResponse = serde_json::from_str(&body)
+            .with_context(|| format!("Failed to parse {} response", context))?;
+        Ok(response.game)
+    }
+
+    /// Prints a `[verbose]` line with secrets masked.
+    fn log(&self, message: &str) {
+        if self.verbose {
+            eprintln!("[verbose] {}", self.redactor.redact(message));
+        }
+    }
+
     async fn request_with_retry(&self, url: &str, context: &str) -> Result<String> {
+        match &self.recording {
+            Some(Recording::Replay(dir)) => {
+                self.log(&format!("Replaying {} from {}", context, dir.display()));
+                recording::replay(dir, &self.base_url, url)
+            }
+            Some(Recording::Record(dir)) => {
+                let result = self.send_with_retry(url, context).await;
+                recording::record(dir, &self.base_url, url, &result, &self.redactor)?;
+                result
+            }
+            None => self.send_with_retry(url, context).await,
+        }
+    }
+
+    async fn send_with_retry(&self, url: &str, context: &str) -> Result<String> {
         let mut last_error = None;
 
         for attempt in 0..MAX_RETRIES {
             if attempt > 0 {
                 let backoff = Duration::from_millis(INITIAL_BACKOFF_MS * 2u64.pow(attempt - 1));
-                if self.verbose {
-                    eprintln!(
-                        "[verbose] Retry {}/{} for {} (waiting {}ms)",
-                        attempt,
-                        MAX_RETRIES - 1,
-                        context,
-                        backoff.as_millis()
-                    );
-                }
+                self.log(&format!(
+                    "Retry {}/{} for {} (waiting {}ms)",
+                    attempt,
+                    MAX_RETRIES - 1,
+                    context,
+                    backoff.as_millis()
+                ));
                 tokio::time::sleep(backoff).await;
             }
 
-            let api_error = match self.client.get(url).send().await {
-                Ok(response) => {
-                    let status = response.status();
+            let api_error = self
+                .redactor
+                .redact_error(match self.client.get(url).send().await {
+                    Ok(response) => {
+                        let status = response.status();
 
-                    if self.verbose {
-                        eprintln!("[verbose] {} API status: {}", context, status);
-                    }
+                        self.log(&format!("{} API status: {}", context, status));
 
-                    if status.is_success() {
-                        let body = response
-                            .text()
-                            .await
-                            .with_context(|| format!("Failed to read {} response body", context))?;
+                        if status.is_success() {
+                            // reqwest errors embed the request URL, which carries the key.
+                            let body = response
+                                .text()
+                                .await
+                                .map_err(reqwest::Error::without_url)
+                                .with_context(|| {
+                                format!("Failed to read {} response body", context)
+                            })?;
 
-                        if self.verbose {
                             let truncated = &body[..body.floor_char_boundary(500)];
-                            eprintln!("[verbose] {} response body: {}", context, truncated);
+                            self.log(&format!("{} response body: {}", context, truncated));
+
+                            return Ok(body);
                         }
 
-                        return Ok(body);
+                        classify_http_error(status, response).await
                     }
+                    Err(e) if e.is_timeout() => SteamApiError::Timeout,
+                    Err(e) => SteamApiError::NetworkError(e.to_string()),
+                });
 
-                    classify_http_error(status, response).await
-                }
-                Err(e) if e.is_timeout() => SteamApiError::Timeout,
-                Err(e) => SteamApiError::NetworkError(e.to_string()),
-            };
-
-            if self.verbose {
-                eprintln!("[verbose] {} request failed: {}", context, api_error);
-            }
+            self.log(&format!("{} request failed: {}", context, api_error));
 
             if !api_error.is_retryable() {
                 return Err(api_error.into());
@@ -546,13 +777,13 @@ fn build_http_client(timeout: Duration) -> Client {
         .expect("Failed to build HTTP client")
 }
 
-fn extract_top_games(games: &super::models::OwnedGamesData) -> Vec<GameStat> {
+fn extract_top_games(games: &super::models::OwnedGamesData, limit: usize) -> Vec<GameStat> {
     let mut sorted: Vec<_> = games.games.iter().collect();
     sorted.sort_by_key(|g| std::cmp::Reverse(g.playtime_forever));
 
     sorted
         .into_iter()
-        .take(5)
+        .take(limit)
         .map(|g| GameStat {
             name: g.name.clone().unwrap_or_else(|| format!("App {}", g.appid)),
             playtime_minutes: g.playtime_forever,
@@ -577,6 +808,21 @@ async fn classify_http_error(
     }
 }
 
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
+}
+
+fn has_no_stats(message: &str) -> bool {
+    message.contains("has no stats")
+}
+
+fn is_no_stats_error(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<SteamApiError>(),
+        Some(SteamApiError::ApiError { status: 400, message }) if has_no_stats(message)
+    )
+}
+
 fn detect_api_error(body: &str, verbose: bool) -> Result<()> {
     if body.contains("\"players\":[]") || body.contains("\"players\": []") {
         return Err(SteamApiError::PlayerNotFound.into());
@@ -615,10 +861,91 @@ fn detect_private_profile(body: &str) -> Result<()> {
     }
 }
 
+/// Running totals for the achievement crawl. Every operation is commutative
+/// and the rarest pick has a total tie-break order, so the result is identical
+/// regardless of the order in which games complete.
+#[derive(Default)]
+struct AchievementTotals {
+    total_achieved: u32,
+    total_possible: u32,
+    perfect_games: u32,
+    /// Rarest first, at most `rarest_limit` (but always the single rarest).
+    rarest: Vec<RarestAchievement>,
+    rarest_limit: usize,
+    failed_games: u32,
+    near_complete: Vec<GameCompletion>,
+    near_complete_limit: usize,
+}
+
+impl AchievementTotals {
+    fn add(
+        &mut self,
+        achieved: u32,
+        total: u32,
+        rarest: impl IntoIterator<Item = RarestAchievement>,
+    ) {
+        self.total_achieved += achieved;
+        self.total_possible += total;
+        if achieved == total && total > 0 {
+            self.perfect_games += 1;
+        }
+        self.rarest.extend(rarest);
+        self.rarest.sort_by(rarity_order);
+        self.rarest.truncate(self.rarest_limit.max(1));
+    }
+
+    /// Keeps started, unfinished games as near-completion candidates.
+    fn add_completion(&mut self, appid: u32, name: &str, achieved: u32, total: u32) {
+        if self.near_complete_limit > 0 && achieved > 0 && achieved < total {
+            self.near_complete.push(GameCompletion {
+                appid,
+                name: name.to_string(),
+                achieved,
+                total,
+            });
+        }
+    }
+
+    fn into_stats(mut self) -> Option<AchievementStats> {
+        // Fewest remaining first, then highest completion; the name and app
+        // ID keep the order independent of fetch completion order.
+        self.near_complete.sort_by(|a, b| {
+            a.remaining()
+                .cmp(&b.remaining())
+                .then_with(|| b.percent().total_cmp(&a.percent()))
+                .then_with(|| a.name.cmp(&b.name))
+                .then(a.appid.cmp(&b.appid))
+        });
+        self.near_complete.truncate(self.near_complete_limit);
+
+        // Failures are still reported so a fully failed crawl is not
+        // mistaken for a library without achievements.
+        (self.total_possible > 0 || self.failed_games > 0).then_some(AchievementStats {
+            total_achieved: self.total_achieved,
+            total_possible: self.total_possible,
+            perfect_games: self.perfect_games,
+            rarest: self.rarest.first().cloned(),
+            rarest_unlocks: self.rarest.into_iter().take(self.rarest_limit).collect(),
+            failed_games: self.failed_games,
+            near_complete: self.near_complete,
+        })
+    }
+}
+
+/// Rarest first; ties are broken by game and name so the order does not
+/// depend on fetch completion order.
+fn rarity_order(a: &RarestAchievement, b: &RarestAchievement) -> std::cmp::Ordering {
+    a.percent
+        .total_cmp(&b.percent)
+        .then_with(|| a.game.cmp(&b.game))
+        .then_with(|| a.name.cmp(&b.name))
+}
+
+#[derive(Debug)]
 struct GameAchievementResult {
     achieved: u32,
     total: u32,
-    rarest: Option<RarestAchievement>,
+    rarest: Vec<RarestAchievement>,
 }
 
 #[cfg(test)]
@@ -747,6 +1074,45 @@ mod tests {
         spawn_tls_server(&[(request_path, body)], 1)
     }
 
+    // Plain-HTTP stand-in for the Steam Web API, reached through
+    // `with_base_url`. Each route is a request path with its query, a status
+    // code and a body; other requests get a 404. Serves until the test exits.
+    fn spawn_mock_steam_api(routes: &[(&'static str, u16, &'static str)]) -> String {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind mock API");
+        let base_url = format!("http://{}", listener.local_addr().expect("local_addr"));
+        let routes = routes.to_vec();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request);
+                let path = request.split_whitespace().nth(1).unwrap_or("");
+                let (status, body) = routes
+                    .iter()
+                    .find(|(route, _, _)| *route == path)
+                    .map(|(_, status, body)| (*status, *body))
+                    .unwrap_or((404, ""));
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+            }
+        });
+        base_url
+    }
+
     #[cfg(unix)]
     #[test]
     fn test_spawn_tls_server_returns_none_when_openssl_fails() {
@@ -835,6 +1201,11 @@ mod tests {
             steam_id: "id".into(),
             verbose: true,
             timeout: Duration::from_secs(3),
+            options: FetchOptions::default(),
+            concurrency: DEFAULT_CONCURRENCY,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            recording: None,
+            redactor: Redactor::default(),
         };
 
         let player = run_async(client.fetch_player()).expect("player response should parse");
@@ -867,6 +1238,11 @@ mod tests {
             steam_id: "id".into(),
             verbose: true,
             timeout: Duration::from_secs(3),
+            options: FetchOptions::default(),
+            concurrency: DEFAULT_CONCURRENCY,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            recording: None,
+            redactor: Redactor::default(),
         };
 
         let err = run_async(client.fetch_recently_played())
@@ -895,6 +1271,11 @@ mod tests {
             steam_id: "id".into(),
             verbose: false,
             timeout: Duration::from_secs(3),
+            options: FetchOptions::default(),
+            concurrency: DEFAULT_CONCURRENCY,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            recording: None,
+            redactor: Redactor::default(),
         };
 
         let level = run_async(client.fetch_steam_level()).expect("steam level should parse");
@@ -949,6 +1330,11 @@ mod tests {
             steam_id: "id".into(),
             verbose: true,
             timeout: Duration::from_secs(3),
+            options: FetchOptions::default(),
+            concurrency: DEFAULT_CONCURRENCY,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            recording: None,
+            redactor: Redactor::default(),
         };
 
         let stats = run_async(client.fetch_stats()).expect("stats response should parse");
@@ -973,6 +1359,75 @@ mod tests {
         let _ = std::fs::remove_dir_all(&cache_root);
     }
 
+    #[test]
+    fn test_fetch_stats_honors_fetch_options() {
+        let _guard = crate::test_support::lock_env();
+        let cache_root = unique_temp_root("fetch-stats-options-cache");
+        let previous_cache = std::env::var("XDG_CACHE_HOME").ok();
+        std::env::set_var("XDG_CACHE_HOME", &cache_root);
+
+        // A cached entry would surface as achievement stats if the crawl ran.
+        let mut cache = crate::cache::AchievementCache::default();
+        cache.set(100, 1000, 1, 2, Some(("Rare One", 3.5)));
+        cache.save();
+
+        let files = [
+            (
+                "ISteamUser/GetPlayerSummaries/v2/?key=k&steamids=id",
+                r#"{"response":{"players":[{"personaname":"TLS User"}]}}"#,
+            ),
+            (
+                "IPlayerService/GetOwnedGames/v1/?key=k&steamid=id&include_appinfo=1&include_played_free_games=1",
+                r#"{"response":{"game_count":2,"games":[{"appid":100,"name":"Game One","playtime_forever":120,"rtime_last_played":1000},{"appid":200,"name":"Game Two","playtime_forever":60,"rtime_last_played":2000}]}}"#,
+            ),
+            (
+                "IPlayerService/GetSteamLevel/v1/?key=k&steamid=id",
+                r#"{"response":{"player_level":42}}"#,
+            ),
+        ];
+        let Some(server) = spawn_tls_server(&files, files.len()) else {
+            restore_xdg_cache_home(previous_cache);
+            let _ = std::fs::remove_dir_all(&cache_root);
+            return;
+        };
+        let client = SteamClient {
+            client: Client::builder()
+                .danger_accept_invalid_certs(true)
+                .no_proxy()
+                .timeout(Duration::from_secs(3))
+                .resolve("api.steampowered.com", server.addr)
+                .build()
+                .expect("client should build"),
+            api_key: "k".into(),
+            steam_id: "id".into(),
+            verbose: false,
+            timeout: Duration::from_secs(3),
+            options: FetchOptions {
+                top_games: 1,
+                recently_played: false,
+                achievements: false,
+                rarest: 1,
+                near_complete: 0,
+            },
+            concurrency: DEFAULT_CONCURRENCY,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            recording: None,
+            redactor: Redactor::default(),
+        };
+
+        let stats = run_async(client.fetch_stats()).expect("stats response should parse");
+
+        assert_eq!(stats.top_games.len(), 1);
+        assert_eq!(stats.top_games[0].name, "Game One");
+        assert!(stats.recently_played.is_empty());
+        assert!(stats.achievement_stats.is_none());
+        assert_eq!(stats.steam_level, Some(42));
+
+        drop(server);
+        restore_xdg_cache_home(previous_cache);
+        let _ = std::fs::remove_dir_all(&cache_root);
+    }
+
     #[test]
     fn test_fetch_stats_for_appids_builds_success_response_from_filtered_api_data() {
         let _guard = crate::test_support::lock_env();
@@ -1021,6 +1476,11 @@ mod tests {
             steam_id: "id".into(),
             verbose: true,
             timeout: Duration::from_secs(3),
+            options: FetchOptions::default(),
+            concurrency: DEFAULT_CONCURRENCY,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            recording: None,
+            redactor: Redactor::default(),
         };
 
         let stats = run_async(client.fetch_stats_for_appids(&[100, 200, 300], "Native User"))
@@ -1076,7 +1536,7 @@ mod tests {
                 make_game(3, Some("C"), 50),
             ],
         };
-        let top = extract_top_games(&games);
+        let top = extract_top_games(&games, 5);
         assert_eq!(top[0].name, "B");
         assert_eq!(top[1].name, "C");
         assert_eq!(top[2].name, "A");
@@ -1167,7 +1627,7 @@ mod tests {
             game_count: 0,
             games: vec![],
         };
-        let top = extract_top_games(&games);
+        let top = extract_top_games(&games, 5);
         assert!(top.is_empty());
     }
 
@@ -1179,19 +1639,178 @@ mod tests {
                 .map(|i| make_game(i as u32, Some(&format!("G{}", i)), (i as u32) * 10))
                 .collect(),
         };
-        let top = extract_top_games(&games);
+        let top = extract_top_games(&games, 5);
         assert_eq!(top.len(), 5);
         assert_eq!(top[0].name, "G6");
         assert_eq!(top[4].name, "G2");
     }
 
+    fn rarest(name: &str, game: &str, percent: f64) -> RarestAchievement {
+        RarestAchievement {
+            name: name.into(),
+            game: game.into(),
+            percent,
+        }
+    }
+
+    #[test]
+    fn test_achievement_totals_is_order_independent() {
+        let entries = || {
+            vec![
+                (5, 5, Some(rarest("B", "Game Two", 1.0))),
+                (2, 4, Some(rarest("A", "Game One", 1.0))),
+                (0, 0, None),
+                (3, 3, Some(rarest("Z", "Game One", 1.0))),
+            ]
+        };
+        let mut forward = AchievementTotals::default();
+        for (achieved, total, rarest) in entries() {
+            forward.add(achieved, total, rarest);
+        }
+        let mut backward = AchievementTotals::default();
+        for (achieved, total, rarest) in entries().into_iter().rev() {
+            backward.add(achieved, total, rarest);
+        }
+
+        let forward = forward.into_stats().expect("totals are non-zero");
+        let backward = backward.into_stats().expect("totals are non-zero");
+        assert_eq!(forward.total_achieved, 10);
+        assert_eq!(forward.total_possible, 12);
+        assert_eq!(forward.perfect_games, 2);
+        let (f, b) = (forward.rarest.unwrap(), backward.rarest.unwrap());
+        assert_eq!((f.game.as_str(), f.name.as_str()), ("Game One", "A"));
+        assert_eq!((b.game.as_str(), b.name.as_str()), ("Game One", "A"));
+    }
+
+    #[test]
+    fn test_achievement_totals_keeps_top_n_rarest_in_any_order() {
+        let unlock = |game: &str, name: &str, percent: f64| RarestAchievement {
+            name: name.to_string(),
+            game: game.to_string(),
+            percent,
+        };
+        let games = || {
+            vec![
+                vec![unlock("A", "a1", 4.0), unlock("A", "a2", 0.5)],
+                vec![unlock("B", "b1", 2.0)],
+                vec![unlock("C", "c1", 0.5), unlock("C", "c2", 9.0)],
+            ]
+        };
+        let collect = |order: Vec<Vec<RarestAchievement>>| {
+            let mut totals = AchievementTotals {
+                rarest_limit: 3,
+                ..AchievementTotals::default()
+            };
+            for unlocks in order {
+                totals.add(1, 2, unlocks);
+            }
+            let stats = totals.into_stats().expect("totals are non-zero");
+            assert_eq!(stats.rarest.as_ref().map(|r| r.name.as_str()), Some("a2"));
+            stats
+                .rarest_unlocks
+                .iter()
+                .map(|r| r.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let forward = collect(games());
+        let backward = collect(games().into_iter().rev().collect());
+        assert_eq!(forward, vec!["a2", "c1", "b1"]);
+        assert_eq!(forward, backward);
+    }
+
+    #[test]
+    fn test_achievement_totals_ranks_near_complete_games() {
+        let mut totals = AchievementTotals {
+            near_complete_limit: 3,
+            ..AchievementTotals::default()
+        };
+        for (appid, name, achieved, total) in [
+            (1, "Two Left Small", 8, 10),
+            (2, "Perfect", 10, 10),
+            (3, "One Left", 49, 50),
+            (4, "Untouched", 0, 5),
+            (5, "Two Left Big", 98, 100),
+            (6, "Far Away", 1, 100),
+        ] {
+            totals.add_completion(appid, name, achieved, total);
+            totals.add(achieved, total, None);
+        }
+
+        let stats = totals.into_stats().expect("totals are non-zero");
+        let names: Vec<&str> = stats
+            .near_complete
+            .iter()
+            .map(|g| g.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["One Left", "Two Left Big", "Two Left Small"]);
+        assert_eq!(stats.near_complete[0].remaining(), 1);
+        assert_eq!(stats.near_complete[0].percent(), 98.0);
+    }
+
+    #[test]
+    fn test_achievement_totals_near_complete_disabled_by_zero_limit() {
+        let mut totals = AchievementTotals::default();
+        totals.add_completion(1, "One Left", 9, 10);
+        totals.add(9, 10, None);
+        assert!(totals.into_stats().unwrap().near_complete.is_empty());
+    }
+
+    #[test]
+    fn test_achievement_totals_without_achievements_is_none() {
+        let mut totals = AchievementTotals::default();
+        totals.add(0, 0, None);
+        assert!(totals.into_stats().is_none());
+    }
+
+    #[test]
+    fn test_steam_client_with_base_url_trims_trailing_slash() {
+        let client = SteamClient::new("k".into(), "id".into());
+        assert_eq!(client.base_url, DEFAULT_BASE_URL);
+        let client = client.with_base_url("http://localhost:8080/");
+        assert_eq!(client.base_url, "http://localhost:8080");
+    }
+
+    #[test]
+    fn test_steam_client_with_concurrency_clamps_to_one() {
+        let client = SteamClient::new("k".into(), "id".into()).with_concurrency(0);
+        assert_eq!(client.concurrency, 1);
+        let client = SteamClient::new("k".into(), "id".into());
+        assert_eq!(client.concurrency, DEFAULT_CONCURRENCY);
+    }
+
+    #[test]
+    fn test_extract_top_games_respects_limit() {
+        let games = super::super::models::OwnedGamesData {
+            game_count: 3,
+            games: (0..3)
+                .map(|i| make_game(i as u32, Some(&format!("G{}", i)), (i as u32) * 10))
+                .collect(),
+        };
+        assert_eq!(extract_top_games(&games, 2).len(), 2);
+        assert!(extract_top_games(&games, 0).is_empty());
+    }
+
+    #[test]
+    fn test_steam_client_with_options_overrides_defaults() {
+        let options = FetchOptions {
+            top_games: 10,
+            recently_played: false,
+            achievements: false,
+            rarest: 3,
+            near_complete: 5,
+        };
+        let c = SteamClient::new("k".into(), "id".into()).with_options(options.clone());
+        assert_eq!(c.options, options);
+    }
+
     #[test]
     fn test_extract_top_games_falls_back_to_appid_when_name_missing() {
         let games = super::super::models::OwnedGamesData {
             game_count: 1,
             games: vec![make_game(12345, None, 60)],
         };
-        let top = extract_top_games(&games);
+        let top = extract_top_games(&games, 5);
         assert_eq!(top[0].name, "App 12345");
         assert_eq!(top[0].playtime_minutes, 60);
     }
@@ -1237,6 +1856,11 @@ mod tests {
             steam_id: "id".into(),
             verbose: false,
             timeout: Duration::from_secs(1),
+            options: FetchOptions::default(),
+            concurrency: DEFAULT_CONCURRENCY,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            recording: None,
+            redactor: Redactor::default(),
         };
 
         let err = run_async(client.fetch_stats()).expect_err("player fetch should fail first");
@@ -1255,6 +1879,11 @@ mod tests {
             steam_id: "id".into(),
             verbose: false,
             timeout: Duration::from_secs(1),
+            options: FetchOptions::default(),
+            concurrency: DEFAULT_CONCURRENCY,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            recording: None,
+            redactor: Redactor::default(),
         };
 
         let err = run_async(client.fetch_stats_for_appids(&[1, 2], "native-user"))
@@ -1319,7 +1948,7 @@ mod tests {
         // is entered and `fetch_owned_games_filtered(Some(chunk))` is invoked
         // — exercising the source line that the empty-slice test cannot
         // reach (chunks(100) of [] yields nothing). The inner call targets
-        // BASE_URL and is therefore not reachable from a unit test, so we
+        // the default base URL and is therefore not reachable from a unit test, so we
         // wrap the whole call in a tight `tokio::time::timeout` to abort
         // before any real network handshake completes. The line counter for
         // the loop-body statement increments as soon as the future starts
@@ -1341,6 +1970,108 @@ mod tests {
         });
     }
 
+    mod achievement_details_tests {
+        use super::super::*;
+        use super::{run_async, unique_temp_root};
+        use std::fs;
+        use std::path::Path;
+
+        fn write(dir: &Path, endpoint: &str, stem: &str, body: &str) {
+            let endpoint_dir = dir.join(endpoint);
+            fs::create_dir_all(&endpoint_dir).unwrap();
+            fs::write(endpoint_dir.join(format!("{}.json", stem)), body).unwrap();
+        }
+
+        fn replay_client(dir: &Path) -> SteamClient {
+            SteamClient::new(String::new(), "id".into())
+                .with_recording(Some(Recording::Replay(dir.to_path_buf())))
+        }
+
+        #[test]
+        fn test_fetch_achievement_details_merges_schema_and_sorts_by_rarity() {
+            let dir = unique_temp_root("achievement-details");
+            write(
+                &dir,
+                "ISteamUserStats/GetPlayerAchievements/v1",
+                "appid-620_l-english_steamid-id",
+                r#"{"playerstats":{"achievements":[
+                    {"apiname":"COMMON","achieved":1,"unlocktime":1700000000,"name":"Common","description":"Easy"},
+                    {"apiname":"SECRET","achieved":0,"unlocktime":0,"name":"","description":""},
+                    {"apiname":"UNRATED","achieved":0,"unlocktime":0,"name":"Unrated"},
+                    {"apiname":"rare","achieved":1,"unlocktime":0,"name":"Rare"}
+                ]}}"#,
+            );
+            write(
+                &dir,
+                "ISteamUserStats/GetGlobalAchievementPercentagesForApp/v2",
+                "gameid-620",
+                r#"{"achievementpercentages":{"achievements":[
+                    {"name":"COMMON","percent":"80.5"},{"name":"SECRET","percent":4.0},{"name":"RARE","percent":0.5}
+                ]}}"#,
+            );
+            write(
+                &dir,
+                "ISteamUserStats/GetSchemaForGame/v2",
+                "appid-620_l-english",
+                r#"{"game":{"gameName":"Portal 2","availableGameStats":{"achievements":[
+                    {"name":"SECRET","displayName":"Secret Ending","hidden":1,"description":"Find it"}
+                ]}}}"#,
+            );
+
+            let details = run_async(replay_client(&dir).fetch_achievement_details(620))
+                .expect("recorded achievements should load");
+            assert_eq!(details.game, "Portal 2");
+            assert_eq!(details.unlocked_count(), 2);
+
+            let names: Vec<&str> = details
+                .achievements
+                .iter()
+                .map(|a| a.name.as_str())
+                .collect();
+            assert_eq!(names, vec!["Rare", "Secret Ending", "Common", "Unrated"]);
+
+            let secret = &details.achievements[1];
+            assert!(secret.hidden && !secret.achieved);
+            assert_eq!(secret.description.as_deref(), Some("Find it"));
+            assert_eq!(details.achievements[2].unlock_time, Some(1_700_000_000));
+            assert_eq!(details.achievements[0].unlock_time, None);
+            assert_eq!(details.achievements[3].global_percent, None);
+
+            let _ = fs::remove_dir_all(&dir);
+        }
+
+        #[test]
+        fn test_fetch_achievement_details_without_schema_or_stats() {
+            let dir = unique_temp_root("achievement-details-missing");
+            write(
+                &dir,
+                "ISteamUserStats/GetPlayerAchievements/v1",
+                "appid-1_l-english_steamid-id",
+                r#"{"playerstats":{"achievements":[{"apiname":"ONLY","achieved":0}]}}"#,
+            );
+            let details = run_async(replay_client(&dir).fetch_achievement_details(1))
+                .expect("schema and percentages are optional");
+            assert_eq!(details.game, "App 1");
+            assert_eq!(details.achievements[0].name, "ONLY");
+            assert!(!details.achievements[0].hidden);
+
+            write(
+                &dir,
+                "ISteamUserStats/GetPlayerAchievements/v1",
+                "appid-2_l-english_steamid-id",
+                r#"{"playerstats":{"error":"Requested app has no stats","success":false}}"#,
+            );
+            let err = run_async(replay_client(&dir).fetch_achievement_details(2))
+                .expect_err("games without stats should error");
+            assert!(matches!(
+                err.downcast_ref::<SteamApiError>(),
+                Some(SteamApiError::NoAchievements(2))
+            ));
+
+            let _ = fs::remove_dir_all(&dir);
+        }
+    }
+
     mod fetch_optional_details_tests {
         use super::super::*;
         use super::{run_async, unbound_localhost_addr};
@@ -1357,6 +2088,11 @@ mod tests {
                 steam_id: "id".into(),
                 verbose: true,
                 timeout: Duration::from_secs(1),
+                options: FetchOptions::default(),
+                concurrency: DEFAULT_CONCURRENCY,
+                base_url: DEFAULT_BASE_URL.to_string(),
+                recording: None,
+                redactor: Redactor::default(),
             };
             let (level, recently_played) = run_async(client.fetch_optional_details());
 
@@ -1581,6 +2317,37 @@ mod tests {
             assert!(err.downcast_ref::<SteamApiError>().is_some());
         }
 
+        #[test]
+        fn test_with_base_url_routes_requests_to_custom_host() {
+            // Plain HTTP stand-in: no TLS or DNS overrides needed once the
+            // base URL is configurable.
+            let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+            let base_url = format!("http://{}/", listener.local_addr().expect("addr"));
+            let server = std::thread::spawn(move || {
+                let (mut stream, _) = listener.accept().expect("accept");
+                let mut buf = [0u8; 1024];
+                let n = stream.read(&mut buf).unwrap_or(0);
+                let body = br#"{"response":{"players":[{"personaname":"mock-user"}]}}"#;
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                let _ = stream.write_all(body);
+                String::from_utf8_lossy(&buf[..n]).into_owned()
+            });
+
+            let client = SteamClient::new("k".into(), "id".into()).with_base_url(base_url);
+            let player = run_async(client.fetch_player()).expect("mock player should load");
+            let request = server.join().expect("server thread");
+
+            assert_eq!(player.personaname, "mock-user");
+            assert!(
+                request.starts_with("GET /ISteamUser/GetPlayerSummaries/v2/?key=k&steamids=id "),
+                "unexpected request: {request}"
+            );
+        }
+
         // Spin up a TCP server that responds to consecutive requests using
         // the supplied (status, reason, body) sequence. After the sequence is
         // exhausted the server thread exits.
@@ -1766,17 +2533,28 @@ mod tests {
                 steam_id: "id".into(),
                 verbose: false,
                 timeout: std::time::Duration::from_secs(3),
+                options: FetchOptions::default(),
+                concurrency: DEFAULT_CONCURRENCY,
+                base_url: DEFAULT_BASE_URL.to_string(),
+                recording: None,
+                redactor: Redactor::default(),
             };
 
             let result = run_async(client.fetch_game_achievements(123, "Game 123".to_string()))
+                .expect("player achievements should be fetched")
                 .expect("achievement responses should produce a result");
 
             assert_eq!(result.achieved, 2);
             assert_eq!(result.total, 3);
-            let rarest = result.rarest.expect("achieved item has percentage data");
+            let rarest = result
+                .rarest
+                .first()
+                .expect("achieved item has percentage data");
             assert_eq!(rarest.name, "ach_three");
             assert_eq!(rarest.game, "Game 123");
             assert!((rarest.percent - 3.0).abs() < f64::EPSILON);
+            assert_eq!(result.rarest.len(), 2, "every rated unlock is kept");
+            assert_eq!(result.rarest[1].percent, 12.5);
         }
 
         #[test]
@@ -1801,18 +2579,24 @@ mod tests {
                 steam_id: "id".into(),
                 verbose: false,
                 timeout: std::time::Duration::from_secs(3),
+                options: FetchOptions::default(),
+                concurrency: DEFAULT_CONCURRENCY,
+                base_url: DEFAULT_BASE_URL.to_string(),
+                recording: None,
+                redactor: Redactor::default(),
             };
 
             let result = run_async(client.fetch_game_achievements(321, "Game 321".to_string()))
+                .expect("player achievements should be fetched")
                 .expect("player achievement response should still produce a result");
 
             assert_eq!(result.achieved, 1);
             assert_eq!(result.total, 2);
-            assert!(result.rarest.is_none());
+            assert!(result.rarest.is_empty());
         }
 
         #[test]
-        fn test_fetch_game_achievements_returns_none_when_player_json_is_malformed() {
+        fn test_fetch_game_achievements_fails_when_player_json_is_malformed() {
             let _guard = crate::test_support::lock_env();
             let files = [
                 (
@@ -1839,10 +2623,15 @@ mod tests {
                 steam_id: "id".into(),
                 verbose: false,
                 timeout: std::time::Duration::from_secs(3),
+                options: FetchOptions::default(),
+                concurrency: DEFAULT_CONCURRENCY,
+                base_url: DEFAULT_BASE_URL.to_string(),
+                recording: None,
+                redactor: Redactor::default(),
             };
 
             let result = run_async(client.fetch_game_achievements(987, "Game 987".to_string()));
-            assert!(result.is_none());
+            assert!(result.is_err());
         }
 
         #[test]
@@ -1874,13 +2663,19 @@ mod tests {
                 steam_id: "id".into(),
                 verbose: false,
                 timeout: std::time::Duration::from_secs(3),
+                options: FetchOptions::default(),
+                concurrency: DEFAULT_CONCURRENCY,
+                base_url: DEFAULT_BASE_URL.to_string(),
+                recording: None,
+                redactor: Redactor::default(),
             };
 
             let result = run_async(client.fetch_game_achievements(988, "Game 988".to_string()))
+                .expect("player achievements should be fetched")
                 .expect("player achievements should still provide counts");
             assert_eq!(result.achieved, 1);
             assert_eq!(result.total, 2);
-            assert!(result.rarest.is_none());
+            assert!(result.rarest.is_empty());
         }
 
         #[test]
@@ -1911,13 +2706,19 @@ mod tests {
                 steam_id: "id".into(),
                 verbose: false,
                 timeout: std::time::Duration::from_secs(3),
+                options: FetchOptions::default(),
+                concurrency: DEFAULT_CONCURRENCY,
+                base_url: DEFAULT_BASE_URL.to_string(),
+                recording: None,
+                redactor: Redactor::default(),
             };
 
             let result = run_async(client.fetch_game_achievements(654, "Game 654".to_string()))
+                .expect("player achievements should be fetched")
                 .expect("empty achievement list should still produce counts");
             let counts = (result.achieved, result.total);
             let expected_counts = (0, 0);
-            let has_rarest = result.rarest.is_some();
+            let has_rarest = !result.rarest.is_empty();
 
             assert_eq!(counts, expected_counts);
             assert!(!has_rarest);
@@ -1957,6 +2758,11 @@ mod tests {
                 steam_id: "id".into(),
                 verbose: false,
                 timeout: std::time::Duration::from_secs(3),
+                options: FetchOptions::default(),
+                concurrency: DEFAULT_CONCURRENCY,
+                base_url: DEFAULT_BASE_URL.to_string(),
+                recording: None,
+                redactor: Redactor::default(),
             };
             let games = super::super::super::models::OwnedGamesData {
                 game_count: 1,
@@ -1980,24 +2786,15 @@ mod tests {
         }
 
         #[test]
-        fn test_fetch_achievement_stats_skips_game_when_player_achievements_fail() {
+        fn test_fetch_achievement_stats_counts_game_when_player_achievements_fail() {
             let _guard = crate::test_support::lock_env();
             let cache_root = super::unique_temp_root("achievement-player-fetch-fail");
             let previous_cache = std::env::var("XDG_CACHE_HOME").ok();
             std::env::set_var("XDG_CACHE_HOME", &cache_root);
 
-            let client = SteamClient {
-                client: reqwest::Client::builder()
-                    .no_proxy()
-                    .timeout(std::time::Duration::from_secs(1))
-                    .resolve("api.steampowered.com", super::unbound_localhost_addr())
-                    .build()
-                    .expect("client should build"),
-                api_key: "k".into(),
-                steam_id: "id".into(),
-                verbose: false,
-                timeout: std::time::Duration::from_secs(1),
-            };
+            let client = SteamClient::new("k".into(), "id".into())
+                .with_base_url(format!("http://{}", super::unbound_localhost_addr()))
+                .with_timeout(1);
             let games = super::super::super::models::OwnedGamesData {
                 game_count: 1,
                 games: vec![super::make_game(777, Some("Unavailable Game"), 0)],
@@ -2008,7 +2805,130 @@ mod tests {
             super::restore_xdg_cache_home(previous_cache);
             let _ = std::fs::remove_dir_all(&cache_root);
 
-            assert!(stats.is_none());
+            let stats = stats.expect("failed games are still reported");
+            assert_eq!(stats.failed_games, 1);
+            assert_eq!(stats.total_possible, 0);
+            assert!(stats.rarest.is_none());
+        }
+
+        fn mock_client(base_url: String) -> SteamClient {
+            SteamClient::new("k".into(), "id".into())
+                .with_base_url(base_url)
+                .with_timeout(3)
+        }
+
+        #[test]
+        fn test_fetch_game_achievements_treats_400_no_stats_as_no_achievements() {
+            let _guard = crate::test_support::lock_env();
+            let base_url = super::spawn_mock_steam_api(&[(
+                "/ISteamUserStats/GetPlayerAchievements/v1/?key=k&steamid=id&appid=404&l=english",
+                400,
+                r#"{"playerstats":{"error":"Requested app has no stats","success":false}}"#,
+            )]);
+
+            let result =
+                run_async(mock_client(base_url).fetch_game_achievements(404, "No Stats".into()))
+                    .expect("no stats is not a failure");
+            assert!(result.is_none());
+        }
+
+        #[test]
+        fn test_fetch_game_achievements_treats_error_body_as_no_achievements() {
+            let _guard = crate::test_support::lock_env();
+            let base_url = super::spawn_mock_steam_api(&[(
+                "/ISteamUserStats/GetPlayerAchievements/v1/?key=k&steamid=id&appid=405&l=english",
+                200,
+                r#"{"playerstats":{"error":"Requested app has no stats","success":false}}"#,
+            )]);
+
+            let result =
+                run_async(mock_client(base_url).fetch_game_achievements(405, "No Stats".into()))
+                    .expect("no stats is not a failure");
+            assert!(result.is_none());
+        }
+
+        #[test]
+        fn test_fetch_game_achievements_fails_on_other_client_errors() {
+            let _guard = crate::test_support::lock_env();
+            let base_url = super::spawn_mock_steam_api(&[(
+                "/ISteamUserStats/GetPlayerAchievements/v1/?key=k&steamid=id&appid=406&l=english",
+                400,
+                r#"{"playerstats":{"error":"Profile is not public","success":false}}"#,
+            )]);
+
+            let err =
+                run_async(mock_client(base_url).fetch_game_achievements(406, "Private".into()))
+                    .expect_err("non-stats 400 should be a failure");
+            assert!(matches!(
+                err.downcast_ref::<SteamApiError>(),
+                Some(SteamApiError::ApiError { status: 400, .. })
+            ));
+        }
+
+        #[test]
+        fn test_fetch_game_achievements_retries_server_errors_before_failing() {
+            let _guard = crate::test_support::lock_env();
+            let base_url = super::spawn_mock_steam_api(&[(
+                "/ISteamUserStats/GetPlayerAchievements/v1/?key=k&steamid=id&appid=503&l=english",
+                503,
+                "",
+            )]);
+
+            let err = run_async(mock_client(base_url).fetch_game_achievements(503, "Flaky".into()))
+                .expect_err("persistent 503 should fail");
+            assert!(matches!(
+                err.downcast_ref::<SteamApiError>(),
+                Some(SteamApiError::ApiError { status: 503, .. })
+            ));
+        }
+
+        #[test]
+        fn test_fetch_achievement_stats_reports_failures_and_caches_no_stats_games() {
+            let _guard = crate::test_support::lock_env();
+            let cache_root = super::unique_temp_root("achievement-classification");
+            let previous_cache = std::env::var("XDG_CACHE_HOME").ok();
+            std::env::set_var("XDG_CACHE_HOME", &cache_root);
+
+            let base_url = super::spawn_mock_steam_api(&[
+                (
+                    "/ISteamUserStats/GetPlayerAchievements/v1/?key=k&steamid=id&appid=1&l=english",
+                    200,
+                    r#"{"playerstats":{"achievements":[{"apiname":"A","achieved":1,"name":"A"},{"apiname":"B","achieved":0,"name":"B"}]}}"#,
+                ),
+                (
+                    "/ISteamUserStats/GetPlayerAchievements/v1/?key=k&steamid=id&appid=2&l=english",
+                    400,
+                    r#"{"playerstats":{"error":"Requested app has no stats","success":false}}"#,
+                ),
+                (
+                    "/ISteamUserStats/GetPlayerAchievements/v1/?key=k&steamid=id&appid=3&l=english",
+                    500,
+                    "",
+                ),
+            ]);
+            let games = super::super::super::models::OwnedGamesData {
+                game_count: 3,
+                games: vec![
+                    super::make_game(1, Some("Has Stats"), 0),
+                    super::make_game(2, Some("No Stats"), 0),
+                    super::make_game(3, Some("Broken"), 0),
+                ],
+            };
+
+            let stats = run_async(mock_client(base_url).fetch_achievement_stats(&games));
+            let cache = crate::cache::AchievementCache::load();
+
+            super::restore_xdg_cache_home(previous_cache);
+            let _ = std::fs::remove_dir_all(&cache_root);
+
+            let stats = stats.expect("one game has achievements");
+            assert_eq!(stats.total_achieved, 1);
+            assert_eq!(stats.total_possible, 2);
+            assert_eq!(stats.failed_games, 1);
+            assert!(cache.get(1, 0).is_some());
+            let no_stats = cache.get(2, 0).expect("no-stats game should be cached");
+            assert_eq!((no_stats.achieved, no_stats.total), (0, 0));
+            assert!(cache.get(3, 0).is_none());
         }
 
         #[cfg(target_os = "linux")]
@@ -2196,6 +3116,117 @@ mod tests {
                 });
             }
 
+            // Three games whose rarest achievements tie on percent, so the
+            // aggregated rarest only stays stable if the tie-break is
+            // independent of completion order.
+            const CRAWL_ROUTES: [(&str, u16, &str); 6] = [
+                (
+                    "/ISteamUserStats/GetPlayerAchievements/v1/?key=k&steamid=id&appid=10&l=english",
+                    200,
+                    r#"{"playerstats":{"achievements":[{"apiname":"A","achieved":1,"name":"Alpha"},{"apiname":"B","achieved":1,"name":"Beta"}]}}"#,
+                ),
+                (
+                    "/ISteamUserStats/GetGlobalAchievementPercentagesForApp/v2/?gameid=10",
+                    200,
+                    r#"{"achievementpercentages":{"achievements":[{"name":"A","percent":2.0},{"name":"B","percent":40.0}]}}"#,
+                ),
+                (
+                    "/ISteamUserStats/GetPlayerAchievements/v1/?key=k&steamid=id&appid=20&l=english",
+                    200,
+                    r#"{"playerstats":{"achievements":[{"apiname":"C","achieved":1,"name":"Gamma"},{"apiname":"D","achieved":0,"name":"Delta"}]}}"#,
+                ),
+                (
+                    "/ISteamUserStats/GetGlobalAchievementPercentagesForApp/v2/?gameid=20",
+                    200,
+                    r#"{"achievementpercentages":{"achievements":[{"name":"C","percent":2.0},{"name":"D","percent":1.0}]}}"#,
+                ),
+                (
+                    "/ISteamUserStats/GetPlayerAchievements/v1/?key=k&steamid=id&appid=30&l=english",
+                    200,
+                    r#"{"playerstats":{"achievements":[{"apiname":"E","achieved":0,"name":"Epsilon"},{"apiname":"F","achieved":0,"name":"Zeta"},{"apiname":"G","achieved":1,"name":"Eta"}]}}"#,
+                ),
+                (
+                    "/ISteamUserStats/GetGlobalAchievementPercentagesForApp/v2/?gameid=30",
+                    200,
+                    r#"{"achievementpercentages":{"achievements":[{"name":"G","percent":2.0}]}}"#,
+                ),
+            ];
+
+            fn crawl_with_concurrency(
+                base_url: &str,
+                concurrency: usize,
+            ) -> Option<AchievementStats> {
+                let client = SteamClient::new("k".into(), "id".into())
+                    .with_base_url(base_url)
+                    .with_concurrency(concurrency);
+                let games = models::OwnedGamesData {
+                    game_count: 3,
+                    games: vec![
+                        make_game(30, Some("Game C"), 3),
+                        make_game(10, Some("Game A"), 1),
+                        make_game(20, Some("Game B"), 2),
+                    ],
+                };
+                run_async(client.fetch_achievement_stats(&games))
+            }
+
+            #[test]
+            fn test_fetch_achievement_stats_concurrent_matches_sequential() {
+                let base_url = super::super::spawn_mock_steam_api(&CRAWL_ROUTES);
+                run_with_pinned_cache("concurrent", |root| {
+                    let sequential =
+                        crawl_with_concurrency(&base_url, 1).expect("mock crawl should load");
+                    // A fresh cache root forces the concurrent run over HTTP too.
+                    let _scope = EnvScope::set(&root.join("concurrent"));
+                    let concurrent =
+                        crawl_with_concurrency(&base_url, 4).expect("mock crawl should load");
+
+                    assert_eq!(
+                        serde_json::to_value(&sequential).unwrap(),
+                        serde_json::to_value(&concurrent).unwrap()
+                    );
+                    assert_eq!(concurrent.total_achieved, 4);
+                    assert_eq!(concurrent.total_possible, 7);
+                    assert_eq!(concurrent.perfect_games, 1);
+                    let rarest = concurrent.rarest.expect("every game has a rarest");
+                    assert_eq!(rarest.game, "Game A");
+                    assert_eq!(rarest.name, "Alpha");
+                    true
+                });
+            }
+
+            #[test]
+            fn test_fetch_achievement_stats_refetches_rarest_cut_shorter_than_requested() {
+                let base_url = super::super::spawn_mock_steam_api(&CRAWL_ROUTES);
+                run_with_pinned_cache("rarest-limit", |_root| {
+                    let mut cache = AchievementCache::default();
+                    cache.set_with_rarest_limit(10, 1, 2, 2, [("Cached", 2.0)], Some(1));
+                    cache.save();
+
+                    let games = models::OwnedGamesData {
+                        game_count: 1,
+                        games: vec![make_game(10, Some("Game A"), 1)],
+                    };
+                    let crawl = |rarest: usize| {
+                        let client = SteamClient::new("k".into(), "id".into())
+                            .with_base_url(base_url.as_str())
+                            .with_options(FetchOptions {
+                                rarest,
+                                ..FetchOptions::default()
+                            });
+                        run_async(client.fetch_achievement_stats(&games))
+                            .expect("game has achievements")
+                    };
+                    let names = |stats: AchievementStats| -> Vec<String> {
+                        stats.rarest_unlocks.into_iter().map(|r| r.name).collect()
+                    };
+
+                    assert_eq!(names(crawl(1)), vec!["Cached"]);
+                    assert_eq!(names(crawl(2)), vec!["Alpha", "Beta"]);
+                    true
+                });
+            }
+
             #[test]
             fn test_fetch_achievement_stats_falls_back_to_appid_for_unnamed_game() {
                 // A cached game with `name: None` exercises the
@@ -27,6 +27,9 @@ pub enum SteamApiError {
     #[error("Player not found. Please check your Steam ID.")]
     PlayerNotFound,
 
+    #[error("App {0} has no achievements.")]
+    NoAchievements(u32),
+
     #[error("Steam API error (HTTP {status}): {message}")]
     ApiError { status: u16, message: String },
 }
@@ -0,0 +1,279 @@
+use anyhow::{Context, Result};
+
+/// Known-games list used by the Native SDK to check ownership app by app.
+pub const DEFAULT_GAMES_LIST_URL: &str = "https://gib.me/sam/games.xml";
+
+/// Fetches all known Steam game AppIDs from a games.xml list (see
+/// [`DEFAULT_GAMES_LIST_URL`])
+pub async fn fetch_all_game_appids(url: &str) -> Result<Vec<u32>> {
+    let response = reqwest::get(url)
+        .await
+        .context("Failed to fetch games.xml")?
+        .text()
+        .await
+        .context("Failed to read games.xml")?;
+
+    Ok(parse_games_xml(&response))
+}
+
+fn parse_games_xml(xml: &str) -> Vec<u32> {
+    let mut appids = Vec::new();
+    let mut current_pos = 0;
+
+    while let Some(start) = xml[current_pos..].find("<game") {
+        let abs_start = current_pos + start;
+        let tag_end = abs_start + 5; // len("<game")
+
+        // Skip <games> tag - check if next char is 's' or '>'
+        if xml[tag_end..].starts_with('s') {
+            current_pos = tag_end;
+            continue;
+        }
+
+        if let Some(end_tag) = xml[abs_start..].find('>') {
+            let content_start = abs_start + end_tag + 1;
+            if let Some(close) = xml[content_start..].find("</game>") {
+                let content = &xml[content_start..content_start + close];
+                if let Ok(appid) = content.trim().parse::<u32>() {
+                    appids.push(appid);
+                }
+                current_pos = content_start + close + 7;
+                continue;
+            }
+        }
+        current_pos = abs_start + 1;
+    }
+
+    appids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROXY_VARS: &[&str] = &[
+        "HTTPS_PROXY",
+        "https_proxy",
+        "ALL_PROXY",
+        "all_proxy",
+        "NO_PROXY",
+        "no_proxy",
+    ];
+
+    struct EnvScope {
+        saved: Vec<(&'static str, Option<String>)>,
+    }
+
+    impl EnvScope {
+        fn set_unreachable_proxy(url: &str) -> Self {
+            let saved = PROXY_VARS
+                .iter()
+                .map(|&key| {
+                    let prev = std::env::var(key).ok();
+                    std::env::remove_var(key);
+                    (key, prev)
+                })
+                .collect();
+
+            std::env::set_var("HTTPS_PROXY", url);
+            std::env::set_var("https_proxy", url);
+            std::env::set_var("NO_PROXY", "127.0.0.1,localhost");
+            std::env::set_var("no_proxy", "127.0.0.1,localhost");
+
+            Self { saved }
+        }
+    }
+
+    impl Drop for EnvScope {
+        fn drop(&mut self) {
+            for (key, value) in &self.saved {
+                match value {
+                    Some(v) => std::env::set_var(key, v),
+                    None => std::env::remove_var(key),
+                }
+            }
+        }
+    }
+
+    fn run_async<F: std::future::Future>(f: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("rt")
+            .block_on(f)
+    }
+
+    #[test]
+    fn test_parse_games_xml() {
+        let xml = r#"<?xml version="1.0"?><games><game>220</game><game>240</game><game type="junk">480</game></games>"#;
+        let appids = parse_games_xml(xml);
+        assert_eq!(appids, vec![220, 240, 480]);
+    }
+
+    #[test]
+    fn test_parse_games_xml_empty_string_returns_empty() {
+        assert!(parse_games_xml("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_games_xml_no_game_tags_returns_empty() {
+        let xml = r#"<?xml version="1.0"?><root><other>123</other></root>"#;
+        assert!(parse_games_xml(xml).is_empty());
+    }
+
+    #[test]
+    fn test_parse_games_xml_skips_invalid_appid_content() {
+        let xml = r#"<games><game>not_a_number</game><game>440</game></games>"#;
+        assert_eq!(parse_games_xml(xml), vec![440]);
+    }
+
+    #[test]
+    fn test_parse_games_xml_handles_negative_number_as_invalid() {
+        // u32 cannot parse negative; the entry is dropped.
+        let xml = r#"<games><game>-7</game><game>730</game></games>"#;
+        assert_eq!(parse_games_xml(xml), vec![730]);
+    }
+
+    #[test]
+    fn test_parse_games_xml_trims_whitespace_in_content() {
+        let xml = "<games><game>  570  </game></games>";
+        assert_eq!(parse_games_xml(xml), vec![570]);
+    }
+
+    #[test]
+    fn test_parse_games_xml_handles_attribute_only_tag() {
+        let xml = r#"<games><game id="1">10</game></games>"#;
+        assert_eq!(parse_games_xml(xml), vec![10]);
+    }
+
+    #[test]
+    fn test_parse_games_xml_unclosed_game_tag_is_skipped() {
+        // No closing </game> after the opening tag — falls through to
+        // the `current_pos = abs_start + 1` recovery branch and finds nothing else.
+        let xml = "<games><game>123";
+        assert!(parse_games_xml(xml).is_empty());
+    }
+
+    #[test]
+    fn test_parse_games_xml_open_tag_without_closing_bracket_is_skipped() {
+        // "<game" appears but there's no '>' anywhere — the inner
+        // `xml[abs_start..].find('>')` returns None, recovery advances by 1.
+        let xml = "prefix <game and then nothing";
+        assert!(parse_games_xml(xml).is_empty());
+    }
+
+    #[test]
+    fn test_parse_games_xml_recovers_after_malformed_game_tag() {
+        let xml = "<games><game>broken<game>480</game><game>730</game></games>";
+        assert_eq!(parse_games_xml(xml), vec![730]);
+    }
+
+    #[test]
+    fn test_parse_games_xml_preserves_ordered_edge_cases() {
+        [
+            (
+                "<games><game>10</game><game>20</game><game>10</game></games>",
+                vec![10, 20, 10],
+            ),
+            (
+                r#"<games><game data-id="x">30</game><games><game>40</game></games>"#,
+                vec![30, 40],
+            ),
+            ("noise <game>50</game> tail <game>60</game>", vec![50, 60]),
+        ]
+        .into_iter()
+        .for_each(|(xml, expected)| assert_eq!(parse_games_xml(xml), expected));
+    }
+
+    #[test]
+    fn test_parse_games_xml_multiple_games_wrappers_handled() {
+        // Two `<games>` wrappers in sequence should both be skipped
+        // without producing spurious entries.
+        let xml = "<games></games><games><game>100</game></games>";
+        assert_eq!(parse_games_xml(xml), vec![100]);
+    }
+
+    #[test]
+    fn test_parse_games_xml_overflow_u32_is_skipped() {
+        // Larger than u32::MAX — parse fails, entry skipped.
+        let xml = "<games><game>9999999999999</game><game>20</game></games>";
+        assert_eq!(parse_games_xml(xml), vec![20]);
+    }
+
+    #[test]
+    fn test_fetch_all_game_appids_propagates_fetch_error() {
+        use crate::test_support::lock_env;
+
+        let _guard = lock_env();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind ephemeral port");
+        let addr = listener.local_addr().expect("local addr");
+        drop(listener);
+        let _scope = EnvScope::set_unreachable_proxy(&format!("http://{}", addr));
+
+        let err = run_async(fetch_all_game_appids(DEFAULT_GAMES_LIST_URL))
+            .expect_err("unreachable HTTPS proxy should make games.xml fetch fail");
+        let msg = format!("{:#}", err);
+        assert!(
+            msg.contains("Failed to fetch games.xml"),
+            "expected fetch context, got: {msg}",
+        );
+    }
+
+    #[test]
+    fn test_fetch_all_game_appids_reads_custom_url() {
+        use std::io::{Read, Write};
+
+        let _guard = crate::test_support::lock_env();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind ephemeral port");
+        let url = format!(
+            "http://{}/games.xml",
+            listener.local_addr().expect("local addr")
+        );
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept");
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf);
+            let body = "<games><game>10</game><game>570</game></games>";
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+        });
+
+        let appids = run_async(fetch_all_game_appids(&url)).expect("local games list should load");
+        let _ = server.join();
+        assert_eq!(appids, vec![10, 570]);
+    }
+
+    #[test]
+    fn test_fetch_all_game_appids_proxy_scope_restores_previous_values() {
+        use crate::test_support::lock_env;
+        use std::env;
+
+        let _guard = lock_env();
+        let original: Vec<(&'static str, Option<String>)> = PROXY_VARS
+            .iter()
+            .map(|&key| (key, env::var(key).ok()))
+            .collect();
+        env::set_var("HTTPS_PROXY", "http://proxy-sentinel.invalid:9");
+
+        {
+            let _scope = EnvScope::set_unreachable_proxy("http://127.0.0.1:9");
+            assert_eq!(env::var("HTTPS_PROXY").unwrap(), "http://127.0.0.1:9");
+        }
+
+        assert_eq!(
+            env::var("HTTPS_PROXY").unwrap(),
+            "http://proxy-sentinel.invalid:9"
+        );
+
+        for (key, value) in original {
+            match value {
+                Some(v) => env::set_var(key, v),
+                None => env::remove_var(key),
+            }
+        }
+    }
+}
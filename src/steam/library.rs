@@ -0,0 +1,256 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::local::steam_install_dirs;
+use super::models::{InstalledGame, InstalledGames, LibraryUsage};
+use super::vdf::{self, Vdf};
+
+/// Number of largest installs kept in [`InstalledGames::largest`].
+pub const LARGEST_INSTALLS: usize = 5;
+
+/// Scans every Steam library on this machine. Returns None when no Steam
+/// install is found.
+pub fn scan_installed() -> Option<InstalledGames> {
+    let steam_dirs = steam_install_dirs();
+    if steam_dirs.is_empty() {
+        return None;
+    }
+    let mut libraries: Vec<PathBuf> = Vec::new();
+    for library in steam_dirs.iter().flat_map(|dir| library_paths(dir)) {
+        if !libraries.contains(&library) {
+            libraries.push(library);
+        }
+    }
+    Some(scan_libraries(&libraries))
+}
+
+/// Reads the `appmanifest_*.acf` files of each library folder.
+pub fn scan_libraries(libraries: &[PathBuf]) -> InstalledGames {
+    let mut games: Vec<InstalledGame> = Vec::new();
+    let mut usage = Vec::new();
+
+    for library in libraries {
+        let installed: Vec<InstalledGame> = manifest_paths(library)
+            .iter()
+            .filter_map(|path| read_manifest(path))
+            .filter(|game| !games.iter().any(|seen| seen.appid == game.appid))
+            .collect();
+        usage.push(LibraryUsage {
+            path: library.display().to_string(),
+            game_count: installed.len() as u32,
+            size_bytes: installed.iter().map(|g| g.size_bytes).sum(),
+        });
+        games.extend(installed);
+    }
+
+    let total_bytes = games.iter().map(|g| g.size_bytes).sum();
+    let game_count = games.len() as u32;
+    games.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then(a.appid.cmp(&b.appid)));
+    games.truncate(LARGEST_INSTALLS);
+
+    InstalledGames {
+        game_count,
+        total_bytes,
+        largest: games,
+        libraries: usage,
+    }
+}
+
+/// The install itself plus every folder listed in `libraryfolders.vdf`.
+fn library_paths(steam_dir: &Path) -> Vec<PathBuf> {
+    let mut paths = vec![steam_dir.to_path_buf()];
+    let listed = fs::read_to_string(steam_dir.join("steamapps/libraryfolders.vdf"))
+        .ok()
+        .and_then(|text| vdf::parse(&text).ok())
+        .map(|(_, folders)| listed_library_paths(&folders))
+        .unwrap_or_default();
+    for path in listed {
+        let path = path.canonicalize().unwrap_or(path);
+        if path.is_dir() && !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Handles both the current `"0" { "path" "..." }` layout and the legacy
+/// `"1" "D:\\SteamLibrary"` one.
+fn listed_library_paths(folders: &Vdf) -> Vec<PathBuf> {
+    folders
+        .entries()
+        .iter()
+        .filter(|(key, _)| key.parse::<u32>().is_ok())
+        .filter_map(|(_, folder)| match folder {
+            Vdf::Value(path) => Some(PathBuf::from(path)),
+            Vdf::Section(_) => folder.get_str("path").map(PathBuf::from),
+        })
+        .collect()
+}
+
+fn manifest_paths(library: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(library.join("steamapps")) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("appmanifest_") && name.ends_with(".acf"))
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn read_manifest(path: &Path) -> Option<InstalledGame> {
+    let text = fs::read_to_string(path).ok()?;
+    let (_, state) = vdf::parse(&text).ok()?;
+    let appid = state.get_str("appid")?.parse().ok()?;
+    Some(InstalledGame {
+        appid,
+        name: state
+            .get_str("name")
+            .or_else(|| state.get_str("installdir"))
+            .unwrap_or_default()
+            .to_string(),
+        size_bytes: state
+            .get_str("SizeOnDisk")
+            .and_then(|size| size.parse().ok())
+            .unwrap_or(0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_path;
+
+    fn write_manifest(library: &Path, appid: u32, name: &str, size: u64) {
+        let dir = library.join("steamapps");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(format!("appmanifest_{}.acf", appid)),
+            format!(
+                "\"AppState\"\n{{\n\t\"appid\"\t\t\"{}\"\n\t\"name\"\t\t\"{}\"\n\t\"SizeOnDisk\"\t\t\"{}\"\n}}\n",
+                appid, name, size
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_scan_libraries_reports_totals_largest_and_breakdown() {
+        let root = unique_temp_path("totals");
+        let main = root.join("Steam");
+        let extra = root.join("SteamLibrary");
+        write_manifest(&main, 570, "Dota 2", 40_000_000_000);
+        write_manifest(&main, 10, "Counter-Strike", 500_000_000);
+        write_manifest(&extra, 1086940, "Baldur's Gate 3", 150_000_000_000);
+        fs::write(main.join("steamapps/appmanifest_1.acf"), "not a manifest").unwrap();
+        fs::write(main.join("steamapps/libraryfolders.vdf"), "ignored").unwrap();
+
+        let installed = scan_libraries(&[main.clone(), extra.clone()]);
+        assert_eq!(installed.game_count, 3);
+        assert_eq!(installed.total_bytes, 190_500_000_000);
+        let names: Vec<&str> = installed.largest.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, vec!["Baldur's Gate 3", "Dota 2", "Counter-Strike"]);
+
+        assert_eq!(installed.libraries.len(), 2);
+        assert_eq!(installed.libraries[0].path, main.display().to_string());
+        assert_eq!(installed.libraries[0].game_count, 2);
+        assert_eq!(installed.libraries[0].size_bytes, 40_500_000_000);
+        assert_eq!(installed.libraries[1].game_count, 1);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_scan_libraries_caps_largest_and_skips_duplicate_appids() {
+        let root = unique_temp_path("cap");
+        let main = root.join("a");
+        let other = root.join("b");
+        for appid in 1..=7 {
+            write_manifest(&main, appid, &format!("Game {}", appid), appid as u64);
+        }
+        write_manifest(&other, 7, "Game 7 copy", 1_000);
+
+        let installed = scan_libraries(&[main, other]);
+        assert_eq!(installed.game_count, 7);
+        assert_eq!(installed.largest.len(), LARGEST_INSTALLS);
+        assert_eq!(installed.largest[0].appid, 7);
+        assert_eq!(installed.libraries[1].game_count, 0);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_scan_libraries_missing_folder_is_empty() {
+        let installed = scan_libraries(&[unique_temp_path("missing")]);
+        assert_eq!(installed.game_count, 0);
+        assert_eq!(installed.total_bytes, 0);
+        assert!(installed.largest.is_empty());
+        assert_eq!(installed.libraries[0].game_count, 0);
+    }
+
+    #[test]
+    fn test_library_paths_reads_libraryfolders_vdf() {
+        let root = unique_temp_path("folders");
+        let steam = root.join("Steam");
+        let extra = root.join("Games");
+        let legacy = root.join("Legacy");
+        fs::create_dir_all(steam.join("steamapps")).unwrap();
+        fs::create_dir_all(&extra).unwrap();
+        fs::create_dir_all(&legacy).unwrap();
+        let quoted = |p: &Path| p.display().to_string().replace('\\', "\\\\");
+        fs::write(
+            steam.join("steamapps/libraryfolders.vdf"),
+            format!(
+                r#""libraryfolders"
+{{
+	"0" {{ "path" "{}" "apps" {{ "570" "1" }} }}
+	"1" {{ "path" "{}" }}
+	"2" "{}"
+	"3" {{ "path" "{}" }}
+	"contentstatsid" "123"
+}}"#,
+                quoted(&steam),
+                quoted(&extra),
+                quoted(&legacy),
+                quoted(&root.join("gone")),
+            ),
+        )
+        .unwrap();
+
+        let paths = library_paths(&steam);
+        assert_eq!(
+            paths,
+            vec![
+                steam.clone(),
+                extra.canonicalize().unwrap(),
+                legacy.canonicalize().unwrap()
+            ]
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_read_manifest_falls_back_to_installdir() {
+        let root = unique_temp_path("manifest");
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join("appmanifest_42.acf");
+        fs::write(
+            &path,
+            r#""AppState" { "appid" "42" "installdir" "Example" }"#,
+        )
+        .unwrap();
+
+        let game = read_manifest(&path).unwrap();
+        assert_eq!(game.appid, 42);
+        assert_eq!(game.name, "Example");
+        assert_eq!(game.size_bytes, 0);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,343 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::vdf::{self, Vdf};
+
+/// The Steam account last logged in on this machine, read from the Steam
+/// install's `config/loginusers.vdf` (or `config/config.vdf`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalAccount {
+    pub steam_id: String,
+    pub account_name: String,
+    pub persona_name: Option<String>,
+}
+
+/// Detects the locally logged-in account without a running Steam client.
+pub fn detect_account() -> Option<LocalAccount> {
+    steam_install_dirs().iter().find_map(|dir| account_in(dir))
+}
+
+/// Steam install directories that exist on this machine, most likely first.
+pub fn steam_install_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for dir in steam_install_roots() {
+        let resolved = dir.canonicalize().unwrap_or(dir);
+        if resolved.is_dir() && !dirs.contains(&resolved) {
+            dirs.push(resolved);
+        }
+    }
+    dirs
+}
+
+/// Where Steam installs itself on this platform, most likely first. The
+/// native client looks for its library under the same roots.
+#[cfg(target_os = "linux")]
+pub(crate) fn steam_install_roots() -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return Vec::new();
+    };
+    vec![home.join(".steam/steam"), home.join(".local/share/Steam")]
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn steam_install_roots() -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return Vec::new();
+    };
+    vec![home.join("Library/Application Support/Steam")]
+}
+
+#[cfg(target_os = "windows")]
+pub(crate) fn steam_install_roots() -> Vec<PathBuf> {
+    steam_path_from_registry()
+        .map(PathBuf::from)
+        .into_iter()
+        .chain([
+            PathBuf::from("C:\\Program Files (x86)\\Steam"),
+            PathBuf::from("C:\\Program Files\\Steam"),
+        ])
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+pub(crate) fn steam_install_roots() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+#[cfg(target_os = "windows")]
+fn steam_path_from_registry() -> Option<String> {
+    use std::process::Command;
+
+    let output = Command::new("reg")
+        .args(["query", "HKCU\\Software\\Valve\\Steam", "/v", "SteamPath"])
+        .output()
+        .ok()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines() {
+        if line.contains("SteamPath") && line.contains("REG_SZ") {
+            // Format: "    SteamPath    REG_SZ    C:/Program Files (x86)/Steam"
+            if let Some(idx) = line.find("REG_SZ") {
+                let path = line[idx + 6..].trim();
+                // Convert forward slashes to backslashes
+                return Some(path.replace('/', "\\"));
+            }
+        }
+    }
+    None
+}
+
+fn account_in(steam_dir: &Path) -> Option<LocalAccount> {
+    let config_dir = steam_dir.join("config");
+    let from_loginusers =
+        || read_vdf(&config_dir.join("loginusers.vdf")).and_then(|v| account_from_loginusers(&v));
+    let from_config =
+        || read_vdf(&config_dir.join("config.vdf")).and_then(|v| account_from_config(&v));
+    from_loginusers().or_else(from_config)
+}
+
+fn read_vdf(path: &Path) -> Option<Vdf> {
+    let text = fs::read_to_string(path).ok()?;
+    vdf::parse(&text).ok().map(|(_, root)| root)
+}
+
+/// Picks the `MostRecent` user, then one allowed to auto-login, then the
+/// latest `Timestamp`.
+fn account_from_loginusers(users: &Vdf) -> Option<LocalAccount> {
+    let candidates: Vec<(&str, &Vdf)> = users
+        .entries()
+        .iter()
+        .filter(|(id, user)| is_steam_id(id) && matches!(user, Vdf::Section(_)))
+        .map(|(id, user)| (id.as_str(), user))
+        .collect();
+
+    let flagged = |flag: &str| {
+        candidates
+            .iter()
+            .find(|(_, user)| user.get_str(flag) == Some("1"))
+    };
+    let (steam_id, user) = flagged("MostRecent")
+        .or_else(|| flagged("AllowAutoLogin"))
+        .or_else(|| {
+            candidates.iter().max_by_key(|(_, user)| {
+                user.get_str("Timestamp")
+                    .and_then(|t| t.parse::<u64>().ok())
+                    .unwrap_or(0)
+            })
+        })?;
+
+    Some(LocalAccount {
+        steam_id: steam_id.to_string(),
+        account_name: user.get_str("AccountName").unwrap_or_default().to_string(),
+        persona_name: user.get_str("PersonaName").map(str::to_string),
+    })
+}
+
+/// Falls back to the `Accounts` list of `config.vdf`, which has no
+/// "most recent" marker, so only a single account is unambiguous.
+fn account_from_config(config: &Vdf) -> Option<LocalAccount> {
+    let accounts = config.get_path(&["Software", "Valve", "Steam", "Accounts"])?;
+    let mut found = accounts.entries().iter().filter_map(|(name, account)| {
+        let steam_id = account.get_str("SteamID").filter(|id| is_steam_id(id))?;
+        Some(LocalAccount {
+            steam_id: steam_id.to_string(),
+            account_name: name.clone(),
+            persona_name: None,
+        })
+    });
+    let account = found.next()?;
+    found.next().is_none().then_some(account)
+}
+
+fn is_steam_id(value: &str) -> bool {
+    value.len() == 17 && value.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Vdf {
+        vdf::parse(text).unwrap().1
+    }
+
+    const CONFIG_VDF: &str = r#""InstallConfigStore"
+{
+	"Software"
+	{
+		"Valve"
+		{
+			"Steam"
+			{
+				"Accounts"
+				{
+					"gabelogannewell"
+					{
+						"SteamID"		"76561197960287930"
+					}
+				}
+			}
+		}
+	}
+}"#;
+
+    #[test]
+    fn test_loginusers_prefers_most_recent_user() {
+        let users = parse(
+            r#""users" {
+  "76561197960265728" { "AccountName" "old" "Timestamp" "1800000000" }
+  "76561197960287930" { "AccountName" "gaben" "PersonaName" "Rabscuttle" "MostRecent" "1" }
+}"#,
+        );
+        assert_eq!(
+            account_from_loginusers(&users),
+            Some(LocalAccount {
+                steam_id: "76561197960287930".to_string(),
+                account_name: "gaben".to_string(),
+                persona_name: Some("Rabscuttle".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_loginusers_falls_back_to_auto_login_then_timestamp() {
+        let auto = parse(
+            r#""users" {
+  "76561197960265728" { "Timestamp" "1800000000" }
+  "76561197960287930" { "AllowAutoLogin" "1" "Timestamp" "1" }
+}"#,
+        );
+        let account = account_from_loginusers(&auto).unwrap();
+        assert_eq!(account.steam_id, "76561197960287930");
+        assert_eq!(account.account_name, "");
+        assert_eq!(account.persona_name, None);
+
+        let latest = parse(
+            r#""users" {
+  "76561197960265728" { "Timestamp" "1800000000" }
+  "76561197960287930" { "Timestamp" "1700000000" }
+}"#,
+        );
+        assert_eq!(
+            account_from_loginusers(&latest).unwrap().steam_id,
+            "76561197960265728"
+        );
+    }
+
+    #[test]
+    fn test_loginusers_ignores_non_steam_id_entries() {
+        let users = parse(r#""users" { "123" { "MostRecent" "1" } "76561197960287930" "x" }"#);
+        assert!(account_from_loginusers(&users).is_none());
+    }
+
+    #[test]
+    fn test_config_vdf_single_account() {
+        let account = account_from_config(&parse(CONFIG_VDF)).unwrap();
+        assert_eq!(account.steam_id, "76561197960287930");
+        assert_eq!(account.account_name, "gabelogannewell");
+    }
+
+    #[test]
+    fn test_config_vdf_multiple_accounts_is_ambiguous() {
+        let text = CONFIG_VDF.replace(
+            "\"gabelogannewell\"",
+            "\"second\" { \"SteamID\" \"76561197960265728\" }\n\"gabelogannewell\"",
+        );
+        assert!(account_from_config(&parse(&text)).is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    mod install_dir_tests {
+        use super::super::*;
+        use super::CONFIG_VDF;
+        use crate::test_support::{lock_env, unique_temp_path};
+        use std::env;
+
+        struct HomeScope {
+            prev: Option<String>,
+        }
+
+        impl HomeScope {
+            fn set(home: &Path) -> Self {
+                let prev = env::var("HOME").ok();
+                env::set_var("HOME", home);
+                Self { prev }
+            }
+        }
+
+        impl Drop for HomeScope {
+            fn drop(&mut self) {
+                match &self.prev {
+                    Some(v) => env::set_var("HOME", v),
+                    None => env::remove_var("HOME"),
+                }
+            }
+        }
+
+        fn write(path: &Path, content: &str) {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        #[test]
+        fn test_detect_account_reads_loginusers_from_home() {
+            let _guard = lock_env();
+            let home = unique_temp_path("loginusers");
+            let _scope = HomeScope::set(&home);
+            write(
+                &home.join(".local/share/Steam/config/loginusers.vdf"),
+                r#""users" { "76561197960287930" { "AccountName" "gaben" "MostRecent" "1" } }"#,
+            );
+
+            let account = detect_account().expect("account should be detected");
+            assert_eq!(account.steam_id, "76561197960287930");
+            assert_eq!(account.account_name, "gaben");
+
+            let _ = fs::remove_dir_all(&home);
+        }
+
+        #[test]
+        fn test_detect_account_falls_back_to_config_vdf() {
+            let _guard = lock_env();
+            let home = unique_temp_path("config");
+            let _scope = HomeScope::set(&home);
+            let steam = home.join(".steam/steam");
+            write(&steam.join("config/loginusers.vdf"), "not vdf {");
+            write(&steam.join("config/config.vdf"), CONFIG_VDF);
+
+            let account = detect_account().expect("config.vdf account");
+            assert_eq!(account.account_name, "gabelogannewell");
+            assert_eq!(steam_install_dirs(), vec![steam.canonicalize().unwrap()]);
+
+            let _ = fs::remove_dir_all(&home);
+        }
+
+        #[test]
+        fn test_detect_account_none_without_steam_install() {
+            let _guard = lock_env();
+            let home = unique_temp_path("empty");
+            fs::create_dir_all(&home).unwrap();
+            let _scope = HomeScope::set(&home);
+
+            assert!(steam_install_dirs().is_empty());
+            assert!(detect_account().is_none());
+
+            let _ = fs::remove_dir_all(&home);
+        }
+
+        #[test]
+        fn test_steam_install_dirs_dedupes_symlinked_root() {
+            let _guard = lock_env();
+            let home = unique_temp_path("symlink");
+            let _scope = HomeScope::set(&home);
+            let real = home.join(".local/share/Steam");
+            fs::create_dir_all(&real).unwrap();
+            fs::create_dir_all(home.join(".steam")).unwrap();
+            std::os::unix::fs::symlink(&real, home.join(".steam/steam")).unwrap();
+
+            assert_eq!(steam_install_dirs(), vec![real.canonicalize().unwrap()]);
+
+            let _ = fs::remove_dir_all(&home);
+        }
+    }
+}
@@ -1,8 +1,21 @@
 mod client;
 pub mod error;
+pub mod games_list;
+pub mod library;
+pub mod local;
 mod models;
+#[cfg(feature = "native")]
 pub mod native;
+pub mod recording;
+pub mod redact;
+mod vdf;
 
-pub use client::SteamClient;
-pub use models::{AchievementStats, GameStat, RarestAchievement, SteamStats};
+pub use client::{FetchOptions, SteamClient, DEFAULT_BASE_URL};
+pub use models::{
+    AchievementDetail, AchievementStats, Game, GameAchievements, GameCompletion, GameStat,
+    HistoryDelta, InstalledGame, InstalledGames, LibraryUsage, OwnedGamesData, PlaytimeBucket,
+    RarestAchievement, SteamStats,
+};
+#[cfg(feature = "native")]
 pub use native::NativeSteamClient;
+pub use recording::Recording;
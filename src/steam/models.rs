@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 // Owned Games API
 #[derive(Debug, Deserialize)]
@@ -13,7 +13,7 @@ pub struct OwnedGamesData {
     pub games: Vec<Game>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Game {
     pub appid: u32,
     pub name: Option<String>,
@@ -74,6 +74,10 @@ pub struct AchievementsResponse {
 pub struct AchievementsData {
     #[serde(default)]
     pub achievements: Vec<Achievement>,
+    /// Set instead of `achievements` when Steam rejects the request, e.g.
+    /// "Requested app has no stats".
+    #[serde(default)]
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -81,6 +85,43 @@ pub struct Achievement {
     pub apiname: String,
     pub achieved: u8,
     pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Unix timestamp; 0 while locked.
+    #[serde(default)]
+    pub unlocktime: u64,
+}
+
+// Game Schema API
+#[derive(Debug, Deserialize)]
+pub struct GameSchemaResponse {
+    #[serde(default)]
+    pub game: GameSchema,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct GameSchema {
+    #[serde(rename = "gameName")]
+    pub game_name: Option<String>,
+    #[serde(rename = "availableGameStats", default)]
+    pub available_game_stats: AvailableGameStats,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct AvailableGameStats {
+    #[serde(default)]
+    pub achievements: Vec<SchemaAchievement>,
+}
+
+/// Schema entries carry names and descriptions even for hidden achievements.
+#[derive(Debug, Deserialize)]
+pub struct SchemaAchievement {
+    pub name: String,
+    #[serde(rename = "displayName")]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub hidden: u8,
+    pub description: Option<String>,
 }
 
 // Global Achievement Percentages API
@@ -130,7 +171,8 @@ where
 }
 
 // Aggregated Stats
-#[derive(Debug)]
+/// Everything steamfetch displays for one account.
+#[derive(Debug, Serialize)]
 pub struct SteamStats {
     pub username: String,
     pub game_count: u32,
@@ -142,24 +184,181 @@ pub struct SteamStats {
     pub steam_level: Option<u32>,
     pub recently_played: Vec<GameStat>,
     pub avatar_url: Option<String>,
+    /// Games installed in the local Steam libraries, if Steam is installed.
+    pub installed: Option<InstalledGames>,
+    /// Changes since the previous run, from the local history.
+    pub history: Option<HistoryDelta>,
+    /// Owned games per playtime range, shortest first.
+    pub playtime_distribution: Vec<PlaytimeBucket>,
+    /// Every owned game; kept for history snapshots rather than output.
+    #[serde(skip)]
+    pub games: Vec<Game>,
+}
+
+/// Differences between this run and earlier history snapshots.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryDelta {
+    /// Unix timestamp of the snapshot compared against.
+    pub since: u64,
+    pub playtime_minutes: u32,
+    /// None when either run could not count every achievement.
+    pub achievements: Option<i64>,
+    pub games: i64,
+    pub steam_level: Option<i64>,
+    /// Playtime gained over the last 7 days (or since history began).
+    pub week_playtime_minutes: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct AchievementStats {
     pub total_achieved: u32,
     pub total_possible: u32,
     pub perfect_games: u32,
     pub rarest: Option<RarestAchievement>,
+    /// The rarest unlocked achievements across all games, rarest first.
+    pub rarest_unlocks: Vec<RarestAchievement>,
+    /// Games whose achievements could not be fetched and are missing from the totals.
+    pub failed_games: u32,
+    /// Started but unfinished games, fewest remaining achievements first.
+    pub near_complete: Vec<GameCompletion>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GameCompletion {
+    pub appid: u32,
+    pub name: String,
+    pub achieved: u32,
+    pub total: u32,
 }
 
-#[derive(Debug)]
+impl GameCompletion {
+    pub fn remaining(&self) -> u32 {
+        self.total.saturating_sub(self.achieved)
+    }
+
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.achieved as f64 / self.total as f64 * 100.0
+        }
+    }
+}
+
+/// Number of games whose playtime is in `[min_minutes, max_minutes)`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlaytimeBucket {
+    pub label: String,
+    pub min_minutes: u32,
+    /// None for the open-ended last bucket.
+    pub max_minutes: Option<u32>,
+    pub game_count: u32,
+}
+
+/// Upper bounds of the playtime buckets, in hours; `0` means never played.
+const BUCKET_HOURS: [u32; 5] = [0, 1, 10, 50, 100];
+
+impl PlaytimeBucket {
+    /// Sorts `games` into 0h, <1h, 1-10h, 10-50h, 50-100h and 100h+.
+    pub fn distribution(games: &[Game]) -> Vec<Self> {
+        let mut buckets: Vec<Self> = BUCKET_HOURS
+            .iter()
+            .enumerate()
+            .map(|(i, &hours)| {
+                let (min_minutes, label) = match i {
+                    0 => (0, "0h".to_string()),
+                    1 => (1, "<1h".to_string()),
+                    _ => (
+                        BUCKET_HOURS[i - 1] * 60,
+                        format!("{}-{}h", BUCKET_HOURS[i - 1], hours),
+                    ),
+                };
+                Self {
+                    label,
+                    min_minutes,
+                    max_minutes: Some((hours * 60).max(1)),
+                    game_count: 0,
+                }
+            })
+            .collect();
+        let last = BUCKET_HOURS[BUCKET_HOURS.len() - 1];
+        buckets.push(Self {
+            label: format!("{}h+", last),
+            min_minutes: last * 60,
+            max_minutes: None,
+            game_count: 0,
+        });
+
+        for game in games {
+            if let Some(bucket) = buckets
+                .iter_mut()
+                .find(|b| b.max_minutes.is_none_or(|max| game.playtime_forever < max))
+            {
+                bucket.game_count += 1;
+            }
+        }
+        buckets
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct RarestAchievement {
     pub name: String,
     pub game: String,
     pub percent: f64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+pub struct InstalledGames {
+    pub game_count: u32,
+    pub total_bytes: u64,
+    /// Biggest installs first.
+    pub largest: Vec<InstalledGame>,
+    pub libraries: Vec<LibraryUsage>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InstalledGame {
+    pub appid: u32,
+    pub name: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LibraryUsage {
+    pub path: String,
+    pub game_count: u32,
+    pub size_bytes: u64,
+}
+
+/// Every achievement of one game, rarest first.
+#[derive(Debug, Serialize)]
+pub struct GameAchievements {
+    pub appid: u32,
+    pub game: String,
+    pub achievements: Vec<AchievementDetail>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AchievementDetail {
+    pub api_name: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub hidden: bool,
+    pub achieved: bool,
+    /// Unix timestamp of the unlock.
+    pub unlock_time: Option<u64>,
+    /// Share of all players who unlocked it, if Steam reports one.
+    pub global_percent: Option<f64>,
+}
+
+impl GameAchievements {
+    pub fn unlocked_count(&self) -> usize {
+        self.achievements.iter().filter(|a| a.achieved).count()
+    }
+}
+
+#[derive(Debug, Serialize)]
 pub struct GameStat {
     pub name: String,
     pub playtime_minutes: u32,
@@ -193,6 +392,10 @@ mod tests {
             steam_level: None,
             recently_played: Vec::new(),
             avatar_url: None,
+            installed: None,
+            history: None,
+            playtime_distribution: Vec::new(),
+            games: Vec::new(),
         }
     }
 
@@ -484,4 +687,42 @@ mod tests {
         let result: Result<GlobalAchievementsResponse, _> = serde_json::from_slice(bytes);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_playtime_distribution_buckets_games_by_hours() {
+        let game = |playtime_forever| Game {
+            appid: 1,
+            name: None,
+            playtime_forever,
+            playtime_2weeks: 0,
+            rtime_last_played: 0,
+        };
+        let games: Vec<Game> = [0, 0, 1, 59, 60, 599, 600, 3000, 5999, 6000, 100_000]
+            .into_iter()
+            .map(game)
+            .collect();
+        let buckets = PlaytimeBucket::distribution(&games);
+
+        let summary: Vec<(&str, u32)> = buckets
+            .iter()
+            .map(|b| (b.label.as_str(), b.game_count))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("0h", 2),
+                ("<1h", 2),
+                ("1-10h", 2),
+                ("10-50h", 1),
+                ("50-100h", 2),
+                ("100h+", 2),
+            ]
+        );
+        assert_eq!(buckets[2].min_minutes, 60);
+        assert_eq!(buckets[2].max_minutes, Some(600));
+        assert_eq!(buckets[5].max_minutes, None);
+        assert!(PlaytimeBucket::distribution(&[])
+            .iter()
+            .all(|b| b.game_count == 0));
+    }
 }
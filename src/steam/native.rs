@@ -1,4 +1,3 @@
-use anyhow::{Context, Result};
 use libloading::{Library, Symbol};
 use std::ffi::CString;
 use std::os::raw::{c_char, c_void};
@@ -9,67 +8,33 @@ type CreateInterfaceFn = unsafe extern "C" fn(*const c_char, *mut i32) -> *mut c
 #[cfg(target_os = "linux")]
 fn get_steam_client_path() -> Option<String> {
     let home = std::env::var("HOME").ok()?;
-    let paths = [
-        format!("{}/.steam/sdk64/steamclient.so", home),
-        format!("{}/.steam/steam/linux64/steamclient.so", home),
-        format!("{}/.local/share/Steam/linux64/steamclient.so", home),
-    ];
-    paths.into_iter().find(|p| std::path::Path::new(p).exists())
+    let sdk = std::path::PathBuf::from(format!("{}/.steam/sdk64/steamclient.so", home));
+    std::iter::once(sdk)
+        .chain(client_paths_under_roots("linux64/steamclient.so"))
+        .find(|p| p.exists())
+        .map(|p| p.to_string_lossy().into_owned())
 }
 
 #[cfg(target_os = "windows")]
 fn get_steam_client_path() -> Option<String> {
-    let paths = [
-        "C:\\Program Files (x86)\\Steam\\steamclient64.dll".to_string(),
-        "C:\\Program Files\\Steam\\steamclient64.dll".to_string(),
-    ];
-
-    // Try registry first
-    if let Some(path) = get_steam_path_from_registry() {
-        let client_path = format!("{}\\steamclient64.dll", path);
-        if std::path::Path::new(&client_path).exists() {
-            return Some(client_path);
-        }
-    }
-
-    paths.into_iter().find(|p| std::path::Path::new(p).exists())
-}
-
-#[cfg(target_os = "windows")]
-fn get_steam_path_from_registry() -> Option<String> {
-    use std::process::Command;
-
-    let output = Command::new("reg")
-        .args(["query", "HKCU\\Software\\Valve\\Steam", "/v", "SteamPath"])
-        .output()
-        .ok()?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    for line in stdout.lines() {
-        if line.contains("SteamPath") && line.contains("REG_SZ") {
-            // Format: "    SteamPath    REG_SZ    C:/Program Files (x86)/Steam"
-            if let Some(idx) = line.find("REG_SZ") {
-                let path = line[idx + 6..].trim();
-                // Convert forward slashes to backslashes
-                return Some(path.replace('/', "\\"));
-            }
-        }
-    }
-    None
+    client_paths_under_roots("steamclient64.dll")
+        .find(|p| p.exists())
+        .map(|p| p.to_string_lossy().into_owned())
 }
 
 #[cfg(target_os = "macos")]
 fn get_steam_client_path() -> Option<String> {
-    let home = std::env::var("HOME").ok()?;
-    let path = format!(
-        "{}/Library/Application Support/Steam/Steam.AppBundle/Steam/Contents/MacOS/steamclient.dylib",
-        home
-    );
-    if std::path::Path::new(&path).exists() {
-        Some(path)
-    } else {
-        None
-    }
+    client_paths_under_roots("Steam.AppBundle/Steam/Contents/MacOS/steamclient.dylib")
+        .find(|p| p.exists())
+        .map(|p| p.to_string_lossy().into_owned())
+}
+
+/// `library` inside each Steam install root, in probing order.
+#[cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))]
+fn client_paths_under_roots(library: &str) -> impl Iterator<Item = std::path::PathBuf> + '_ {
+    super::local::steam_install_roots()
+        .into_iter()
+        .map(move |root| root.join(library))
 }
 
 #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
@@ -277,252 +242,8 @@ impl Drop for NativeSteamClient {
     }
 }
 
-/// Fetches all known Steam game AppIDs from gib.me/sam/games.xml
-pub async fn fetch_all_game_appids() -> Result<Vec<u32>> {
-    let url = "https://gib.me/sam/games.xml";
-    let response = reqwest::get(url)
-        .await
-        .context("Failed to fetch games.xml")?
-        .text()
-        .await
-        .context("Failed to read games.xml")?;
-
-    Ok(parse_games_xml(&response))
-}
-
-fn parse_games_xml(xml: &str) -> Vec<u32> {
-    let mut appids = Vec::new();
-    let mut current_pos = 0;
-
-    while let Some(start) = xml[current_pos..].find("<game") {
-        let abs_start = current_pos + start;
-        let tag_end = abs_start + 5; // len("<game")
-
-        // Skip <games> tag - check if next char is 's' or '>'
-        if xml[tag_end..].starts_with('s') {
-            current_pos = tag_end;
-            continue;
-        }
-
-        if let Some(end_tag) = xml[abs_start..].find('>') {
-            let content_start = abs_start + end_tag + 1;
-            if let Some(close) = xml[content_start..].find("</game>") {
-                let content = &xml[content_start..content_start + close];
-                if let Ok(appid) = content.trim().parse::<u32>() {
-                    appids.push(appid);
-                }
-                current_pos = content_start + close + 7;
-                continue;
-            }
-        }
-        current_pos = abs_start + 1;
-    }
-
-    appids
-}
-
 #[cfg(test)]
 mod tests {
-    use super::*;
-
-    const PROXY_VARS: &[&str] = &[
-        "HTTPS_PROXY",
-        "https_proxy",
-        "ALL_PROXY",
-        "all_proxy",
-        "NO_PROXY",
-        "no_proxy",
-    ];
-
-    struct EnvScope {
-        saved: Vec<(&'static str, Option<String>)>,
-    }
-
-    impl EnvScope {
-        fn set_unreachable_proxy(url: &str) -> Self {
-            let saved = PROXY_VARS
-                .iter()
-                .map(|&key| {
-                    let prev = std::env::var(key).ok();
-                    std::env::remove_var(key);
-                    (key, prev)
-                })
-                .collect();
-
-            std::env::set_var("HTTPS_PROXY", url);
-            std::env::set_var("https_proxy", url);
-            std::env::set_var("NO_PROXY", "127.0.0.1,localhost");
-            std::env::set_var("no_proxy", "127.0.0.1,localhost");
-
-            Self { saved }
-        }
-    }
-
-    impl Drop for EnvScope {
-        fn drop(&mut self) {
-            for (key, value) in &self.saved {
-                match value {
-                    Some(v) => std::env::set_var(key, v),
-                    None => std::env::remove_var(key),
-                }
-            }
-        }
-    }
-
-    fn run_async<F: std::future::Future>(f: F) -> F::Output {
-        tokio::runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .expect("rt")
-            .block_on(f)
-    }
-
-    #[test]
-    fn test_parse_games_xml() {
-        let xml = r#"<?xml version="1.0"?><games><game>220</game><game>240</game><game type="junk">480</game></games>"#;
-        let appids = parse_games_xml(xml);
-        assert_eq!(appids, vec![220, 240, 480]);
-    }
-
-    #[test]
-    fn test_parse_games_xml_empty_string_returns_empty() {
-        assert!(parse_games_xml("").is_empty());
-    }
-
-    #[test]
-    fn test_parse_games_xml_no_game_tags_returns_empty() {
-        let xml = r#"<?xml version="1.0"?><root><other>123</other></root>"#;
-        assert!(parse_games_xml(xml).is_empty());
-    }
-
-    #[test]
-    fn test_parse_games_xml_skips_invalid_appid_content() {
-        let xml = r#"<games><game>not_a_number</game><game>440</game></games>"#;
-        assert_eq!(parse_games_xml(xml), vec![440]);
-    }
-
-    #[test]
-    fn test_parse_games_xml_handles_negative_number_as_invalid() {
-        // u32 cannot parse negative; the entry is dropped.
-        let xml = r#"<games><game>-7</game><game>730</game></games>"#;
-        assert_eq!(parse_games_xml(xml), vec![730]);
-    }
-
-    #[test]
-    fn test_parse_games_xml_trims_whitespace_in_content() {
-        let xml = "<games><game>  570  </game></games>";
-        assert_eq!(parse_games_xml(xml), vec![570]);
-    }
-
-    #[test]
-    fn test_parse_games_xml_handles_attribute_only_tag() {
-        let xml = r#"<games><game id="1">10</game></games>"#;
-        assert_eq!(parse_games_xml(xml), vec![10]);
-    }
-
-    #[test]
-    fn test_parse_games_xml_unclosed_game_tag_is_skipped() {
-        // No closing </game> after the opening tag — falls through to
-        // the `current_pos = abs_start + 1` recovery branch and finds nothing else.
-        let xml = "<games><game>123";
-        assert!(parse_games_xml(xml).is_empty());
-    }
-
-    #[test]
-    fn test_parse_games_xml_open_tag_without_closing_bracket_is_skipped() {
-        // "<game" appears but there's no '>' anywhere — the inner
-        // `xml[abs_start..].find('>')` returns None, recovery advances by 1.
-        let xml = "prefix <game and then nothing";
-        assert!(parse_games_xml(xml).is_empty());
-    }
-
-    #[test]
-    fn test_parse_games_xml_recovers_after_malformed_game_tag() {
-        let xml = "<games><game>broken<game>480</game><game>730</game></games>";
-        assert_eq!(parse_games_xml(xml), vec![730]);
-    }
-
-    #[test]
-    fn test_parse_games_xml_preserves_ordered_edge_cases() {
-        [
-            (
-                "<games><game>10</game><game>20</game><game>10</game></games>",
-                vec![10, 20, 10],
-            ),
-            (
-                r#"<games><game data-id="x">30</game><games><game>40</game></games>"#,
-                vec![30, 40],
-            ),
-            ("noise <game>50</game> tail <game>60</game>", vec![50, 60]),
-        ]
-        .into_iter()
-        .for_each(|(xml, expected)| assert_eq!(parse_games_xml(xml), expected));
-    }
-
-    #[test]
-    fn test_parse_games_xml_multiple_games_wrappers_handled() {
-        // Two `<games>` wrappers in sequence should both be skipped
-        // without producing spurious entries.
-        let xml = "<games></games><games><game>100</game></games>";
-        assert_eq!(parse_games_xml(xml), vec![100]);
-    }
-
-    #[test]
-    fn test_parse_games_xml_overflow_u32_is_skipped() {
-        // Larger than u32::MAX — parse fails, entry skipped.
-        let xml = "<games><game>9999999999999</game><game>20</game></games>";
-        assert_eq!(parse_games_xml(xml), vec![20]);
-    }
-
-    #[test]
-    fn test_fetch_all_game_appids_propagates_fetch_error() {
-        use crate::test_support::lock_env;
-
-        let _guard = lock_env();
-        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind ephemeral port");
-        let addr = listener.local_addr().expect("local addr");
-        drop(listener);
-        let _scope = EnvScope::set_unreachable_proxy(&format!("http://{}", addr));
-
-        let err = run_async(fetch_all_game_appids())
-            .expect_err("unreachable HTTPS proxy should make games.xml fetch fail");
-        let msg = format!("{:#}", err);
-        assert!(
-            msg.contains("Failed to fetch games.xml"),
-            "expected fetch context, got: {msg}",
-        );
-    }
-
-    #[test]
-    fn test_fetch_all_game_appids_proxy_scope_restores_previous_values() {
-        use crate::test_support::lock_env;
-        use std::env;
-
-        let _guard = lock_env();
-        let original: Vec<(&'static str, Option<String>)> = PROXY_VARS
-            .iter()
-            .map(|&key| (key, env::var(key).ok()))
-            .collect();
-        env::set_var("HTTPS_PROXY", "http://proxy-sentinel.invalid:9");
-
-        {
-            let _scope = EnvScope::set_unreachable_proxy("http://127.0.0.1:9");
-            assert_eq!(env::var("HTTPS_PROXY").unwrap(), "http://127.0.0.1:9");
-        }
-
-        assert_eq!(
-            env::var("HTTPS_PROXY").unwrap(),
-            "http://proxy-sentinel.invalid:9"
-        );
-
-        for (key, value) in original {
-            match value {
-                Some(v) => env::set_var(key, v),
-                None => env::remove_var(key),
-            }
-        }
-    }
-
     #[cfg(target_os = "linux")]
     mod steam_client_path_tests {
         use super::super::*;
@@ -0,0 +1,311 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::error::SteamApiError;
+use super::redact::Redactor;
+
+/// Record raw API responses to a directory, or serve them back offline.
+///
+/// Each response is stored as `<endpoint>/<params>.json`, e.g.
+/// `ISteamUserStats/GetPlayerAchievements/v1/appid-570_l-english_steamid-7656.json`.
+/// The `key` parameter is never part of the file name. Non-retryable HTTP
+/// errors (such as "Requested app has no stats") are stored next to it as
+/// `<params>.<status>.json` so they replay as the same error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recording {
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+/// Writes the outcome of a request to the recording directory, with secrets
+/// masked in both the file name and the body.
+pub(crate) fn record(
+    dir: &Path,
+    base_url: &str,
+    url: &str,
+    result: &Result<String>,
+    redactor: &Redactor,
+) -> Result<()> {
+    let (endpoint, stem) = response_key(base_url, &redactor.redact(url));
+    let endpoint_dir = dir.join(endpoint);
+
+    let (path, body) = match result {
+        Ok(body) => (endpoint_dir.join(format!("{}.json", stem)), body.as_str()),
+        Err(e) => match e.downcast_ref::<SteamApiError>() {
+            Some(SteamApiError::ApiError { status, message }) => (
+                endpoint_dir.join(format!("{}.{}.json", stem, status)),
+                message.as_str(),
+            ),
+            // Network failures and rate limits say nothing about the account.
+            _ => return Ok(()),
+        },
+    };
+
+    fs::create_dir_all(&endpoint_dir).with_context(|| {
+        format!(
+            "Failed to create recording directory: {}",
+            endpoint_dir.display()
+        )
+    })?;
+    fs::write(&path, redactor.redact(body))
+        .with_context(|| format!("Failed to write recording: {}", path.display()))
+}
+
+/// Returns the recorded response for `url`, or the recorded HTTP error.
+pub(crate) fn replay(dir: &Path, base_url: &str, url: &str) -> Result<String> {
+    let (endpoint, stem) = response_key(base_url, url);
+    let endpoint_dir = dir.join(&endpoint);
+
+    let path = endpoint_dir.join(format!("{}.json", stem));
+    if path.exists() {
+        return fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read recording: {}", path.display()));
+    }
+
+    if let Some((status, path)) = find_recorded_error(&endpoint_dir, &stem) {
+        let message = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read recording: {}", path.display()))?;
+        return Err(SteamApiError::ApiError { status, message }.into());
+    }
+
+    anyhow::bail!(
+        "No recorded response for {}/{} in {}",
+        endpoint,
+        stem,
+        dir.display()
+    )
+}
+
+/// Steam ID of the player recorded in `dir`, if exactly one. The player
+/// summary is checked first, then the owned games list (`games` records
+/// only that).
+pub fn recorded_steam_id(dir: &Path) -> Option<String> {
+    recorded_param(dir, "ISteamUser/GetPlayerSummaries/v2", "steamids-")
+        .or_else(|| recorded_param(dir, "IPlayerService/GetOwnedGames/v1", "steamid-"))
+}
+
+fn recorded_param(dir: &Path, endpoint: &str, prefix: &str) -> Option<String> {
+    let entries = fs::read_dir(dir.join(endpoint)).ok()?;
+    let mut ids = entries.filter_map(|entry| {
+        let name = entry.ok()?.file_name().into_string().ok()?;
+        let stem = name.strip_suffix(".json")?;
+        stem.split('_')
+            .find_map(|param| param.strip_prefix(prefix))
+            .map(str::to_string)
+    });
+    let id = ids.next()?;
+    ids.next().is_none().then_some(id)
+}
+
+fn find_recorded_error(endpoint_dir: &Path, stem: &str) -> Option<(u16, PathBuf)> {
+    let prefix = format!("{}.", stem);
+    fs::read_dir(endpoint_dir).ok()?.find_map(|entry| {
+        let path = entry.ok()?.path();
+        let name = path.file_name()?.to_str()?;
+        let status = name.strip_prefix(&prefix)?.strip_suffix(".json")?;
+        Some((status.parse().ok()?, path))
+    })
+}
+
+/// Splits `url` into an endpoint directory and a file stem built from the
+/// sorted query parameters, with the API key dropped.
+fn response_key(base_url: &str, url: &str) -> (String, String) {
+    let relative = url.strip_prefix(base_url).unwrap_or(url);
+    let (path, query) = relative.split_once('?').unwrap_or((relative, ""));
+
+    let endpoint = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(sanitize)
+        .collect::<Vec<_>>()
+        .join("/");
+
+    let mut params: Vec<String> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter_map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (name != "key").then(|| format!("{}-{}", sanitize(name), sanitize(value)))
+        })
+        .collect();
+    params.sort();
+
+    let stem = if params.is_empty() {
+        "index".to_string()
+    } else {
+        params.join("_")
+    };
+    (endpoint, stem)
+}
+
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' => c,
+            _ => '+',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_path;
+
+    const BASE: &str = "https://api.steampowered.com";
+
+    #[test]
+    fn test_response_key_strips_api_key_and_sorts_params() {
+        let url = format!(
+            "{}/ISteamUserStats/GetPlayerAchievements/v1/?key=SECRET&steamid=7656&appid=570&l=english",
+            BASE
+        );
+        let (endpoint, stem) = response_key(BASE, &url);
+        assert_eq!(endpoint, "ISteamUserStats/GetPlayerAchievements/v1");
+        assert_eq!(stem, "appid-570_l-english_steamid-7656");
+        assert!(!stem.contains("SECRET"));
+    }
+
+    #[test]
+    fn test_response_key_sanitizes_separators() {
+        let url = format!(
+            "{}/IPlayerService/GetOwnedGames/v1/?key=k&appids_filter[0]=10&x=../..",
+            BASE
+        );
+        let (endpoint, stem) = response_key(BASE, &url);
+        assert_eq!(endpoint, "IPlayerService/GetOwnedGames/v1");
+        assert_eq!(stem, "appids+filter+0+-10_x-+++++");
+        assert!(!stem.contains('/'));
+    }
+
+    #[test]
+    fn test_response_key_without_params_uses_index() {
+        let (endpoint, stem) = response_key(BASE, &format!("{}/Foo/Bar/v1/", BASE));
+        assert_eq!(endpoint, "Foo/Bar/v1");
+        assert_eq!(stem, "index");
+    }
+
+    #[test]
+    fn test_record_then_replay_round_trips_body() {
+        let dir = unique_temp_path("round-trip");
+        let url = format!(
+            "{}/ISteamUser/GetPlayerSummaries/v2/?key=k&steamids=42",
+            BASE
+        );
+
+        record(
+            &dir,
+            BASE,
+            &url,
+            &Ok("{\"ok\":true}".to_string()),
+            &Redactor::default(),
+        )
+        .unwrap();
+        let other_key = url.replace("key=k", "key=other");
+        assert_eq!(replay(&dir, BASE, &other_key).unwrap(), "{\"ok\":true}");
+        assert_eq!(recorded_steam_id(&dir).as_deref(), Some("42"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_record_then_replay_round_trips_http_error() {
+        let dir = unique_temp_path("error");
+        let url = format!(
+            "{}/ISteamUserStats/GetPlayerAchievements/v1/?key=k&steamid=1&appid=2",
+            BASE
+        );
+        let error = SteamApiError::ApiError {
+            status: 400,
+            message: "Requested app has no stats".to_string(),
+        };
+
+        record(&dir, BASE, &url, &Err(error.into()), &Redactor::default()).unwrap();
+        let err = replay(&dir, BASE, &url).expect_err("recorded error should replay");
+        match err.downcast_ref::<SteamApiError>() {
+            Some(SteamApiError::ApiError { status, message }) => {
+                assert_eq!(*status, 400);
+                assert_eq!(message, "Requested app has no stats");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_record_masks_redacted_steam_id() {
+        let dir = unique_temp_path("redacted");
+        let url = format!(
+            "{}/ISteamUser/GetPlayerSummaries/v2/?key=k&steamids=7656",
+            BASE
+        );
+        let redactor = Redactor::new("k-secret").with_secret("7656");
+
+        let body = r#"{"players":[{"steamid":"7656","personaname":"p"}]}"#;
+        record(&dir, BASE, &url, &Ok(body.to_string()), &redactor).unwrap();
+
+        assert_eq!(recorded_steam_id(&dir).as_deref(), Some("REDACTED"));
+        let replay_url = url.replace("7656", "REDACTED");
+        let replayed = replay(&dir, BASE, &replay_url).unwrap();
+        assert!(!replayed.contains("7656"));
+        assert!(replayed.contains("\"steamid\":\"REDACTED\""));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_record_skips_transient_errors() {
+        let dir = unique_temp_path("transient");
+        let url = format!("{}/Foo/Bar/v1/?a=1", BASE);
+
+        record(
+            &dir,
+            BASE,
+            &url,
+            &Err(SteamApiError::Timeout.into()),
+            &Redactor::default(),
+        )
+        .unwrap();
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_replay_errors_when_response_missing() {
+        let dir = unique_temp_path("missing");
+        let err = replay(&dir, BASE, &format!("{}/Foo/Bar/v1/?a=1", BASE))
+            .expect_err("missing recording should error");
+        assert!(err
+            .to_string()
+            .contains("No recorded response for Foo/Bar/v1/a-1"));
+    }
+
+    #[test]
+    fn test_recorded_steam_id_requires_single_player() {
+        let dir = unique_temp_path("steam-id");
+        assert!(recorded_steam_id(&dir).is_none());
+
+        for id in ["1", "2"] {
+            let url = format!("{}/ISteamUser/GetPlayerSummaries/v2/?steamids={}", BASE, id);
+            record(&dir, BASE, &url, &Ok(String::new()), &Redactor::default()).unwrap();
+        }
+        assert!(recorded_steam_id(&dir).is_none());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_recorded_steam_id_falls_back_to_owned_games() {
+        let dir = unique_temp_path("owned-games");
+        let url = format!(
+            "{}/IPlayerService/GetOwnedGames/v1/?key=k&steamid=42&include_appinfo=1",
+            BASE
+        );
+        record(&dir, BASE, &url, &Ok(String::new()), &Redactor::default()).unwrap();
+        assert_eq!(recorded_steam_id(&dir).as_deref(), Some("42"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,105 @@
+use super::error::SteamApiError;
+
+/// Replacement for secrets in logs, errors and recordings.
+pub const MASK: &str = "REDACTED";
+
+/// Masks the API key (and optionally the Steam ID) in any text that leaves
+/// the client: verbose lines, error messages and recorded responses.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    secrets: Vec<String>,
+}
+
+impl Redactor {
+    pub fn new(api_key: &str) -> Self {
+        Self::default().with_secret(api_key)
+    }
+
+    /// Also masks `secret`. Empty values are ignored.
+    pub fn with_secret(mut self, secret: &str) -> Self {
+        if !secret.is_empty() && !self.secrets.iter().any(|s| s == secret) {
+            self.secrets.push(secret.to_string());
+            // Longest first so a secret containing another is masked whole.
+            self.secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        }
+        self
+    }
+
+    pub fn redact(&self, text: &str) -> String {
+        self.secrets
+            .iter()
+            .fold(text.to_string(), |text, secret| text.replace(secret, MASK))
+    }
+
+    /// Redacts the free-form text carried by `error`.
+    pub fn redact_error(&self, error: SteamApiError) -> SteamApiError {
+        match error {
+            SteamApiError::NetworkError(message) => {
+                SteamApiError::NetworkError(self.redact(&message))
+            }
+            SteamApiError::ApiError { status, message } => SteamApiError::ApiError {
+                status,
+                message: self.redact(&message),
+            },
+            other => other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_masks_every_occurrence() {
+        let redactor = Redactor::new("SECRET");
+        assert_eq!(
+            redactor.redact("url?key=SECRET&x=SECRET"),
+            "url?key=REDACTED&x=REDACTED"
+        );
+    }
+
+    #[test]
+    fn test_redact_masks_optional_steam_id() {
+        let redactor = Redactor::new("SECRET").with_secret("7656");
+        assert_eq!(
+            redactor.redact("key=SECRET&steamid=7656"),
+            "key=REDACTED&steamid=REDACTED"
+        );
+    }
+
+    #[test]
+    fn test_redact_ignores_empty_secret() {
+        let redactor = Redactor::new("");
+        assert_eq!(redactor.redact("unchanged"), "unchanged");
+    }
+
+    #[test]
+    fn test_redact_prefers_longer_overlapping_secret() {
+        let redactor = Redactor::new("ABC").with_secret("ABCDEF");
+        assert_eq!(redactor.redact("ABCDEF ABC"), "REDACTED REDACTED");
+    }
+
+    #[test]
+    fn test_redact_error_masks_messages() {
+        let redactor = Redactor::new("SECRET");
+        let network = redactor.redact_error(SteamApiError::NetworkError(
+            "error sending request for url (https://x/?key=SECRET)".to_string(),
+        ));
+        assert!(!network.to_string().contains("SECRET"));
+
+        let api = redactor.redact_error(SteamApiError::ApiError {
+            status: 403,
+            message: "bad key SECRET".to_string(),
+        });
+        assert_eq!(
+            api.to_string(),
+            "Steam API error (HTTP 403): bad key REDACTED"
+        );
+
+        assert!(matches!(
+            redactor.redact_error(SteamApiError::Timeout),
+            SteamApiError::Timeout
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+use anyhow::{bail, Result};
+
+/// A node of Valve's KeyValues text format, as used by `loginusers.vdf`,
+/// `config.vdf` and `appmanifest_*.acf`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Vdf {
+    Value(String),
+    Section(Vec<(String, Vdf)>),
+}
+
+impl Vdf {
+    /// Child of a section by key. Keys are case-insensitive, as in Steam.
+    pub fn get(&self, key: &str) -> Option<&Vdf> {
+        self.entries()
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+    }
+
+    /// Follows `path` through nested sections.
+    pub fn get_path(&self, path: &[&str]) -> Option<&Vdf> {
+        path.iter().try_fold(self, |node, key| node.get(key))
+    }
+
+    /// String value of a child, if it is a value rather than a section.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(Vdf::as_str)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Vdf::Value(value) => Some(value),
+            Vdf::Section(_) => None,
+        }
+    }
+
+    /// Children of a section, or nothing for a value.
+    pub fn entries(&self) -> &[(String, Vdf)] {
+        match self {
+            Vdf::Section(entries) => entries,
+            Vdf::Value(_) => &[],
+        }
+    }
+}
+
+/// Parses a KeyValues document into its root key and section.
+pub fn parse(text: &str) -> Result<(String, Vdf)> {
+    let mut tokens = Tokenizer::new(text);
+    let key = match tokens.next()? {
+        Some(Token::Str(key)) => key,
+        Some(token) => bail!("line {}: expected a key, found {}", tokens.line, token),
+        None => bail!("empty VDF document"),
+    };
+    let value = parse_value(&mut tokens)?;
+    if let Some(token) = tokens.next()? {
+        bail!("line {}: unexpected {} after root", tokens.line, token);
+    }
+    Ok((key, value))
+}
+
+fn parse_value(tokens: &mut Tokenizer) -> Result<Vdf> {
+    match tokens.next()? {
+        Some(Token::Str(value)) => Ok(Vdf::Value(value)),
+        Some(Token::Open) => parse_section(tokens),
+        Some(Token::Close) => bail!("line {}: unexpected '}}'", tokens.line),
+        None => bail!("unexpected end of VDF document"),
+    }
+}
+
+fn parse_section(tokens: &mut Tokenizer) -> Result<Vdf> {
+    let mut entries = Vec::new();
+    loop {
+        match tokens.next()? {
+            Some(Token::Str(key)) => entries.push((key, parse_value(tokens)?)),
+            Some(Token::Close) => return Ok(Vdf::Section(entries)),
+            Some(Token::Open) => bail!("line {}: expected a key, found '{{'", tokens.line),
+            None => bail!("unterminated section at end of VDF document"),
+        }
+    }
+}
+
+enum Token {
+    Str(String),
+    Open,
+    Close,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Str(s) => write!(f, "\"{}\"", s),
+            Token::Open => write!(f, "'{{'"),
+            Token::Close => write!(f, "'}}'"),
+        }
+    }
+}
+
+struct Tokenizer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            chars: text.chars().peekable(),
+            line: 1,
+        }
+    }
+
+    fn next(&mut self) -> Result<Option<Token>> {
+        loop {
+            let Some(c) = self.chars.next() else {
+                return Ok(None);
+            };
+            match c {
+                '\n' => self.line += 1,
+                c if c.is_whitespace() => {}
+                '/' if self.chars.peek() == Some(&'/') => self.skip_line(),
+                // Platform conditionals such as `[$WIN32]` are ignored.
+                '[' => self.skip_until(']'),
+                '{' => return Ok(Some(Token::Open)),
+                '}' => return Ok(Some(Token::Close)),
+                '"' => return self.quoted().map(|s| Some(Token::Str(s))),
+                c => return Ok(Some(Token::Str(self.bare(c)))),
+            }
+        }
+    }
+
+    fn quoted(&mut self) -> Result<String> {
+        let mut value = String::new();
+        while let Some(c) = self.chars.next() {
+            match c {
+                '"' => return Ok(value),
+                '\\' => match self.chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some(other) => value.push(other),
+                    None => break,
+                },
+                c => {
+                    if c == '\n' {
+                        self.line += 1;
+                    }
+                    value.push(c);
+                }
+            }
+        }
+        bail!("line {}: unterminated string", self.line)
+    }
+
+    fn bare(&mut self, first: char) -> String {
+        let mut value = first.to_string();
+        while let Some(&c) = self.chars.peek() {
+            if c.is_whitespace() || matches!(c, '"' | '{' | '}') {
+                break;
+            }
+            value.push(c);
+            self.chars.next();
+        }
+        value
+    }
+
+    fn skip_line(&mut self) {
+        self.skip_until('\n');
+        self.line += 1;
+    }
+
+    fn skip_until(&mut self, end: char) {
+        for c in self.chars.by_ref() {
+            if c == end {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOGINUSERS: &str = r#""users"
+{
+	"76561197960287930"
+	{
+		"AccountName"		"gabelogannewell"
+		"PersonaName"		"Rabscuttle"
+		"RememberPassword"		"1"
+		"MostRecent"		"1"
+		"Timestamp"		"1700000000"
+	}
+	"76561197960265728"
+	{
+		"AccountName"		"other"
+		"PersonaName"		"Other \"quoted\" name"
+		"MostRecent"		"0"
+	}
+}
+"#;
+
+    #[test]
+    fn test_parse_loginusers_sample() {
+        let (root, users) = parse(LOGINUSERS).unwrap();
+        assert_eq!(root, "users");
+        assert_eq!(users.entries().len(), 2);
+
+        let user = users.get("76561197960287930").unwrap();
+        assert_eq!(user.get_str("PersonaName"), Some("Rabscuttle"));
+        assert_eq!(user.get_str("mostrecent"), Some("1"));
+        assert_eq!(
+            users.get_path(&["76561197960265728", "PersonaName"]),
+            Some(&Vdf::Value("Other \"quoted\" name".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_skips_comments_conditionals_and_bare_tokens() {
+        let text = r#"// generated
+"AppState" { appid 570 // Dota
+  "name" "Dota 2" [$WIN32]
+  "nested" { } }"#;
+        let (root, state) = parse(text).unwrap();
+        assert_eq!(root, "AppState");
+        assert_eq!(state.get_str("appid"), Some("570"));
+        assert_eq!(state.get_str("name"), Some("Dota 2"));
+        assert_eq!(state.get("nested"), Some(&Vdf::Section(vec![])));
+        assert!(state.get_str("nested").is_none());
+    }
+
+    #[test]
+    fn test_parse_reports_malformed_documents() {
+        for (text, expected) in [
+            ("", "empty VDF document"),
+            ("\"a\" {\n\"b\" \"c\"", "unterminated section"),
+            ("\"a\" \"b", "unterminated string"),
+            ("\"a\"", "unexpected end"),
+            ("\"a\" {\n{", "line 2: expected a key"),
+            ("\"a\" {} \"b\"", "after root"),
+            ("}", "expected a key"),
+        ] {
+            let err = parse(text).expect_err(text).to_string();
+            assert!(err.contains(expected), "{:?}: {}", text, err);
+        }
+    }
+
+    #[test]
+    fn test_value_has_no_entries() {
+        let value = Vdf::Value("x".to_string());
+        assert!(value.entries().is_empty());
+        assert!(value.get("x").is_none());
+        assert_eq!(value.get_path(&[]), Some(&value));
+    }
+}
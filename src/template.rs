@@ -0,0 +1,648 @@
+//! `--template`: text with placeholders over the stats, for prompts, status
+//! lines and bots.
+//!
+//! `{games}` and `{top_games[0].name}` insert values, `{#if rarest}..{#else}..{/if}`
+//! and `{#each top_games}..{/each}` branch and loop, and `{{`/`}}` are
+//! literal braces. See [`context`] for the available fields.
+
+use anyhow::{Context, Result};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::display::{account_age_years, format_bytes, format_number, format_playtime};
+use crate::games::format_date;
+use crate::steam::{GameCompletion, GameStat, RarestAchievement, SteamStats};
+
+/// A value a placeholder can refer to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A missing optional value; renders as nothing.
+    Null,
+    Text(String),
+    /// A number with the text it renders as, e.g. `1234` as `1,234`.
+    Number(f64, String),
+    List(Vec<Value>),
+    Map(BTreeMap<&'static str, Value>),
+}
+
+impl Value {
+    fn count(n: u32) -> Self {
+        Value::Number(n as f64, format_number(n))
+    }
+
+    fn percent(pct: f64, decimals: usize) -> Self {
+        Value::Number(pct, format!("{:.*}", decimals, pct))
+    }
+
+    fn signed(n: i64) -> Self {
+        Value::Number(n as f64, format!("{:+}", n))
+    }
+
+    fn text(s: impl Into<String>) -> Self {
+        Value::Text(s.into())
+    }
+
+    fn map<const N: usize>(fields: [(&'static str, Value); N]) -> Self {
+        Value::Map(BTreeMap::from(fields))
+    }
+
+    fn truthy(&self) -> bool {
+        match self {
+            Value::Null => false,
+            Value::Text(s) => !s.is_empty(),
+            Value::Number(n, _) => *n != 0.0,
+            Value::List(items) => !items.is_empty(),
+            Value::Map(_) => true,
+        }
+    }
+}
+
+/// Every field a template can use:
+///
+/// - `username`, `games`, `unplayed`, `unplayed_pct`, `playtime` (`12h 30m`),
+///   `playtime_hours`, `playtime_minutes`, `level`, `member_years`, `member_since`
+/// - `achievements.{achieved,total,pct,perfect}`
+/// - `rarest.{name,game,percent}` and the list `rarest_unlocks`
+/// - the lists `top_games` and `recent_games`, each `{name,playtime,playtime_hours,playtime_minutes}`
+/// - the list `near_complete`, each `{name,achieved,total,left}`
+/// - `installed.{games,size}`
+/// - `history.{since,playtime,achievements,games,level,week_playtime}`
+///
+/// Optional values that are missing are empty and false in `{#if}`.
+pub fn context(stats: &SteamStats) -> Value {
+    let optional = |value: Option<Value>| value.unwrap_or(Value::Null);
+    let unplayed_pct = if stats.game_count > 0 {
+        stats.unplayed_count as f64 / stats.game_count as f64 * 100.0
+    } else {
+        0.0
+    };
+    let achievements = stats.achievement_stats.as_ref();
+
+    Value::map([
+        ("username", Value::text(&stats.username)),
+        ("games", Value::count(stats.game_count)),
+        ("unplayed", Value::count(stats.unplayed_count)),
+        ("unplayed_pct", Value::percent(unplayed_pct, 0)),
+        (
+            "playtime",
+            Value::text(format_playtime(stats.total_playtime_minutes)),
+        ),
+        ("playtime_hours", Value::count(stats.playtime_hours())),
+        (
+            "playtime_minutes",
+            Value::count(stats.total_playtime_minutes),
+        ),
+        ("level", optional(stats.steam_level.map(Value::count))),
+        (
+            "member_years",
+            optional(
+                stats
+                    .account_created
+                    .map(|created| Value::count(account_age_years(created))),
+            ),
+        ),
+        (
+            "member_since",
+            optional(stats.account_created.map(|c| Value::text(format_date(c)))),
+        ),
+        (
+            "achievements",
+            optional(achievements.map(|a| {
+                let pct = if a.total_possible > 0 {
+                    a.total_achieved as f64 / a.total_possible as f64 * 100.0
+                } else {
+                    0.0
+                };
+                Value::map([
+                    ("achieved", Value::count(a.total_achieved)),
+                    ("total", Value::count(a.total_possible)),
+                    ("pct", Value::percent(pct, 0)),
+                    ("perfect", Value::count(a.perfect_games)),
+                ])
+            })),
+        ),
+        (
+            "rarest",
+            optional(achievements.and_then(|a| a.rarest.as_ref()).map(rarest)),
+        ),
+        (
+            "rarest_unlocks",
+            Value::List(
+                achievements
+                    .map(|a| a.rarest_unlocks.iter().map(rarest).collect())
+                    .unwrap_or_default(),
+            ),
+        ),
+        (
+            "top_games",
+            Value::List(stats.top_games.iter().map(game).collect()),
+        ),
+        (
+            "recent_games",
+            Value::List(stats.recently_played.iter().map(game).collect()),
+        ),
+        (
+            "near_complete",
+            Value::List(
+                achievements
+                    .map(|a| a.near_complete.iter().map(completion).collect())
+                    .unwrap_or_default(),
+            ),
+        ),
+        (
+            "installed",
+            optional(stats.installed.as_ref().map(|i| {
+                Value::map([
+                    ("games", Value::count(i.game_count)),
+                    ("size", Value::text(format_bytes(i.total_bytes))),
+                ])
+            })),
+        ),
+        (
+            "history",
+            optional(stats.history.as_ref().map(|h| {
+                Value::map([
+                    ("since", Value::text(format_date(h.since))),
+                    ("playtime", Value::text(format_playtime(h.playtime_minutes))),
+                    ("achievements", optional(h.achievements.map(Value::signed))),
+                    ("games", Value::signed(h.games)),
+                    ("level", optional(h.steam_level.map(Value::signed))),
+                    (
+                        "week_playtime",
+                        Value::text(format_playtime(h.week_playtime_minutes)),
+                    ),
+                ])
+            })),
+        ),
+    ])
+}
+
+fn game(game: &GameStat) -> Value {
+    Value::map([
+        ("name", Value::text(&game.name)),
+        (
+            "playtime",
+            Value::text(format_playtime(game.playtime_minutes)),
+        ),
+        ("playtime_hours", Value::count(game.playtime_hours())),
+        ("playtime_minutes", Value::count(game.playtime_minutes)),
+    ])
+}
+
+fn rarest(achievement: &RarestAchievement) -> Value {
+    Value::map([
+        ("name", Value::text(&achievement.name)),
+        ("game", Value::text(&achievement.game)),
+        ("percent", Value::percent(achievement.percent, 1)),
+    ])
+}
+
+fn completion(game: &GameCompletion) -> Value {
+    Value::map([
+        ("name", Value::text(&game.name)),
+        ("achieved", Value::count(game.achieved)),
+        ("total", Value::count(game.total)),
+        ("left", Value::count(game.remaining())),
+    ])
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// A dotted field path such as `top_games[0].name`.
+#[derive(Debug, Clone, PartialEq)]
+struct FieldPath {
+    raw: String,
+    segments: Vec<Segment>,
+}
+
+impl FieldPath {
+    fn parse(raw: &str) -> Result<Self> {
+        let invalid = || anyhow::anyhow!("Invalid field '{}' in template", raw);
+        let mut segments = Vec::new();
+        for part in raw.split('.') {
+            let (key, mut rest) = part.split_once('[').map_or((part, ""), |(k, r)| (k, r));
+            let valid_key = !key.is_empty()
+                && key
+                    .strip_prefix('@')
+                    .unwrap_or(key)
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid_key {
+                return Err(invalid());
+            }
+            segments.push(Segment::Key(key.to_string()));
+            while !rest.is_empty() {
+                let (index, after) = rest.split_once(']').ok_or_else(invalid)?;
+                segments.push(Segment::Index(index.parse().map_err(|_| invalid())?));
+                rest = match after {
+                    "" => "",
+                    _ => after.strip_prefix('[').ok_or_else(invalid)?,
+                };
+            }
+        }
+        Ok(Self {
+            raw: raw.to_string(),
+            segments,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Field(FieldPath),
+    If {
+        negate: bool,
+        condition: FieldPath,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Each {
+        list: FieldPath,
+        body: Vec<Node>,
+    },
+}
+
+/// A parsed template; parse once and render against any stats.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+/// Where a nested block ended, for checking that tags are balanced.
+enum Closer {
+    End,
+    Else,
+    EndIf,
+    EndEach,
+}
+
+impl Template {
+    /// A template file, or else the template text itself.
+    pub fn load(file_or_text: &str) -> Result<Self> {
+        let path = Path::new(file_or_text);
+        if path.is_file() {
+            let text = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read template file: {}", path.display()))?;
+            return Self::parse(&text)
+                .with_context(|| format!("Invalid template file: {}", path.display()));
+        }
+        Self::parse(file_or_text)
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut tokens = tokenize(text)?.into_iter();
+        let (nodes, closer) = parse_block(&mut tokens)?;
+        match closer {
+            Closer::End => Ok(Self { nodes }),
+            Closer::Else => anyhow::bail!("{{#else}} without {{#if}} in template"),
+            Closer::EndIf => anyhow::bail!("{{/if}} without {{#if}} in template"),
+            Closer::EndEach => anyhow::bail!("{{/each}} without {{#each}} in template"),
+        }
+    }
+
+    pub fn render(&self, stats: &SteamStats) -> Result<String> {
+        let root = context(stats);
+        let mut out = String::new();
+        render_nodes(&self.nodes, &mut vec![Scope::root(&root)], &mut out)?;
+        Ok(out)
+    }
+}
+
+enum Token {
+    Text(String),
+    Tag(String),
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut literal = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut tag = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some('{') | None => {
+                            anyhow::bail!(
+                                "Unclosed '{{{}' in template (use {{{{ for a literal brace)",
+                                tag
+                            )
+                        }
+                        Some(c) => tag.push(c),
+                    }
+                }
+                if !literal.is_empty() {
+                    tokens.push(Token::Text(std::mem::take(&mut literal)));
+                }
+                tokens.push(Token::Tag(tag.trim().to_string()));
+            }
+            '}' => anyhow::bail!("Unmatched '}}' in template (use }}}} for a literal brace)"),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        tokens.push(Token::Text(literal));
+    }
+    Ok(tokens)
+}
+
+fn parse_block(tokens: &mut impl Iterator<Item = Token>) -> Result<(Vec<Node>, Closer)> {
+    let mut nodes = Vec::new();
+    while let Some(token) = tokens.next() {
+        let tag = match token {
+            Token::Text(text) => {
+                nodes.push(Node::Text(text));
+                continue;
+            }
+            Token::Tag(tag) => tag,
+        };
+        match tag.as_str() {
+            "#else" => return Ok((nodes, Closer::Else)),
+            "/if" => return Ok((nodes, Closer::EndIf)),
+            "/each" => return Ok((nodes, Closer::EndEach)),
+            _ => {}
+        }
+        if let Some(condition) = tag.strip_prefix("#if ") {
+            let condition = condition.trim();
+            let (negate, condition) = match condition.strip_prefix('!') {
+                Some(rest) => (true, rest.trim()),
+                None => (false, condition),
+            };
+            let (then, closer) = parse_block(tokens)?;
+            let otherwise = match closer {
+                Closer::EndIf => Vec::new(),
+                Closer::Else => match parse_block(tokens)? {
+                    (otherwise, Closer::EndIf) => otherwise,
+                    _ => anyhow::bail!("{{#if {}}} is missing {{/if}}", condition),
+                },
+                _ => anyhow::bail!("{{#if {}}} is missing {{/if}}", condition),
+            };
+            nodes.push(Node::If {
+                negate,
+                condition: FieldPath::parse(condition)?,
+                then,
+                otherwise,
+            });
+        } else if let Some(list) = tag.strip_prefix("#each ") {
+            let list = list.trim();
+            let body = match parse_block(tokens)? {
+                (body, Closer::EndEach) => body,
+                _ => anyhow::bail!("{{#each {}}} is missing {{/each}}", list),
+            };
+            nodes.push(Node::Each {
+                list: FieldPath::parse(list)?,
+                body,
+            });
+        } else if tag.starts_with('#') || tag.starts_with('/') {
+            anyhow::bail!(
+                "Unknown tag '{{{}}}' in template (expected #if, #else, /if, #each or /each)",
+                tag
+            );
+        } else {
+            nodes.push(Node::Field(FieldPath::parse(&tag)?));
+        }
+    }
+    Ok((nodes, Closer::End))
+}
+
+/// The root fields, or one item of an `{#each}` loop.
+struct Scope<'a> {
+    value: &'a Value,
+    index: usize,
+    len: usize,
+}
+
+impl<'a> Scope<'a> {
+    fn root(value: &'a Value) -> Self {
+        Self {
+            value,
+            index: 0,
+            len: 1,
+        }
+    }
+}
+
+fn render_nodes<'a>(nodes: &[Node], scopes: &mut Vec<Scope<'a>>, out: &mut String) -> Result<()> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Field(path) => match lookup(path, scopes)?.as_ref() {
+                Value::Null => {}
+                Value::Text(text) | Value::Number(_, text) => out.push_str(text),
+                Value::List(_) => {
+                    anyhow::bail!("'{}' is a list; loop over it with {{#each}}", path.raw)
+                }
+                Value::Map(fields) => anyhow::bail!(
+                    "'{}' has fields: {}",
+                    path.raw,
+                    fields.keys().copied().collect::<Vec<_>>().join(", ")
+                ),
+            },
+            Node::If {
+                negate,
+                condition,
+                then,
+                otherwise,
+            } => {
+                let branch = if lookup(condition, scopes)?.truthy() != *negate {
+                    then
+                } else {
+                    otherwise
+                };
+                render_nodes(branch, scopes, out)?;
+            }
+            Node::Each { list, body } => {
+                let items: &'a [Value] = match lookup(list, scopes)? {
+                    Cow::Borrowed(Value::List(items)) => items,
+                    Cow::Borrowed(Value::Null) | Cow::Owned(Value::Null) => &[],
+                    _ => anyhow::bail!("'{}' is not a list", list.raw),
+                };
+                for (index, item) in items.iter().enumerate() {
+                    scopes.push(Scope {
+                        value: item,
+                        index,
+                        len: items.len(),
+                    });
+                    let result = render_nodes(body, scopes, out);
+                    scopes.pop();
+                    result?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Resolves `path` in the innermost scope that has its first field.
+/// `@index` (from 0), `@number` (from 1), `@first` and `@last` describe
+/// the current loop item.
+fn lookup<'a>(path: &FieldPath, scopes: &[Scope<'a>]) -> Result<Cow<'a, Value>> {
+    let Some(Segment::Key(first)) = path.segments.first() else {
+        unreachable!("field paths start with a key");
+    };
+    let innermost = scopes.last().expect("the root scope is always present");
+    let loop_value = match first.as_str() {
+        "@index" => Some(Value::Number(
+            innermost.index as f64,
+            innermost.index.to_string(),
+        )),
+        "@number" => Some(Value::count(innermost.index as u32 + 1)),
+        "@first" => Some(flag(innermost.index == 0)),
+        "@last" => Some(flag(innermost.index + 1 == innermost.len)),
+        name if name.starts_with('@') => {
+            anyhow::bail!("Unknown loop field '{}' in template", path.raw)
+        }
+        _ => None,
+    };
+    if let Some(value) = loop_value {
+        return Ok(Cow::Owned(value));
+    }
+
+    let scope = scopes
+        .iter()
+        .rev()
+        .find(|s| matches!(s.value, Value::Map(fields) if fields.contains_key(first.as_str())))
+        .ok_or_else(|| anyhow::anyhow!("Unknown field '{}' in template", path.raw))?;
+    let mut value = scope.value;
+    for segment in &path.segments {
+        value = match (value, segment) {
+            (Value::Null, _) => return Ok(Cow::Owned(Value::Null)),
+            (Value::Map(fields), Segment::Key(key)) => {
+                fields.get(key.as_str()).ok_or_else(|| {
+                    anyhow::anyhow!("Unknown field '{}' in template ('{}')", key, path.raw)
+                })?
+            }
+            (Value::List(items), Segment::Index(i)) => match items.get(*i) {
+                Some(item) => item,
+                None => return Ok(Cow::Owned(Value::Null)),
+            },
+            (_, Segment::Key(key)) => {
+                anyhow::bail!("'{}' has no field '{}'", path.raw, key)
+            }
+            (_, Segment::Index(_)) => {
+                anyhow::bail!("'{}' indexes something that is not a list", path.raw)
+            }
+        };
+    }
+    Ok(Cow::Borrowed(value))
+}
+
+fn flag(on: bool) -> Value {
+    Value::Number(f64::from(u8::from(on)), on.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(template: &str) -> String {
+        Template::parse(template)
+            .unwrap()
+            .render(&crate::demo_stats())
+            .unwrap()
+    }
+
+    fn render_err(template: &str) -> String {
+        Template::parse(template)
+            .and_then(|t| t.render(&crate::demo_stats()))
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn test_placeholders_use_display_formatting() {
+        assert_eq!(
+            render("{username}: {games} games, {playtime_hours}h, {achievements.pct}%"),
+            "unhappychoice: 486 games, 2,847h, 55%"
+        );
+        assert_eq!(
+            render("{top_games[0].name} ({top_games[0].playtime})"),
+            "Borderlands 3 (478h 0m)"
+        );
+        assert_eq!(
+            render("{rarest.percent}% {installed.size}"),
+            "0.1% 612.4 GB"
+        );
+        assert_eq!(render("{history.games} {history.playtime}"), "+2 2h 30m");
+        assert_eq!(render("{{literal}} {top_games[9].name}."), "{literal} .");
+    }
+
+    #[test]
+    fn test_conditionals_and_loops() {
+        assert_eq!(
+            render("{#each top_games}{@number}. {name}{#if !@last}, {/if}{/each}"),
+            "1. Borderlands 3, 2. Coin Push RPG, 3. DRG Survivor"
+        );
+        assert_eq!(
+            render("{#each recent_games}[{username}/{name}]{/each}"),
+            "[unhappychoice/Elden Ring][unhappychoice/Hades II]"
+        );
+        assert_eq!(render("{#if rarest}yes{#else}no{/if}"), "yes");
+
+        let mut stats = crate::demo_stats();
+        stats.achievement_stats = None;
+        stats.recently_played.clear();
+        let template =
+            Template::parse("{#if rarest}{rarest.name}{#else}none{/if}|{#each recent_games}x{/each}|{achievements.pct}")
+                .unwrap();
+        assert_eq!(template.render(&stats).unwrap(), "none||");
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_problem() {
+        let parse_err = |t: &str| Template::parse(t).unwrap_err().to_string();
+        assert!(parse_err("{games").contains("Unclosed"));
+        assert!(parse_err("games}").contains("Unmatched"));
+        assert!(parse_err("{#if rarest}x").contains("missing {/if}"));
+        assert!(parse_err("{#each top_games}x{/if}").contains("missing {/each}"));
+        assert!(parse_err("{/each}").contains("without"));
+        assert!(parse_err("{#unless rarest}").contains("Unknown tag"));
+        assert!(parse_err("{top_games[x]}").contains("Invalid field"));
+        assert!(parse_err("{top games}").contains("Invalid field"));
+    }
+
+    #[test]
+    fn test_render_errors_on_unknown_or_misused_fields() {
+        assert!(render_err("{gmaes}").contains("Unknown field 'gmaes'"));
+        assert!(render_err("{achievements.pcent}").contains("pcent"));
+        assert!(render_err("{top_games}").contains("#each"));
+        assert!(render_err("{achievements}").contains("achieved"));
+        assert!(render_err("{#each games}{/each}").contains("not a list"));
+        assert!(render_err("{@count}").contains("@count"));
+    }
+
+    #[test]
+    fn test_load_reads_files_or_takes_text() {
+        let path = std::env::temp_dir().join(format!(
+            "steamfetch-template-test-{}.txt",
+            std::process::id()
+        ));
+        fs::write(&path, "{username}\n").unwrap();
+        let from_file = Template::load(path.to_str().unwrap()).unwrap();
+        assert_eq!(
+            from_file.render(&crate::demo_stats()).unwrap(),
+            "unhappychoice\n"
+        );
+        let _ = fs::remove_file(&path);
+
+        let inline = Template::load("{games}").unwrap();
+        assert_eq!(inline.render(&crate::demo_stats()).unwrap(), "486");
+    }
+}
@@ -1,8 +1,12 @@
 #![cfg(test)]
 
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 static ENV_LOCK: Mutex<()> = Mutex::new(());
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
 // Serialize env-mutating tests across the whole crate. A single shared
 // mutex prevents distinct module-local mutexes from racing on the same
@@ -12,6 +16,30 @@ pub fn lock_env() -> MutexGuard<'static, ()> {
     ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner())
 }
 
+// A path under the system temp dir that no other test, call or run uses.
+// Nothing is created there; tests remove whatever they write.
+pub fn unique_temp_path(label: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let n = TEMP_COUNTER.fetch_add(1, Ordering::SeqCst);
+    std::env::temp_dir().join(format!(
+        "steamfetch-test-{}-{}-{}-{}",
+        label,
+        std::process::id(),
+        nanos,
+        n
+    ))
+}
+
+// Like `unique_temp_path`, but creates the directory.
+pub fn unique_temp_dir(label: &str) -> PathBuf {
+    let dir = unique_temp_path(label);
+    std::fs::create_dir_all(&dir).expect("temp dir should be created");
+    dir
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,4 +57,12 @@ mod tests {
         assert!(result.is_err());
         let _guard = lock_env();
     }
+
+    #[test]
+    fn unique_temp_paths_differ_between_calls() {
+        assert_ne!(unique_temp_path("same"), unique_temp_path("same"));
+        let dir = unique_temp_dir("created");
+        assert!(dir.is_dir());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
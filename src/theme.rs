@@ -0,0 +1,407 @@
+//! Colors and glyphs of the terminal output and the SVG card, from the
+//! `[theme]` config table.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use unicode_width::UnicodeWidthStr;
+
+use crate::color;
+
+/// Names accepted by `[theme] name` and `--theme`.
+pub const BUILTIN_THEMES: [&str; 4] = ["default", "monochrome", "steam-classic", "deck"];
+
+/// A 24-bit color, written `#rrggbb` in config files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub fn parse(value: &str) -> Result<Self> {
+        let hex = value.strip_prefix('#').unwrap_or(value);
+        let channel = |i: usize| {
+            hex.get(i..i + 2)
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+        };
+        match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(Self(r, g, b)),
+            _ => anyhow::bail!("'{}' is not a #rrggbb color", value),
+        }
+    }
+
+    /// The color `t` (0.0..=1.0) of the way from `self` to `other`.
+    pub fn lerp(self, other: Rgb, t: f64) -> Rgb {
+        let mix =
+            |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t.clamp(0.0, 1.0)).round() as u8;
+        Rgb(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+
+    /// Every channel scaled by `factor`, capped at 255.
+    pub fn scale(self, factor: f64) -> Rgb {
+        let scale = |c: u8| (c as f64 * factor).min(255.0) as u8;
+        Rgb(scale(self.0), scale(self.1), scale(self.2))
+    }
+}
+
+impl From<(u8, u8, u8)> for Rgb {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Self(r, g, b)
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rgb {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Rgb::parse(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Resolved colors and glyphs used by [`crate::display`] and [`crate::card`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub logo: Rgb,
+    /// When set, the logo fades from `logo` at the top to this at the bottom.
+    pub logo_gradient: Option<Rgb>,
+    /// The `user@Steam` header and chart bars; None uses the terminal's cyan.
+    pub accent: Option<Rgb>,
+    /// Stat labels and section headings; None keeps the terminal's text color.
+    pub label: Option<Rgb>,
+    /// Title colors from the lowest tier to the highest; empty keeps each
+    /// tier's own color.
+    pub title_palette: Vec<Rgb>,
+    /// How much darker the end of a title is than its start (0.0 is flat).
+    pub title_fade: f64,
+    /// Repeated to draw the line under the header.
+    pub separator: String,
+    /// Background of `steamfetch card`; the terminal keeps its own. None
+    /// draws a dark card.
+    pub background: Option<Rgb>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            logo: Rgb(0x1b, 0x4b, 0x67),
+            logo_gradient: None,
+            accent: None,
+            label: None,
+            title_palette: Vec::new(),
+            title_fade: 0.4,
+            separator: "─".to_string(),
+            background: None,
+        }
+    }
+}
+
+impl Theme {
+    pub fn builtin(name: &str) -> Option<Self> {
+        let theme = match name {
+            "default" => Self::default(),
+            "monochrome" => Self {
+                logo: Rgb(0x9a, 0x9a, 0x9a),
+                accent: Some(Rgb(0xe0, 0xe0, 0xe0)),
+                title_palette: vec![Rgb(0xd0, 0xd0, 0xd0)],
+                ..Self::default()
+            },
+            "steam-classic" => Self {
+                logo: Rgb(0x4c, 0x58, 0x44),
+                logo_gradient: Some(Rgb(0x8c, 0x96, 0x7d)),
+                accent: Some(Rgb(0xc4, 0xb5, 0x50)),
+                label: Some(Rgb(0xd8, 0xde, 0xd3)),
+                title_palette: vec![Rgb(0xa0, 0xaa, 0x95), Rgb(0xc4, 0xb5, 0x50)],
+                title_fade: 0.25,
+                separator: "═".to_string(),
+                background: Some(Rgb(0x3e, 0x46, 0x37)),
+            },
+            "deck" => Self {
+                logo: Rgb(0x1a, 0x9f, 0xff),
+                logo_gradient: Some(Rgb(0x0e, 0x14, 0x1b)),
+                accent: Some(Rgb(0x1a, 0x9f, 0xff)),
+                label: Some(Rgb(0xff, 0xff, 0xff)),
+                title_palette: vec![
+                    Rgb(0x8b, 0x92, 0x9a),
+                    Rgb(0x1a, 0x9f, 0xff),
+                    Rgb(0xff, 0x85, 0x00),
+                ],
+                title_fade: 0.3,
+                separator: "━".to_string(),
+                background: Some(Rgb(0x23, 0x26, 0x2e)),
+            },
+            _ => return None,
+        };
+        Some(theme)
+    }
+
+    /// A built-in theme name, or else the path of a theme file.
+    pub fn load(name_or_path: &str) -> Result<Self> {
+        if let Some(theme) = Self::builtin(name_or_path) {
+            return Ok(theme);
+        }
+        let path = Path::new(name_or_path);
+        if !path.exists() {
+            anyhow::bail!(
+                "Unknown theme '{}' (built-in themes: {})",
+                name_or_path,
+                BUILTIN_THEMES.join(", ")
+            );
+        }
+        ThemeConfig::from_file(path)?.resolve(path.parent())
+    }
+
+    /// Color of logo row `row` out of `rows`.
+    pub fn logo_color(&self, row: usize, rows: usize) -> Rgb {
+        match self.logo_gradient {
+            Some(end) => self
+                .logo
+                .lerp(end, row as f64 / rows.saturating_sub(1).max(1) as f64),
+            None => self.logo,
+        }
+    }
+
+    /// Title color for tier `tier` of `tiers`, given the tier's own color.
+    pub fn title_color(&self, tier: usize, tiers: usize, own: Rgb) -> Rgb {
+        match self.title_palette.as_slice() {
+            [] => own,
+            [only] => *only,
+            palette => {
+                let position = tier as f64 / tiers.saturating_sub(1).max(1) as f64
+                    * (palette.len() - 1) as f64;
+                let start = (position.floor() as usize).min(palette.len() - 2);
+                palette[start].lerp(palette[start + 1], position - start as f64)
+            }
+        }
+    }
+
+    pub fn accent(&self, text: &str) -> String {
+        match self.accent {
+            Some(color) => color::paint(text, color),
+            None => text.cyan().to_string(),
+        }
+    }
+
+    /// Bold, in the label color if the theme has one.
+    pub fn label(&self, text: &str) -> String {
+        let bold = text.bold().to_string();
+        match self.label {
+            Some(color) => color::paint(&bold, color),
+            None => bold,
+        }
+    }
+
+    /// The separator repeated to fill about `width` columns.
+    pub fn separator_line(&self, width: usize) -> String {
+        repeat_to_width(&self.separator, width)
+    }
+}
+
+/// `glyph` repeated to fill about `width` columns, at least once.
+pub fn repeat_to_width(glyph: &str, width: usize) -> String {
+    glyph.repeat((width / glyph.width().max(1)).max(1))
+}
+
+/// The `[theme]` table, or a theme file with the same keys. Unset keys
+/// come from the `name`d built-in theme, then from `file`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub name: Option<String>,
+    pub file: Option<PathBuf>,
+    pub logo: Option<Rgb>,
+    pub logo_gradient: Option<Rgb>,
+    pub accent: Option<Rgb>,
+    pub label: Option<Rgb>,
+    pub title_palette: Option<Vec<Rgb>>,
+    pub title_fade: Option<f64>,
+    pub separator: Option<String>,
+    pub background: Option<Rgb>,
+}
+
+impl ThemeConfig {
+    fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read theme file: {}", path.display()))?;
+        let config: Self = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse theme file: {}", path.display()))?;
+        if config.file.is_some() {
+            anyhow::bail!("Theme file {} cannot set `file`", path.display());
+        }
+        Ok(config)
+    }
+
+    /// Relative `file` paths are resolved against `base_dir`, the directory
+    /// of the file this table came from.
+    pub fn resolve(self, base_dir: Option<&Path>) -> Result<Theme> {
+        let from_file = match &self.file {
+            Some(file) => {
+                let path = match base_dir {
+                    Some(dir) if file.is_relative() => dir.join(file),
+                    _ => file.clone(),
+                };
+                Some(Self::from_file(&path)?)
+            }
+            None => None,
+        };
+        let name = self
+            .name
+            .as_deref()
+            .or(from_file.as_ref().and_then(|f| f.name.as_deref()))
+            .unwrap_or("default");
+        let mut theme = Theme::builtin(name).with_context(|| {
+            format!(
+                "Unknown theme '{}' (built-in themes: {})",
+                name,
+                BUILTIN_THEMES.join(", ")
+            )
+        })?;
+        if let Some(from_file) = from_file {
+            from_file.apply(&mut theme)?;
+        }
+        self.apply(&mut theme)?;
+        Ok(theme)
+    }
+
+    fn apply(self, theme: &mut Theme) -> Result<()> {
+        if let Some(fade) = self.title_fade {
+            if !(0.0..=1.0).contains(&fade) {
+                anyhow::bail!("title_fade must be between 0.0 and 1.0, got {}", fade);
+            }
+            theme.title_fade = fade;
+        }
+        if let Some(separator) = self.separator {
+            if separator.is_empty() {
+                anyhow::bail!("separator must not be empty");
+            }
+            theme.separator = separator;
+        }
+        theme.logo = self.logo.unwrap_or(theme.logo);
+        theme.logo_gradient = self.logo_gradient.or(theme.logo_gradient);
+        theme.accent = self.accent.or(theme.accent);
+        theme.label = self.label.or(theme.label);
+        theme.background = self.background.or(theme.background);
+        if let Some(palette) = self.title_palette {
+            theme.title_palette = palette;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::unique_temp_dir;
+
+    #[test]
+    fn test_rgb_parse_accepts_hex_with_or_without_hash() {
+        assert_eq!(Rgb::parse("#1b4b67").unwrap(), Rgb(27, 75, 103));
+        assert_eq!(Rgb::parse("FFFFFF").unwrap(), Rgb(255, 255, 255));
+        assert!(Rgb::parse("#fff").is_err());
+        assert!(Rgb::parse("#gg0000").is_err());
+        assert!(Rgb::parse("#ééé").is_err());
+        assert_eq!(Rgb(27, 75, 103).to_string(), "#1b4b67");
+    }
+
+    #[test]
+    fn test_every_builtin_theme_resolves() {
+        for name in BUILTIN_THEMES {
+            assert!(Theme::builtin(name).is_some(), "{name}");
+        }
+        assert_eq!(Theme::builtin("default"), Some(Theme::default()));
+        assert!(Theme::builtin("neon").is_none());
+        assert!(Theme::load("neon")
+            .unwrap_err()
+            .to_string()
+            .contains("deck"));
+    }
+
+    #[test]
+    fn test_logo_color_fades_from_top_to_bottom() {
+        let theme = Theme {
+            logo: Rgb(0, 0, 0),
+            logo_gradient: Some(Rgb(100, 200, 50)),
+            ..Theme::default()
+        };
+        assert_eq!(theme.logo_color(0, 3), Rgb(0, 0, 0));
+        assert_eq!(theme.logo_color(1, 3), Rgb(50, 100, 25));
+        assert_eq!(theme.logo_color(2, 3), Rgb(100, 200, 50));
+        assert_eq!(Theme::default().logo_color(5, 18), Rgb(27, 75, 103));
+    }
+
+    #[test]
+    fn test_title_color_spreads_palette_across_tiers() {
+        let own = Rgb(1, 2, 3);
+        assert_eq!(Theme::default().title_color(4, 10, own), own);
+
+        let theme = Theme {
+            title_palette: vec![Rgb(0, 0, 0), Rgb(200, 0, 0), Rgb(200, 200, 0)],
+            ..Theme::default()
+        };
+        assert_eq!(theme.title_color(0, 5, own), Rgb(0, 0, 0));
+        assert_eq!(theme.title_color(1, 5, own), Rgb(100, 0, 0));
+        assert_eq!(theme.title_color(2, 5, own), Rgb(200, 0, 0));
+        assert_eq!(theme.title_color(4, 5, own), Rgb(200, 200, 0));
+    }
+
+    #[test]
+    fn test_separator_line_fills_width_in_columns() {
+        assert_eq!(Theme::default().separator_line(3), "───");
+        let theme = Theme {
+            separator: "-=".to_string(),
+            ..Theme::default()
+        };
+        assert_eq!(theme.separator_line(5), "-=-=");
+    }
+
+    #[test]
+    fn test_theme_config_layers_inline_keys_over_file_over_builtin() {
+        let dir = unique_temp_dir("layers");
+        fs::write(
+            dir.join("mine.toml"),
+            "name = \"deck\"\naccent = \"#ff0000\"\nseparator = \"~\"\n",
+        )
+        .unwrap();
+        let config: ThemeConfig =
+            toml::from_str("file = \"mine.toml\"\nseparator = \"=\"\ntitle_fade = 0.0").unwrap();
+
+        let theme = config.resolve(Some(&dir)).unwrap();
+        let deck = Theme::builtin("deck").unwrap();
+        assert_eq!(theme.logo, deck.logo);
+        assert_eq!(theme.background, deck.background);
+        assert_eq!(theme.accent, Some(Rgb(255, 0, 0)));
+        assert_eq!(theme.separator, "=");
+        assert_eq!(theme.title_fade, 0.0);
+
+        let loaded = Theme::load(dir.join("mine.toml").to_str().unwrap()).unwrap();
+        assert_eq!(loaded.separator, "~");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_theme_config_rejects_bad_values() {
+        let bad = |toml: &str| {
+            toml::from_str::<ThemeConfig>(toml)
+                .map_err(anyhow::Error::from)
+                .and_then(|c| c.resolve(None))
+                .is_err()
+        };
+        assert!(bad("name = \"neon\""));
+        assert!(bad("logo = \"blue\""));
+        assert!(bad("title_fade = 1.5"));
+        assert!(bad("separator = \"\""));
+        assert!(bad("colour = \"#ffffff\""));
+        assert!(bad("file = \"/nonexistent/steamfetch-theme.toml\""));
+        assert!(!bad("name = \"monochrome\"\nlabel = \"#c0c0c0\""));
+    }
+}
@@ -60,6 +60,96 @@ fn demo_flag_renders_demo_profile_without_config() {
     assert!(stdout.contains("Top Played"));
 }
 
+#[test]
+fn demo_color_flag_picks_escape_depth() {
+    let run = |args: &[&str], env: &[(&str, &str)]| {
+        let output = Command::new(binary())
+            .arg("--demo")
+            .args(args)
+            .env_remove("COLORTERM")
+            .env_remove("NO_COLOR")
+            .envs(env.iter().copied())
+            .output()
+            .expect("steamfetch should run");
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).expect("stdout should be utf8")
+    };
+
+    // Piped stdout is not a TTY, so auto turns colors off.
+    assert!(!run(&[], &[]).contains("\x1b["));
+    assert!(!run(&["--color", "never"], &[("COLORTERM", "truecolor")]).contains("\x1b["));
+
+    let truecolor = run(&["--color", "always"], &[("COLORTERM", "truecolor")]);
+    assert!(truecolor.contains("\x1b[38;2;27;75;103m"));
+
+    let ansi256 = run(&["--color", "always"], &[("TERM", "xterm-256color")]);
+    assert!(ansi256.contains("\x1b[38;5;23m"));
+    assert!(!ansi256.contains("\x1b[38;2;"));
+
+    let ansi16 = run(&["--color", "always"], &[("TERM", "linux")]);
+    assert!(ansi16.contains("\x1b[90m"));
+    assert!(!ansi16.contains("\x1b[38;"));
+}
+
+#[test]
+fn demo_logo_flag_loads_logo_file() {
+    let root = unique_temp_root("logo");
+    std::fs::create_dir_all(&root).unwrap();
+    let logo = root.join("logo.txt");
+    std::fs::write(&logo, "$1[]$2[]\n$2<>\n").unwrap();
+
+    let output = Command::new(binary())
+        .arg("--demo")
+        .arg("--logo")
+        .arg(&logo)
+        .output()
+        .expect("steamfetch should run");
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(output.status.success());
+
+    // The stats start three columns right of the four-column logo.
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[1].trim_end(), "[][]");
+    assert_eq!(lines[2], "<>     unhappychoice@Steam");
+    assert!(lines[5].starts_with("       Level:"));
+
+    let output = Command::new(binary())
+        .args(["--demo", "--logo", "arch"])
+        .output()
+        .expect("steamfetch should run");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("built-in logos"));
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn demo_with_config_uses_its_module_layout() {
+    let root = unique_temp_root("layout");
+    std::fs::create_dir_all(&root).unwrap();
+    let config = root.join("layout.toml");
+    std::fs::write(
+        &config,
+        "[display]\nmodules = [{ type = \"games\", title = false }, \"playtime\"]\n",
+    )
+    .unwrap();
+
+    let output = Command::new(binary())
+        .args(["--demo", "--logo", "none", "--config"])
+        .arg(&config)
+        .output()
+        .expect("steamfetch should run");
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    assert!(output.status.success());
+
+    let lines: Vec<&str> = stdout.lines().filter(|l| !l.is_empty()).collect();
+    assert_eq!(lines.len(), 2, "{stdout}");
+    assert_eq!(lines[0], "Games:        486");
+    assert!(lines[1].starts_with("Playtime:     2,847h"));
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
 #[test]
 fn invalid_config_exits_before_network_request() {
     let root = unique_temp_root("invalid-config");
@@ -83,3 +173,485 @@ fn invalid_config_exits_before_network_request() {
 
     let _ = std::fs::remove_dir_all(&root);
 }
+
+#[test]
+fn demo_template_renders_file_and_rejects_unknown_fields_early() {
+    let root = unique_temp_root("template");
+    std::fs::create_dir_all(&root).unwrap();
+    let template = root.join("motd.txt");
+    std::fs::write(
+        &template,
+        "{username}: {games} games\n{#each top_games}{@number}. {name}\n{/each}",
+    )
+    .unwrap();
+
+    let output = Command::new(binary())
+        .arg("--demo")
+        .arg("--template")
+        .arg(&template)
+        .output()
+        .expect("steamfetch should run");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "unhappychoice: 486 games\n1. Borderlands 3\n2. Coin Push RPG\n3. DRG Survivor\n"
+    );
+
+    // Without credentials this would fail on the missing API key instead.
+    let output = Command::new(binary())
+        .args(["--template", "{playtime_hours}h {gmaes}"])
+        .env("XDG_CONFIG_HOME", &root)
+        .env("HOME", &root)
+        .env_remove("STEAM_API_KEY")
+        .output()
+        .expect("steamfetch should run");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown field 'gmaes'"));
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn demo_markdown_and_html_formats_print_tables() {
+    let output = Command::new(binary())
+        .args(["--demo", "--format", "md"])
+        .output()
+        .expect("steamfetch should run");
+    assert!(output.status.success());
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.starts_with("## unhappychoice@Steam\n\n| Stat | Value | Title |\n"));
+    assert!(!text.contains('\x1b'));
+
+    let output = Command::new(binary())
+        .args(["--demo", "--format", "html"])
+        .output()
+        .expect("steamfetch should run");
+    assert!(output.status.success());
+    let html = String::from_utf8(output.stdout).unwrap();
+    assert!(html.starts_with("<section class=\"steamfetch\">"));
+    assert!(html.contains("<td class=\"title\" style=\"color: #"));
+
+    let output = Command::new(binary())
+        .args(["--format", "html", "history"])
+        .output()
+        .expect("steamfetch should run");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("only supported for the summary"));
+}
+
+#[test]
+fn demo_card_writes_snapshot_svg() {
+    let root = unique_temp_root("card");
+    std::fs::create_dir_all(&root).unwrap();
+    let out = root.join("card.svg");
+
+    let output = Command::new(binary())
+        .args(["--demo", "card", "--out"])
+        .arg(&out)
+        .output()
+        .expect("steamfetch should run");
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        std::fs::read_to_string(&out).unwrap(),
+        include_str!("snapshots/card-demo.svg")
+    );
+
+    let output = Command::new(binary())
+        .args(["--demo", "--format", "json", "card"])
+        .output()
+        .expect("steamfetch should run");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("takes no --format"));
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn demo_flag_with_json_format_prints_versioned_document() {
+    let output = Command::new(binary())
+        .args(["--demo", "--format", "json"])
+        .output()
+        .expect("steamfetch should run");
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    let value: serde_json::Value =
+        serde_json::from_str(&stdout).expect("stdout should be a single JSON document");
+
+    assert!(output.status.success(), "stderr: {stderr}");
+    assert_eq!(value["schema_version"], 1);
+    assert_eq!(value["username"], "unhappychoice");
+    assert!(!stdout.contains("\x1b["));
+}
+
+// Minimal plain-HTTP stand-in for the Steam Web API. Routes are matched by
+// path prefix; unmatched requests get a 404. Serves until the test exits.
+fn spawn_mock_steam_api(routes: &'static [(&'static str, u16, &'static str)]) -> String {
+    use std::io::{Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind mock API");
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => request.extend_from_slice(&buf[..n]),
+                }
+            }
+            let request = String::from_utf8_lossy(&request);
+            let path = request.split_whitespace().nth(1).unwrap_or("");
+            let (status, body) = routes
+                .iter()
+                .find(|(prefix, _, _)| path.starts_with(prefix))
+                .map(|(_, status, body)| (*status, *body))
+                .unwrap_or((404, ""));
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+        }
+    });
+    base_url
+}
+
+const MOCK_ROUTES: &[(&str, u16, &str)] = &[
+    (
+        "/ISteamUser/GetPlayerSummaries/v2/",
+        200,
+        r#"{"response":{"players":[{"personaname":"recorded-user","timecreated":1500000000}]}}"#,
+    ),
+    (
+        "/IPlayerService/GetOwnedGames/v1/",
+        200,
+        r#"{"response":{"game_count":2,"games":[{"appid":10,"name":"Alpha","playtime_forever":600,"rtime_last_played":5},{"appid":20,"name":"Beta","playtime_forever":0}]}}"#,
+    ),
+    (
+        "/IPlayerService/GetSteamLevel/v1/",
+        200,
+        r#"{"response":{"player_level":7}}"#,
+    ),
+    (
+        "/IPlayerService/GetRecentlyPlayedGames/v1/",
+        200,
+        r#"{"response":{"games":[{"appid":10,"name":"Alpha","playtime_forever":600,"playtime_2weeks":30}]}}"#,
+    ),
+    (
+        "/ISteamUserStats/GetPlayerAchievements/v1/?key=secret-key&steamid=765&appid=10&",
+        200,
+        r#"{"playerstats":{"achievements":[{"apiname":"WIN","achieved":1,"name":"Winner"},{"apiname":"LOSE","achieved":0,"name":"Loser"}]}}"#,
+    ),
+    (
+        "/ISteamUserStats/GetPlayerAchievements/v1/?key=secret-key&steamid=765&appid=20&",
+        400,
+        r#"{"playerstats":{"error":"Requested app has no stats","success":false}}"#,
+    ),
+    (
+        "/ISteamUserStats/GetGlobalAchievementPercentagesForApp/v2/?gameid=10",
+        200,
+        r#"{"achievementpercentages":{"achievements":[{"name":"WIN","percent":3.5}]}}"#,
+    ),
+];
+
+fn run_json(root: &Path, args: &[&std::ffi::OsStr]) -> serde_json::Value {
+    let output = Command::new(binary())
+        .args(["--format", "json"])
+        .args(args)
+        .env("XDG_CONFIG_HOME", root.join("config"))
+        .env("XDG_CACHE_HOME", root.join("cache"))
+        .env("XDG_DATA_HOME", root.join("data"))
+        .env("HOME", root)
+        .env("STEAM_API_KEY", "secret-key")
+        .env("STEAM_ID", "765")
+        .output()
+        .expect("steamfetch should run");
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(output.status.success(), "stderr: {stderr}");
+    serde_json::from_str(&stdout).expect("stdout should be JSON")
+}
+
+fn files_under(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            files.extend(files_under(&path));
+        } else {
+            files.push(path);
+        }
+    }
+    files
+}
+
+#[test]
+fn record_then_replay_reproduces_stats_offline() {
+    let root = unique_temp_root("record-replay");
+    std::fs::create_dir_all(&root).unwrap();
+    let recording = root.join("recording");
+    let base_url = spawn_mock_steam_api(MOCK_ROUTES);
+
+    let recorded = run_json(
+        &root,
+        &[
+            "--api-base-url".as_ref(),
+            base_url.as_ref(),
+            "--record".as_ref(),
+            recording.as_os_str(),
+        ],
+    );
+    assert_eq!(recorded["username"], "recorded-user");
+    assert_eq!(recorded["achievement_stats"]["total_achieved"], 1);
+    assert_eq!(recorded["achievement_stats"]["failed_games"], 0);
+
+    let files = files_under(&recording);
+    assert!(!files.is_empty());
+    for file in &files {
+        let contents = std::fs::read_to_string(file).unwrap();
+        assert!(!file.to_string_lossy().contains("secret-key"));
+        assert!(!contents.contains("secret-key"));
+    }
+
+    // The mock is not consulted: replay points at an unreachable base URL
+    // and carries no credentials.
+    let output = Command::new(binary())
+        .args(["--format", "json", "--replay"])
+        .arg(&recording)
+        .args(["--api-base-url", "http://127.0.0.1:9"])
+        .env("XDG_CONFIG_HOME", root.join("config"))
+        .env("XDG_CACHE_HOME", root.join("cache"))
+        .env("HOME", &root)
+        .env_remove("STEAM_API_KEY")
+        .env_remove("STEAM_ID")
+        .output()
+        .expect("steamfetch should run");
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert!(output.status.success(), "stderr: {stderr}");
+    let replayed: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+
+    assert_eq!(recorded, replayed);
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+const SECRET_KEY: &str = "SUPERSECRETKEY0123456789";
+// Long enough not to collide with ports or timings in verbose output.
+const SECRET_STEAM_ID: &str = "76561197960287930";
+
+fn run_verbose_with_secret_key(base_url: &str, extra_args: &[&str]) -> (bool, String) {
+    let root = unique_temp_root("redaction");
+    std::fs::create_dir_all(&root).unwrap();
+    let output = Command::new(binary())
+        .args(["--verbose", "--format", "json", "--api-base-url", base_url])
+        .args(extra_args)
+        .env("XDG_CONFIG_HOME", root.join("config"))
+        .env("XDG_CACHE_HOME", root.join("cache"))
+        .env("HOME", &root)
+        .env("STEAM_API_KEY", SECRET_KEY)
+        .env("STEAM_ID", SECRET_STEAM_ID)
+        .output()
+        .expect("steamfetch should run");
+    let _ = std::fs::remove_dir_all(&root);
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    (output.status.success(), stderr)
+}
+
+#[test]
+fn verbose_error_paths_never_print_api_key() {
+    const SERVER_ERROR: &[(&str, u16, &str)] = &[("/", 500, "internal error")];
+    const FORBIDDEN: &[(&str, u16, &str)] = &[("/", 403, "Forbidden")];
+    const ECHOES_KEY: &[(&str, u16, &str)] = &[(
+        "/",
+        400,
+        "Bad request: key SUPERSECRETKEY0123456789 rejected",
+    )];
+    const MALFORMED: &[(&str, u16, &str)] = &[("/", 200, "{not json")];
+
+    let unreachable = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    };
+    let cases = [
+        ("unreachable", unreachable),
+        ("server error", spawn_mock_steam_api(SERVER_ERROR)),
+        ("forbidden", spawn_mock_steam_api(FORBIDDEN)),
+        ("echoes key", spawn_mock_steam_api(ECHOES_KEY)),
+        ("malformed", spawn_mock_steam_api(MALFORMED)),
+    ];
+
+    for (name, base_url) in cases {
+        let (success, stderr) = run_verbose_with_secret_key(&base_url, &[]);
+        assert!(!success, "{name}: expected failure");
+        assert!(stderr.contains("[verbose]"), "{name}: stderr: {stderr}");
+        assert!(
+            !stderr.contains(SECRET_KEY),
+            "{name}: API key leaked to stderr: {stderr}"
+        );
+    }
+}
+
+#[test]
+fn verbose_success_path_can_mask_steam_id() {
+    const ROUTES: &[(&str, u16, &str)] = &[
+        (
+            "/ISteamUser/GetPlayerSummaries/v2/",
+            200,
+            r#"{"response":{"players":[{"steamid":"76561197960287930","personaname":"masked"}]}}"#,
+        ),
+        (
+            "/IPlayerService/GetOwnedGames/v1/",
+            200,
+            r#"{"response":{"game_count":0,"games":[]}}"#,
+        ),
+        ("/", 400, r#"{"playerstats":{"error":"nothing here"}}"#),
+    ];
+    let base_url = spawn_mock_steam_api(ROUTES);
+
+    let (success, stderr) = run_verbose_with_secret_key(&base_url, &["--redact-steam-id"]);
+    assert!(success, "stderr: {stderr}");
+    assert!(stderr.contains("REDACTED"), "stderr: {stderr}");
+    assert!(!stderr.contains(SECRET_KEY), "stderr: {stderr}");
+    assert!(!stderr.contains(SECRET_STEAM_ID), "stderr: {stderr}");
+}
+
+#[test]
+fn games_subcommand_lists_filtered_library_as_json() {
+    let root = unique_temp_root("games");
+    std::fs::create_dir_all(&root).unwrap();
+    let base_url = spawn_mock_steam_api(MOCK_ROUTES);
+
+    let all = run_json(
+        &root,
+        &[
+            "--api-base-url".as_ref(),
+            base_url.as_ref(),
+            "games".as_ref(),
+            "--sort".as_ref(),
+            "name".as_ref(),
+        ],
+    );
+    assert_eq!(all["schema_version"], 1);
+    assert_eq!(all["game_count"], 2);
+    assert_eq!(all["games"][0]["name"], "Alpha");
+    assert_eq!(all["games"][0]["last_played"], 5);
+    assert_eq!(all["games"][1]["name"], "Beta");
+    assert!(all["games"][1]["last_played"].is_null());
+
+    let unplayed = run_json(
+        &root,
+        &[
+            "--api-base-url".as_ref(),
+            base_url.as_ref(),
+            "games".as_ref(),
+            "--filter".as_ref(),
+            "unplayed".as_ref(),
+        ],
+    );
+    assert_eq!(unplayed["games"].as_array().map(Vec::len), Some(1));
+    assert_eq!(unplayed["games"][0]["appid"], 20);
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn achievements_subcommand_resolves_game_by_name() {
+    let root = unique_temp_root("achievements");
+    std::fs::create_dir_all(&root).unwrap();
+    let base_url = spawn_mock_steam_api(MOCK_ROUTES);
+
+    let details = run_json(
+        &root,
+        &[
+            "--api-base-url".as_ref(),
+            base_url.as_ref(),
+            "achievements".as_ref(),
+            "alp".as_ref(),
+        ],
+    );
+    assert_eq!(details["schema_version"], 1);
+    assert_eq!(details["appid"], 10);
+    assert_eq!(details["game"], "Alpha");
+    assert_eq!(details["unlocked_count"], 1);
+    assert_eq!(details["total_count"], 2);
+    assert_eq!(details["achievements"][0]["name"], "Winner");
+    assert_eq!(details["achievements"][0]["global_percent"], 3.5);
+    assert_eq!(details["achievements"][1]["name"], "Loser");
+    assert!(details["achievements"][1]["global_percent"].is_null());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn second_run_reports_changes_since_the_first() {
+    let root = unique_temp_root("history");
+    let config_dir = root.join("config/steamfetch");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("config.toml"),
+        "[display]\nshow_history = true\n",
+    )
+    .unwrap();
+    let base_url = spawn_mock_steam_api(MOCK_ROUTES);
+    let args: [&std::ffi::OsStr; 2] = ["--api-base-url".as_ref(), base_url.as_ref()];
+
+    let first = run_json(&root, &args);
+    assert!(first["history"].is_null());
+
+    let second = run_json(&root, &args);
+    let history = &second["history"];
+    assert!(history["since"].as_u64().is_some());
+    assert_eq!(history["playtime_minutes"], 0);
+    assert_eq!(history["games"], 0);
+    assert_eq!(history["achievements"], 0);
+
+    let lines = std::fs::read_to_string(root.join("data/steamfetch/history.jsonl")).unwrap();
+    assert_eq!(lines.lines().count(), 1, "unchanged runs are not appended");
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn history_subcommand_reports_saved_snapshots_offline() {
+    let root = unique_temp_root("history-report");
+    let dir = root.join("data/steamfetch");
+    std::fs::create_dir_all(&dir).unwrap();
+    let snapshots = [
+        r#"{"timestamp":1704067200,"game_count":1,"total_playtime_minutes":60,"steam_level":3,"achievements_unlocked":5,"games":[{"appid":10,"name":"Alpha","playtime_minutes":60}]}"#,
+        r#"{"timestamp":1704153600,"game_count":2,"total_playtime_minutes":150,"steam_level":3,"achievements_unlocked":7,"games":[{"appid":10,"name":"Alpha","playtime_minutes":150},{"appid":20,"name":"Beta","playtime_minutes":0}]}"#,
+    ];
+    std::fs::write(dir.join("history.jsonl"), snapshots.join("\n")).unwrap();
+
+    let report = run_json(
+        &root,
+        &["history".as_ref(), "--group-by".as_ref(), "day".as_ref()],
+    );
+    assert_eq!(report["group_by"], "day");
+    assert_eq!(report["snapshot_count"], 2);
+    let period = &report["periods"][0];
+    assert_eq!(period["label"], "2024-01-02");
+    assert_eq!(period["playtime_minutes"], 90);
+    assert_eq!(period["achievements"], 2);
+    assert_eq!(period["games"][0]["name"], "Alpha");
+    assert_eq!(period["new_games"][0]["appid"], 20);
+
+    let output = Command::new(binary())
+        .args(["history", "--since", "2024-01-02", "--until", "2024-01-01"])
+        .env("XDG_DATA_HOME", root.join("data"))
+        .env("HOME", &root)
+        .output()
+        .expect("steamfetch should run");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("--since must not be later than --until")
+    );
+
+    let _ = std::fs::remove_dir_all(&root);
+}